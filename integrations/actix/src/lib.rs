@@ -23,6 +23,7 @@ use hydration_context::SsrSharedContext;
 use leptos::{
     config::LeptosOptions,
     context::{provide_context, use_context},
+    persist::RequestCookies,
     prelude::expect_context,
     reactive::{computed::ScopedFuture, owner::Owner},
     IntoView,
@@ -33,6 +34,7 @@ use leptos_integration_utils::{
 use leptos_meta::ServerMetaContext;
 use leptos_router::{
     components::provide_server_redirect,
+    i18n::LocalePreferences,
     location::RequestUrl,
     static_routes::{RegenerationFn, ResolvedStaticPath},
     ExpandOptionals, Method, PathSegment, RouteList, RouteListing, SsrMode,
@@ -761,6 +763,17 @@ fn provide_contexts(
     provide_context(RequestUrl::new(&path));
     provide_context(meta_context.clone());
     provide_context(res_options.clone());
+    let header_str = |name| {
+        req.headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+    };
+    provide_context(LocalePreferences {
+        accept_language: header_str(header::ACCEPT_LANGUAGE),
+        cookie: header_str(header::COOKIE),
+    });
+    provide_context(RequestCookies(header_str(header::COOKIE)));
     provide_context(req);
     provide_server_redirect(redirect);
     leptos::nonce::provide_nonce();
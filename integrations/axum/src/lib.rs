@@ -53,6 +53,7 @@ use hydration_context::SsrSharedContext;
 use leptos::{
     config::LeptosOptions,
     context::{provide_context, use_context},
+    persist::RequestCookies,
     prelude::*,
     reactive::{computed::ScopedFuture, owner::Owner},
     IntoView,
@@ -64,9 +65,9 @@ use leptos_meta::ServerMetaContext;
 #[cfg(feature = "default")]
 use leptos_router::static_routes::ResolvedStaticPath;
 use leptos_router::{
-    components::provide_server_redirect, location::RequestUrl,
-    static_routes::RegenerationFn, ExpandOptionals, PathSegment, RouteList,
-    RouteListing, SsrMode,
+    components::provide_server_redirect, i18n::LocalePreferences,
+    location::RequestUrl, static_routes::RegenerationFn, ExpandOptionals,
+    PathSegment, RouteList, RouteListing, SsrMode,
 };
 #[cfg(feature = "default")]
 use once_cell::sync::Lazy;
@@ -926,12 +927,24 @@ fn provide_contexts(
 ) {
     provide_context(RequestUrl::new(path));
     provide_context(meta_context.clone());
+    provide_context(LocalePreferences {
+        accept_language: header_str(&parts.headers, header::ACCEPT_LANGUAGE),
+        cookie: header_str(&parts.headers, header::COOKIE),
+    });
+    provide_context(RequestCookies(header_str(&parts.headers, header::COOKIE)));
     provide_context(parts);
     provide_context(default_res_options);
     provide_server_redirect(redirect);
     leptos::nonce::provide_nonce();
 }
 
+fn header_str(headers: &HeaderMap, name: HeaderName) -> Option<String> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+}
+
 /// Returns an Axum [Handler](axum::handler::Handler) that listens for a `GET` request and tries
 /// to route it using [leptos_router], asynchronously rendering an HTML page after all
 /// `async` resources have loaded.
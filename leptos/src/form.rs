@@ -5,6 +5,7 @@ use serde::de::DeserializeOwned;
 use server_fn::{
     client::Client, codec::PostUrl, request::ClientReq, ServerFn, ServerFnError,
 };
+use std::{fmt::Debug, future::Future, pin::Pin, sync::Arc};
 use tachys::{
     either::Either,
     html::{
@@ -69,6 +70,15 @@ use web_sys::{
 ///     Ok(())
 /// }
 /// ```
+///
+/// ## Validation
+/// If a [`FormValidation`] is passed as `validation`, it is run when the form is submitted, and
+/// the server function is only called once it has succeeded. If the server function returns an
+/// error, it is passed to [`FormValidation::handle_server_error`], so that it can be shown with
+/// the field it applies to.
+///
+/// With the `stores` feature, a `FormStore` can be used as the
+/// validation.
 #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip_all))]
 #[component]
 pub fn ActionForm<ServFn>(
@@ -77,6 +87,10 @@ pub fn ActionForm<ServFn>(
     /// A [`NodeRef`] in which the `<form>` element should be stored.
     #[prop(optional)]
     node_ref: Option<NodeRef<Form>>,
+    /// Validation that should succeed before submitting, and that is told about errors returned
+    /// by the server.
+    #[prop(optional, into)]
+    validation: Option<AnyFormValidation>,
     /// Component children; should include the HTML of the form elements.
     children: Children,
 ) -> impl IntoView
@@ -104,6 +118,16 @@ where
     let version = action.version();
     let value = action.value();
 
+    if let Some(validation) = validation.clone() {
+        Effect::new(move |_| {
+            value.with(|value| {
+                if let Some(Err(err)) = value {
+                    validation.0.handle_server_error(&err.to_string());
+                }
+            })
+        });
+    }
+
     let on_submit = {
         move |ev: SubmitEvent| {
             if ev.default_prevented() {
@@ -114,7 +138,9 @@ where
 
             match ServFn::from_event(&ev) {
                 Ok(new_input) => {
-                    action.dispatch(new_input);
+                    submit_if_valid(validation.clone(), move || {
+                        action.dispatch(new_input);
+                    });
                 }
                 Err(err) => {
                     crate::logging::error!(
@@ -142,6 +168,79 @@ where
     }
 }
 
+/// Validation that an [`ActionForm`] runs before calling its server function.
+///
+/// With the `stores` feature, this is implemented for
+/// `FormStore` and `FormState`.
+pub trait FormValidation: Send + Sync {
+    /// Validates the form, resolving to `true` if it can be submitted.
+    fn validate(&self) -> Pin<Box<dyn Future<Output = bool>>>;
+
+    /// Handles an error returned by the server function, for example by showing it with the
+    /// field it applies to.
+    fn handle_server_error(&self, error: &str);
+}
+
+/// A type-erased [`FormValidation`], which is passed to [`ActionForm`].
+#[derive(Clone)]
+pub struct AnyFormValidation(Arc<dyn FormValidation>);
+
+impl Debug for AnyFormValidation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("AnyFormValidation").finish()
+    }
+}
+
+impl<V> From<V> for AnyFormValidation
+where
+    V: FormValidation + 'static,
+{
+    fn from(value: V) -> Self {
+        Self(Arc::new(value))
+    }
+}
+
+#[cfg(feature = "stores")]
+impl FormValidation for reactive_stores::FormState {
+    fn validate(&self) -> Pin<Box<dyn Future<Output = bool>>> {
+        let form = *self;
+        Box::pin(async move { form.validate_async().await })
+    }
+
+    fn handle_server_error(&self, error: &str) {
+        reactive_stores::FormState::handle_server_error(self, &error);
+    }
+}
+
+#[cfg(feature = "stores")]
+impl<T> FormValidation for reactive_stores::FormStore<T>
+where
+    T: Send + Sync + 'static,
+{
+    fn validate(&self) -> Pin<Box<dyn Future<Output = bool>>> {
+        FormValidation::validate(&self.state())
+    }
+
+    fn handle_server_error(&self, error: &str) {
+        FormValidation::handle_server_error(&self.state(), error);
+    }
+}
+
+/// Calls `dispatch` if there is no validation, or once the validation has succeeded.
+fn submit_if_valid(
+    validation: Option<AnyFormValidation>,
+    dispatch: impl FnOnce() + 'static,
+) {
+    match validation {
+        None => dispatch(),
+        Some(validation) => crate::task::spawn_local(async move {
+            if validation.0.validate().await {
+                dispatch();
+            }
+        }),
+    }
+}
+
 /// Automatically turns a server [MultiAction](leptos_server::MultiAction) into an HTML
 /// [`form`](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/form)
 /// progressively enhanced to use client-side routing.
@@ -317,3 +416,74 @@ fn form_data_from_event(
         }
     }
 }
+
+#[cfg(all(test, feature = "stores"))]
+mod tests {
+    use super::submit_if_valid;
+    use crate::prelude::*;
+    use any_spawner::Executor;
+    use futures::{channel::oneshot, FutureExt};
+    use reactive_stores::{FormStore, Store};
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    #[derive(Store)]
+    struct Signup {
+        name: String,
+    }
+
+    #[test]
+    fn submits_once_async_validation_succeeds() {
+        _ = Executor::init_futures_executor();
+        let owner = Owner::new();
+        owner.set();
+
+        let (finish, finished) = oneshot::channel::<()>();
+        let finished = finished.shared();
+        let form = FormStore::new(Signup {
+            name: "Bob".to_string(),
+        })
+        .async_validator(
+            |signup| signup.name(),
+            move |name| {
+                let finished = finished.clone();
+                async move {
+                    _ = finished.await;
+                    if name == "Bob" {
+                        Err("taken".to_string())
+                    } else {
+                        Ok(())
+                    }
+                }
+            },
+        );
+        let submitted = Arc::new(AtomicUsize::new(0));
+        let submit = {
+            let submitted = Arc::clone(&submitted);
+            move || {
+                submitted.fetch_add(1, Ordering::Relaxed);
+            }
+        };
+
+        // the form is not submitted while it is being validated, or if it is invalid
+        submit_if_valid(Some(form.into()), submit.clone());
+        Executor::poll_local();
+        assert!(form.state().is_validating());
+        assert_eq!(submitted.load(Ordering::Relaxed), 0);
+        _ = finish.send(());
+        Executor::poll_local();
+        assert_eq!(submitted.load(Ordering::Relaxed), 0);
+        assert_eq!(form.errors(form.store().name()), ["taken"]);
+
+        form.store().name().set("Alice".to_string());
+        submit_if_valid(Some(form.into()), submit.clone());
+        Executor::poll_local();
+        assert_eq!(submitted.load(Ordering::Relaxed), 1);
+
+        // without form state, the form is submitted immediately
+        submit_if_valid(None, submit);
+        assert_eq!(submitted.load(Ordering::Relaxed), 2);
+    }
+}
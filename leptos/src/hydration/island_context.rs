@@ -0,0 +1,152 @@
+use crate::prelude::*;
+use or_poisoned::OrPoisoned;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+/// The contexts that are shared by every island in the page.
+#[derive(Clone, Default)]
+pub(crate) struct IslandContexts(Arc<Mutex<IslandContextsInner>>);
+
+#[derive(Default)]
+struct IslandContextsInner {
+    /// On the server, the serialized contexts, and whether each has been included in the page.
+    serialized: Vec<(&'static str, String, bool)>,
+    /// In the browser, the owner that deserialized contexts belong to.
+    owner: Option<Owner>,
+    /// In the browser, the contexts that have already been deserialized.
+    values: HashMap<TypeId, Box<dyn Any + Send + Sync>>,
+}
+
+impl IslandContexts {
+    /// Creates the contexts for the islands in the page, which are deserialized in the given
+    /// root owner, so that they live as long as the islands that use them.
+    #[cfg_attr(not(feature = "hydrate"), allow(dead_code))]
+    pub(crate) fn new(owner: Owner) -> Self {
+        Self(Arc::new(Mutex::new(IslandContextsInner {
+            owner: Some(owner),
+            ..Default::default()
+        })))
+    }
+}
+
+fn context_key<T>() -> &'static str {
+    std::any::type_name::<T>()
+}
+
+/// Provides a context that can be used by every island in the page, with
+/// [`use_island_context`].
+///
+/// This should be called on the server, in a component that is an ancestor of the islands that
+/// use it. The context is provided as usual, so that it is available while the islands are
+/// rendered on the server, and its value is serialized into the page once, before the first island
+/// that is rendered after it.
+///
+/// In the browser, every island that uses the context shares the same deserialized value, so a
+/// signal provided this way can keep several islands in sync.
+///
+/// Contexts are identified by their type name, so each type can only be provided once per page.
+pub fn provide_island_context<T>(value: T)
+where
+    T: Serialize + Clone + Send + Sync + 'static,
+{
+    let contexts = use_context::<IslandContexts>().unwrap_or_else(|| {
+        let contexts = IslandContexts::default();
+        provide_context(contexts.clone());
+        contexts
+    });
+    match serde_json::to_string(&value) {
+        Ok(serialized) => {
+            let key = context_key::<T>();
+            let mut contexts = contexts.0.lock().or_poisoned();
+            contexts.serialized.retain(|(other, _, _)| *other != key);
+            contexts.serialized.push((key, serialized, false));
+        }
+        Err(e) => crate::logging::error!(
+            "Could not serialize island context {}: {e}",
+            context_key::<T>()
+        ),
+    }
+    provide_context(value);
+}
+
+/// Returns a context that was provided to every island in the page with
+/// [`provide_island_context`].
+///
+/// On the server, this is the same as [`use_context`]. In the browser, the context is
+/// deserialized from the page the first time it is used, and then shared by every island.
+pub fn use_island_context<T>() -> Option<T>
+where
+    T: DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    if let Some(value) = use_context::<T>() {
+        return Some(value);
+    }
+
+    let contexts = use_context::<IslandContexts>()?;
+    let mut contexts = contexts.0.lock().or_poisoned();
+    if let Some(value) = contexts
+        .values
+        .get(&TypeId::of::<T>())
+        .and_then(|value| value.downcast_ref::<T>())
+    {
+        return Some(value.clone());
+    }
+
+    let value = deserialize_island_context::<T>(contexts.owner.as_ref()?)?;
+    contexts
+        .values
+        .insert(TypeId::of::<T>(), Box::new(value.clone()));
+    Some(value)
+}
+
+#[cfg(feature = "hydrate")]
+fn deserialize_island_context<T>(owner: &Owner) -> Option<T>
+where
+    T: DeserializeOwned,
+{
+    let key = context_key::<T>();
+    let script = document()
+        .query_selector(&format!("script[data-island-context=\"{key}\"]"))
+        .ok()??;
+    let data = super::island_props::unescape(&script.text_content()?);
+    match owner.with(|| serde_json::from_str(&data)) {
+        Ok(value) => Some(value),
+        Err(e) => {
+            crate::logging::error!(
+                "Could not deserialize island context {key}: {e}"
+            );
+            None
+        }
+    }
+}
+
+#[cfg(not(feature = "hydrate"))]
+fn deserialize_island_context<T>(_owner: &Owner) -> Option<T> {
+    None
+}
+
+/// Returns the island contexts that have not been included in the page yet, as pairs of their
+/// keys and serialized values, and marks them as included.
+#[doc(hidden)]
+pub fn pending_island_contexts() -> Vec<(&'static str, String)> {
+    use_context::<IslandContexts>()
+        .map(|contexts| {
+            contexts
+                .0
+                .lock()
+                .or_poisoned()
+                .serialized
+                .iter_mut()
+                .filter(|(_, _, included)| !*included)
+                .map(|(key, value, included)| {
+                    *included = true;
+                    (*key, value.clone())
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
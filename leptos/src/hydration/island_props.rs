@@ -0,0 +1,76 @@
+//! Encoding and decoding the props of islands, which is used by the code generated by
+//! `#[island]`.
+
+use crate::prelude::*;
+use codee::{Decoder, Encoder};
+use leptos_server::{FromEncodedStr, IntoEncodedString};
+use or_poisoned::OrPoisoned;
+use std::{
+    borrow::Borrow,
+    collections::HashSet,
+    fmt::Debug,
+    hash::{DefaultHasher, Hash, Hasher},
+    sync::{Arc, Mutex},
+};
+
+/// The keys of the shared island props that have already been included in the page.
+#[derive(Clone, Default)]
+pub(crate) struct SharedIslandProps(Arc<Mutex<HashSet<u64>>>);
+
+/// Encodes the props of an island with the codec `C`.
+pub fn encode<C, T>(props: &T) -> String
+where
+    C: Encoder<T>,
+    C::Encoded: IntoEncodedString,
+    C::Error: Debug,
+{
+    C::encode(props)
+        .expect("couldn't serialize island props")
+        .into_encoded_string()
+}
+
+/// Returns the key for these encoded props, and the props themselves if they have not already
+/// been included in the page.
+///
+/// Props are only deduplicated if [`HydrationScripts`](super::HydrationScripts) has been
+/// rendered in `islands` mode.
+pub fn share(encoded: String) -> (String, Option<String>) {
+    let mut hasher = DefaultHasher::new();
+    encoded.hash(&mut hasher);
+    let hash = hasher.finish();
+
+    let is_new = use_context::<SharedIslandProps>()
+        .map(|shared| shared.0.lock().or_poisoned().insert(hash))
+        .unwrap_or(true);
+    (format!("{hash:x}"), is_new.then_some(encoded))
+}
+
+/// Decodes the props of an island with the codec `C`, either from its `data-props` attribute or
+/// from the shared `<script>` that its `data-props-ref` attribute refers to.
+pub fn decode<C, T>(el: &web_sys::HtmlElement) -> Option<T>
+where
+    C: Decoder<T>,
+    C::Encoded: FromEncodedStr,
+{
+    let dataset = el.dataset();
+    let data = match dataset.get(wasm_bindgen::intern("props")) {
+        Some(data) => data,
+        None => {
+            let key = dataset.get(wasm_bindgen::intern("propsRef"))?;
+            let script = document()
+                .query_selector(&format!("script[data-island-props=\"{key}\"]"))
+                .ok()??;
+            unescape(&script.text_content()?)
+        }
+    };
+    let encoded = C::Encoded::from_encoded_str(&data).ok()?;
+    C::decode(encoded.borrow()).ok()
+}
+
+/// Unescapes the payload of a `<script>` that was escaped as HTML text when it was rendered,
+/// because the content of a `<script>` is never unescaped by the browser.
+pub(crate) fn unescape(data: &str) -> String {
+    data.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
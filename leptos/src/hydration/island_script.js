@@ -1,5 +1,11 @@
 ((root, pkg_path, output_name, wasm_output_name) => {
-	let MOST_RECENT_CHILDREN_CB;
+	const INTERACTION_EVENTS = ["click", "dblclick", "pointerdown", "mousedown", "touchstart", "keydown", "focusin", "input", "change", "submit"];
+	// these events have default actions that would happen before the island can handle them,
+	// so they are held back and replayed once it has hydrated
+	const DEFERRED_DEFAULTS = ["click", "dblclick", "submit"];
+	// a submit event that is replayed does not submit the form, so a held-back submission has to be
+	// requested again, which is only possible with requestSubmit()
+	const CAN_REQUEST_SUBMIT = "requestSubmit" in HTMLFormElement.prototype;
 
 	function idle(c) {
 		if ("requestIdleCallback" in window) {
@@ -8,25 +14,43 @@
 			c();
 		}
 	}
-	function hydrateIslands(rootNode, mod) {
-		function traverse(node) {
+	const MOD = new Promise(resolve => {
+		idle(() => {
+			import(`${root}/${pkg_path}/${output_name}.js`)
+				.then(mod => {
+					mod.default(`${root}/${pkg_path}/${wasm_output_name}.wasm`).then(() => {
+						mod.hydrate();
+						resolve(mod);
+					});
+				})
+		});
+	});
+
+	function hydrateIslands(rootNode) {
+		function traverse(node, onChildrenHydrate) {
 			if (node.nodeType === Node.ELEMENT_NODE) {
 				const tag = node.tagName.toLowerCase();
 				if(tag === 'leptos-island') {
-					const children = [];
 					const id = node.dataset.component || null;
 
-					hydrateIsland(node, id, mod);
-					
-					for(const child of node.children) {
-						traverse(child, children);
-					}
+					whenNeeded(node, (onHydrated) => {
+						MOD.then(mod => {
+							hydrateIsland(node, id, mod, onChildrenHydrate);
+
+							for(const child of node.children) {
+								traverse(child, onChildrenHydrate);
+							}
+							if (onHydrated) {
+								onHydrated();
+							}
+						});
+					});
 				} else {
 					if(tag === 'leptos-children') {
-						MOST_RECENT_CHILDREN_CB = node.$$on_hydrate;
+						onChildrenHydrate = node.$$on_hydrate;
 					}
 					for(const child of node.children) {
-						traverse(child);
+						traverse(child, onChildrenHydrate);
 					};
 				}
 			}
@@ -34,24 +58,95 @@
 
 		traverse(rootNode);
 	}
-	function hydrateIsland(el, id, mod) {
+	function hydrateIsland(el, id, mod, onChildrenHydrate) {
 		const islandFn = mod[id];
 		if (islandFn) {
-			if (MOST_RECENT_CHILDREN_CB) {
-				MOST_RECENT_CHILDREN_CB();
+			if (onChildrenHydrate) {
+				onChildrenHydrate();
 			}
 			islandFn(el);
 		} else {
 			console.warn(`Could not find WASM function for the island ${id}.`);
 		}
 	}
-	idle(() => {
-		import(`${root}/${pkg_path}/${output_name}.js`)
-			.then(mod => {
-				mod.default(`${root}/${pkg_path}/${wasm_output_name}.wasm`).then(() => {
-					mod.hydrate();
-					hydrateIslands(document.body, mod);
-				});
-			})
-	});
+
+	function whenNeeded(el, hydrate) {
+		const strategy = el.dataset.hydrate || "idle";
+		if (strategy === "idle") {
+			hydrate();
+		} else if (strategy === "visible") {
+			onVisible(el, hydrate);
+		} else if (strategy === "interaction") {
+			onInteraction(el, hydrate);
+		} else if (strategy.startsWith("media(") && strategy.endsWith(")")) {
+			onMedia(strategy.slice("media(".length, -1), hydrate);
+		} else {
+			console.warn(`Unknown hydration strategy ${strategy} for the island ${el.dataset.component}.`);
+			hydrate();
+		}
+	}
+	function onVisible(el, hydrate) {
+		if (!("IntersectionObserver" in window)) {
+			hydrate();
+			return;
+		}
+		const observer = new IntersectionObserver(entries => {
+			if (entries.some(entry => entry.isIntersecting)) {
+				observer.disconnect();
+				hydrate();
+			}
+		});
+		// the island itself may not have a box (e.g., with `display: contents`), so observe its children too
+		observer.observe(el);
+		for(const child of el.children) {
+			observer.observe(child);
+		}
+	}
+	function onInteraction(el, hydrate) {
+		const captured = [];
+		let hydrating = false;
+
+		function capture(ev) {
+			captured.push(ev);
+			ev.stopImmediatePropagation();
+			if (DEFERRED_DEFAULTS.includes(ev.type) && (ev.type !== "submit" || CAN_REQUEST_SUBMIT)) {
+				ev.preventDefault();
+			}
+			if (!hydrating) {
+				hydrating = true;
+				hydrate(replay);
+			}
+		}
+		function replay() {
+			for(const type of INTERACTION_EVENTS) {
+				el.removeEventListener(type, capture, true);
+			}
+			for(const ev of captured) {
+				if (ev.type === "submit" && CAN_REQUEST_SUBMIT) {
+					ev.target.requestSubmit(ev.submitter);
+				} else {
+					ev.target.dispatchEvent(new ev.constructor(ev.type, ev));
+				}
+			}
+		}
+
+		for(const type of INTERACTION_EVENTS) {
+			el.addEventListener(type, capture, true);
+		}
+	}
+	function onMedia(query, hydrate) {
+		const media = window.matchMedia(query);
+		if (media.matches) {
+			hydrate();
+		} else {
+			media.addEventListener("change", function onChange(ev) {
+				if (ev.matches) {
+					media.removeEventListener("change", onChange);
+					hydrate();
+				}
+			});
+		}
+	}
+
+	hydrateIslands(document.body);
 })
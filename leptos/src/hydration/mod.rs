@@ -4,6 +4,14 @@ use crate::prelude::*;
 use leptos_config::LeptosOptions;
 use leptos_macro::{component, view};
 
+#[cfg(feature = "islands")]
+mod island_context;
+#[doc(hidden)]
+pub mod island_props;
+
+#[cfg(feature = "islands")]
+pub use island_context::*;
+
 /// Inserts auto-reloading code used in `cargo-leptos`.
 ///
 /// This should be included in the `<head>` of your application shell during development.
@@ -93,6 +101,11 @@ pub fn HydrationScripts(
         if let Some(sc) = Owner::current_shared_context() {
             sc.set_is_hydrating(false);
         }
+        provide_context(island_props::SharedIslandProps::default());
+        #[cfg(feature = "islands")]
+        if use_context::<island_context::IslandContexts>().is_none() {
+            provide_context(island_context::IslandContexts::default());
+        }
         include_str!("./island_script.js")
     } else {
         include_str!("./hydration_script.js")
@@ -91,6 +91,8 @@
 //! - **`rkyv`** In SSR/hydrate mode, uses [`rkyv`](https://docs.rs/rkyv/latest/rkyv/) to serialize resources and send them
 //!   from the server to the client.
 //! - **`tracing`** Adds support for [`tracing`](https://docs.rs/tracing/latest/tracing/).
+//! - **`stores`** Allows a `FormStore` to be used to validate an
+//!   [`ActionForm`](leptos::form::ActionForm).
 //!
 //! **Important Note:** You must enable one of `csr`, `hydrate`, or `ssr` to tell Leptos
 //! which mode your app is operating in. You should only enable one of these per build target,
@@ -225,6 +227,12 @@ pub mod hydration;
 #[cfg(feature = "nonce")]
 pub mod nonce;
 
+/// Signals that are persisted to web storage, cookies, or another storage backend.
+pub mod persist;
+
+/// Signals that are kept in sync between tabs, workers, or other contexts.
+pub mod shared;
+
 /// Components to load asynchronous data.
 pub mod suspense {
     pub use crate::{suspense_component::*, transition::*};
@@ -323,6 +331,9 @@ pub mod task {
 // these reexports are used in islands
 #[cfg(feature = "islands")]
 #[doc(hidden)]
+pub use codee;
+#[cfg(feature = "islands")]
+#[doc(hidden)]
 pub use serde;
 #[cfg(feature = "islands")]
 #[doc(hidden)]
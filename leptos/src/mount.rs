@@ -181,6 +181,11 @@ pub fn hydrate_islands() {
     sc.set_is_hydrating(false); // islands mode starts in "not hydrating"
     let owner = Owner::new_root(Some(Arc::new(sc)));
     owner.set();
+    // every island is hydrated in a child of this owner, so they can all share island contexts
+    #[cfg(feature = "islands")]
+    reactive_graph::owner::provide_context(
+        crate::hydration::IslandContexts::new(owner.clone()),
+    );
     std::mem::forget(owner);
 }
 
@@ -0,0 +1,611 @@
+//! Signals that are persisted to web storage, cookies, or some other storage backend.
+//!
+//! A [`PersistedSignal`] reads its initial value from a [`StorageBackend`], and writes its value
+//! back to the backend every time it changes. Values are converted to and from strings with a
+//! codec from [`codee`], like [`FromToStringCodec`](codee::string::FromToStringCodec) or
+//! [`JsonSerdeCodec`](codee::string::JsonSerdeCodec).
+//!
+//! ```rust
+//! # use leptos::prelude::*;
+//! # let owner = Owner::new(); owner.set();
+//! use codee::string::FromToStringCodec;
+//! use leptos::persist::{MemoryStorage, PersistedSignal, StorageBackend};
+//!
+//! let storage = MemoryStorage::default();
+//! let count = PersistedSignal::<i32, FromToStringCodec>::new(
+//!     storage.clone(),
+//!     "count",
+//!     0,
+//! );
+//! count.set(5);
+//! assert_eq!(storage.get("count").as_deref(), Some("5"));
+//!
+//! // signals created later start with the persisted value
+//! let count =
+//!     PersistedSignal::<i32, FromToStringCodec>::new(storage, "count", 0);
+//! assert_eq!(count.get(), 5);
+//! ```
+//!
+//! ## Server rendering and hydration
+//! Web storage is only available in the browser, so while hydrating, a signal backed by
+//! [`WebStorage`] starts with its default value (as it did on the server) and switches to the
+//! stored value once hydration has finished.
+//!
+//! Cookies are sent with every request, so during server rendering a signal backed by a
+//! [`Cookie`] reads its value from the [`RequestCookies`] provided by the server integrations,
+//! and the page hydrates with the same value.
+
+use crate::prelude::*;
+use codee::{Decoder, Encoder};
+use or_poisoned::OrPoisoned;
+use reactive_graph::{
+    signal::guards::{Plain, ReadGuard, WriteGuard},
+    traits::UntrackableGuard,
+};
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    marker::PhantomData,
+    ops::DerefMut,
+    panic::Location,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, RwLock,
+    },
+    time::Duration,
+};
+
+/// A callback that is called with the new stored value, or `None` if it has been removed.
+pub type OnStorageChange = Arc<dyn Fn(Option<String>) + Send + Sync>;
+
+/// Somewhere a [`PersistedSignal`] can store its value.
+pub trait StorageBackend: Send + Sync + 'static {
+    /// Returns the stored value for the key, if any.
+    fn get(&self, key: &str) -> Option<String>;
+
+    /// Stores a value for the key.
+    fn set(&self, key: &str, value: &str);
+
+    /// Removes the stored value for the key.
+    fn remove(&self, key: &str);
+
+    /// Calls `on_change` whenever the value for the key is changed from elsewhere (for example,
+    /// in another browser tab), until the returned function is called.
+    ///
+    /// By default, changes are not watched.
+    fn watch(
+        &self,
+        key: &str,
+        on_change: OnStorageChange,
+    ) -> Box<dyn FnOnce() + Send + Sync> {
+        _ = (key, on_change);
+        Box::new(|| {})
+    }
+
+    /// Whether stored values can be read during server rendering. If not, signals using this
+    /// backend start with their default value while hydrating, to match the server-rendered HTML.
+    ///
+    /// Defaults to `true`.
+    fn is_available_on_server(&self) -> bool {
+        true
+    }
+}
+
+/// Returns `true` if this is running in the browser, rather than on the server or natively.
+fn is_browser() -> bool {
+    cfg!(target_arch = "wasm32")
+        && Owner::current_shared_context()
+            .map(|sc| sc.is_browser())
+            .unwrap_or(true)
+}
+
+/// The browser's `localStorage` or `sessionStorage`.
+///
+/// Changes made in other tabs are synced through the `storage` event. Outside the browser, nothing
+/// is stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WebStorage {
+    /// `localStorage`, which is shared by all tabs and kept when the browser is closed.
+    Local,
+    /// `sessionStorage`, which is kept for the lifetime of the tab.
+    Session,
+}
+
+impl WebStorage {
+    fn storage(&self) -> Option<web_sys::Storage> {
+        if !is_browser() {
+            return None;
+        }
+        let window = window();
+        match self {
+            WebStorage::Local => window.local_storage(),
+            WebStorage::Session => window.session_storage(),
+        }
+        .ok()
+        .flatten()
+    }
+}
+
+impl StorageBackend for WebStorage {
+    fn get(&self, key: &str) -> Option<String> {
+        self.storage()?.get_item(key).ok().flatten()
+    }
+
+    fn set(&self, key: &str, value: &str) {
+        if let Some(storage) = self.storage() {
+            _ = storage.set_item(key, value);
+        }
+    }
+
+    fn remove(&self, key: &str) {
+        if let Some(storage) = self.storage() {
+            _ = storage.remove_item(key);
+        }
+    }
+
+    fn watch(
+        &self,
+        key: &str,
+        on_change: OnStorageChange,
+    ) -> Box<dyn FnOnce() + Send + Sync> {
+        let Some(storage) = self.storage() else {
+            return Box::new(|| {});
+        };
+        let key = key.to_string();
+        let handle = window_event_listener(crate::ev::storage, move |ev| {
+            // a `null` key means that the whole storage area was cleared
+            let matches_key = ev.key().map(|k| k == key).unwrap_or(true);
+            if matches_key && ev.storage_area().as_ref() == Some(&storage) {
+                on_change(ev.new_value());
+            }
+        });
+        Box::new(move || handle.remove())
+    }
+
+    fn is_available_on_server(&self) -> bool {
+        false
+    }
+}
+
+/// The `Cookie` header of the current request.
+///
+/// This is provided as context by the server integrations, and used to read [`Cookie`] values
+/// during server rendering.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RequestCookies(pub Option<String>);
+
+/// Stores values in cookies, which are sent to the server with every request.
+///
+/// In the browser, values are read from and written to `document.cookie`. During server
+/// rendering, values are read from the [`RequestCookies`]; changes made on the server are not
+/// sent back to the browser.
+///
+/// Values are percent-encoded, so any string can be stored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cookie {
+    path: String,
+    max_age: Option<Duration>,
+    same_site: Option<String>,
+    secure: bool,
+}
+
+impl Default for Cookie {
+    fn default() -> Self {
+        Self {
+            path: "/".to_string(),
+            max_age: None,
+            same_site: Some("Lax".to_string()),
+            secure: false,
+        }
+    }
+}
+
+impl Cookie {
+    /// Creates a cookie backend with the default attributes (`Path=/; SameSite=Lax`).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the `Path` of the cookies. Defaults to `/`.
+    pub fn path(mut self, path: impl Into<String>) -> Self {
+        self.path = path.into();
+        self
+    }
+
+    /// Sets the `Max-Age` of the cookies. By default, cookies are removed when the browser is
+    /// closed.
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Sets the `SameSite` attribute of the cookies. Defaults to `Lax`.
+    pub fn same_site(mut self, same_site: impl Into<String>) -> Self {
+        self.same_site = Some(same_site.into());
+        self
+    }
+
+    /// Sets the `Secure` attribute of the cookies.
+    pub fn secure(mut self, secure: bool) -> Self {
+        self.secure = secure;
+        self
+    }
+
+    fn cookie_header(&self) -> Option<String> {
+        if is_browser() {
+            use wasm_bindgen::JsValue;
+            use web_sys::js_sys::Reflect;
+
+            Reflect::get(&document(), &JsValue::from_str("cookie"))
+                .ok()
+                .and_then(|cookie| cookie.as_string())
+        } else {
+            use_context::<RequestCookies>().and_then(|cookies| cookies.0)
+        }
+    }
+
+    fn write(&self, key: &str, value: &str, max_age: Option<Duration>) {
+        if !is_browser() {
+            return;
+        }
+        use wasm_bindgen::JsValue;
+        use web_sys::js_sys::Reflect;
+
+        let mut cookie = format!(
+            "{}={}; Path={}",
+            percent_encode(key),
+            percent_encode(value),
+            self.path
+        );
+        if let Some(max_age) = max_age {
+            cookie.push_str(&format!("; Max-Age={}", max_age.as_secs()));
+        }
+        if let Some(same_site) = &self.same_site {
+            cookie.push_str(&format!("; SameSite={same_site}"));
+        }
+        if self.secure {
+            cookie.push_str("; Secure");
+        }
+        _ = Reflect::set(
+            &document(),
+            &JsValue::from_str("cookie"),
+            &JsValue::from_str(&cookie),
+        );
+    }
+}
+
+impl StorageBackend for Cookie {
+    fn get(&self, key: &str) -> Option<String> {
+        let header = self.cookie_header()?;
+        header.split(';').find_map(|cookie| {
+            let (name, value) = cookie.trim().split_once('=')?;
+            (percent_decode(name) == key).then(|| percent_decode(value))
+        })
+    }
+
+    fn set(&self, key: &str, value: &str) {
+        self.write(key, value, self.max_age);
+    }
+
+    fn remove(&self, key: &str) {
+        self.write(key, "", Some(Duration::ZERO));
+    }
+}
+
+fn percent_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-_.~".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = (bytes[i] == b'%')
+            .then(|| value.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Stores values in memory, which is useful for tests and native applications.
+///
+/// Clones of a `MemoryStorage` share the same values, and signals using the same storage are kept
+/// in sync with one another, like signals in different tabs using [`WebStorage::Local`].
+#[derive(Clone, Default)]
+pub struct MemoryStorage {
+    inner: Arc<RwLock<MemoryStorageInner>>,
+}
+
+#[derive(Default)]
+struct MemoryStorageInner {
+    values: HashMap<String, String>,
+    watchers: Vec<(usize, String, OnStorageChange)>,
+    next_id: usize,
+}
+
+impl Debug for MemoryStorage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MemoryStorage")
+            .field("values", &self.inner.read().or_poisoned().values)
+            .finish_non_exhaustive()
+    }
+}
+
+impl MemoryStorage {
+    fn notify(&self, key: &str, value: Option<String>) {
+        let watchers = self
+            .inner
+            .read()
+            .or_poisoned()
+            .watchers
+            .iter()
+            .filter(|(_, watched, _)| watched == key)
+            .map(|(_, _, on_change)| Arc::clone(on_change))
+            .collect::<Vec<_>>();
+        for on_change in watchers {
+            on_change(value.clone());
+        }
+    }
+}
+
+impl StorageBackend for MemoryStorage {
+    fn get(&self, key: &str) -> Option<String> {
+        self.inner.read().or_poisoned().values.get(key).cloned()
+    }
+
+    fn set(&self, key: &str, value: &str) {
+        self.inner
+            .write()
+            .or_poisoned()
+            .values
+            .insert(key.to_string(), value.to_string());
+        self.notify(key, Some(value.to_string()));
+    }
+
+    fn remove(&self, key: &str) {
+        self.inner.write().or_poisoned().values.remove(key);
+        self.notify(key, None);
+    }
+
+    fn watch(
+        &self,
+        key: &str,
+        on_change: OnStorageChange,
+    ) -> Box<dyn FnOnce() + Send + Sync> {
+        let id = {
+            let mut inner = self.inner.write().or_poisoned();
+            let id = inner.next_id;
+            inner.next_id += 1;
+            inner.watchers.push((id, key.to_string(), on_change));
+            id
+        };
+        let inner = Arc::clone(&self.inner);
+        Box::new(move || {
+            inner
+                .write()
+                .or_poisoned()
+                .watchers
+                .retain(|(watcher, _, _)| *watcher != id);
+        })
+    }
+}
+
+/// A signal whose value is persisted to a [`StorageBackend`], using the [`codee`] codec `C` to
+/// convert it to and from a string.
+///
+/// The signal starts with the stored value if there is one that can be decoded, and with the
+/// default value otherwise. Every tracked write to the signal stores the new value, and changes
+/// made elsewhere (for example, in another tab) update the signal.
+///
+/// See the [module documentation](crate::persist) for more details.
+pub struct PersistedSignal<T, C> {
+    signal: ArcRwSignal<T>,
+    inner: Arc<PersistedInner<T>>,
+    ty: PhantomData<fn() -> C>,
+}
+
+struct PersistedInner<T> {
+    key: String,
+    backend: Box<dyn StorageBackend>,
+    default: T,
+    // set while this signal is writing, so that it ignores its own changes
+    writing: AtomicBool,
+}
+
+impl<T, C> Clone for PersistedSignal<T, C> {
+    fn clone(&self) -> Self {
+        Self {
+            signal: self.signal.clone(),
+            inner: Arc::clone(&self.inner),
+            ty: PhantomData,
+        }
+    }
+}
+
+impl<T, C> Debug for PersistedSignal<T, C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PersistedSignal")
+            .field("key", &self.inner.key)
+            .field("signal", &self.signal)
+            .finish()
+    }
+}
+
+impl<T, C> PersistedSignal<T, C>
+where
+    T: Clone + Send + Sync + 'static,
+    C: Encoder<T, Encoded = String> + Decoder<T, Encoded = str>,
+{
+    /// Creates a signal that is persisted to the given backend under the given key.
+    #[track_caller]
+    pub fn new(
+        backend: impl StorageBackend,
+        key: impl Into<String>,
+        default: T,
+    ) -> Self {
+        let key = key.into();
+        let hydrating = Owner::current_shared_context()
+            .map(|sc| sc.during_hydration())
+            .unwrap_or(false);
+        let defer_load = hydrating && !backend.is_available_on_server();
+
+        let initial = if defer_load {
+            None
+        } else {
+            backend.get(&key).and_then(|stored| C::decode(&stored).ok())
+        };
+        let signal =
+            ArcRwSignal::new(initial.unwrap_or_else(|| default.clone()));
+        let inner = Arc::new(PersistedInner {
+            key,
+            backend: Box::new(backend),
+            default,
+            writing: AtomicBool::new(false),
+        });
+
+        if defer_load {
+            Effect::new({
+                let signal = signal.clone();
+                let inner = Arc::clone(&inner);
+                move |_| {
+                    if let Some(value) = inner
+                        .backend
+                        .get(&inner.key)
+                        .and_then(|stored| C::decode(&stored).ok())
+                    {
+                        signal.set(value);
+                    }
+                }
+            });
+        }
+
+        let unwatch = inner.backend.watch(
+            &inner.key,
+            Arc::new({
+                let signal = signal.clone();
+                let inner = Arc::downgrade(&inner);
+                move |stored| {
+                    let Some(inner) = inner.upgrade() else {
+                        return;
+                    };
+                    if inner.writing.load(Ordering::Relaxed) {
+                        return;
+                    }
+                    let value = match stored {
+                        Some(stored) => C::decode(&stored).ok(),
+                        None => Some(inner.default.clone()),
+                    };
+                    if let Some(value) = value {
+                        signal.set(value);
+                    }
+                }
+            }),
+        );
+        Owner::on_cleanup(unwatch);
+
+        Self {
+            signal,
+            inner,
+            ty: PhantomData,
+        }
+    }
+
+    /// The key under which the value is stored.
+    pub fn key(&self) -> &str {
+        &self.inner.key
+    }
+
+    /// Removes the stored value, and resets the signal to its default value.
+    pub fn clear(&self) {
+        self.inner.writing.store(true, Ordering::Relaxed);
+        self.inner.backend.remove(&self.inner.key);
+        self.inner.writing.store(false, Ordering::Relaxed);
+        self.signal.set(self.inner.default.clone());
+    }
+
+    fn persist(&self) {
+        let encoded = self.signal.with_untracked(|value| C::encode(value).ok());
+        if let Some(encoded) = encoded {
+            self.inner.writing.store(true, Ordering::Relaxed);
+            self.inner.backend.set(&self.inner.key, &encoded);
+            self.inner.writing.store(false, Ordering::Relaxed);
+        }
+    }
+}
+
+impl<T, C> DefinedAt for PersistedSignal<T, C> {
+    fn defined_at(&self) -> Option<&'static Location<'static>> {
+        self.signal.defined_at()
+    }
+}
+
+impl<T, C> IsDisposed for PersistedSignal<T, C> {
+    fn is_disposed(&self) -> bool {
+        false
+    }
+}
+
+impl<T: 'static, C> ReadUntracked for PersistedSignal<T, C> {
+    type Value = ReadGuard<T, Plain<T>>;
+
+    fn try_read_untracked(&self) -> Option<Self::Value> {
+        self.signal.try_read_untracked()
+    }
+}
+
+impl<T: 'static, C> Track for PersistedSignal<T, C> {
+    fn track(&self) {
+        self.signal.track();
+    }
+}
+
+impl<T, C> Notify for PersistedSignal<T, C>
+where
+    T: Clone + Send + Sync + 'static,
+    C: Encoder<T, Encoded = String> + Decoder<T, Encoded = str>,
+{
+    fn notify(&self) {
+        self.persist();
+        self.signal.notify();
+    }
+}
+
+impl<T, C> Write for PersistedSignal<T, C>
+where
+    T: Clone + Send + Sync + 'static,
+    C: Encoder<T, Encoded = String> + Decoder<T, Encoded = str>,
+{
+    type Value = T;
+
+    fn try_write(&self) -> Option<impl UntrackableGuard<Target = Self::Value>> {
+        self.signal
+            .try_write_untracked()
+            .map(|guard| WriteGuard::new(self.clone(), guard))
+    }
+
+    fn try_write_untracked(
+        &self,
+    ) -> Option<impl DerefMut<Target = Self::Value>> {
+        self.signal.try_write_untracked()
+    }
+}
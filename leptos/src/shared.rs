@@ -0,0 +1,542 @@
+//! Signals that are kept in sync between browser tabs, Web Workers, or any other contexts that
+//! can exchange messages.
+//!
+//! A [`SharedSignal`] sends every tracked write over a [`Transport`], and applies the writes it
+//! receives from other contexts. Messages are encoded with a [`codee`] codec (by default,
+//! [`JsonSerdeCodec`]), so the value only needs to implement `Serialize` and `Deserialize`.
+//!
+//! Every write is given a [`Version`]. When two contexts write at the same time, they both end up
+//! with the value that has the later version ("last writer wins"), so the signals never diverge.
+//!
+//! ```rust
+//! # use leptos::prelude::*;
+//! # let owner = Owner::new(); owner.set();
+//! use leptos::shared::{MemoryTransport, SharedSignal};
+//!
+//! // in a real app, these could be a `BroadcastChannelTransport` in each tab
+//! let page = MemoryTransport::new();
+//! let worker = page.connect();
+//!
+//! let a = SharedSignal::new(page, "count", 0);
+//! let b = SharedSignal::new(worker, "count", 0);
+//!
+//! a.set(5);
+//! assert_eq!(b.get_untracked(), 5);
+//! b.update(|n| *n += 1);
+//! assert_eq!(a.get_untracked(), 6);
+//! ```
+
+use crate::prelude::*;
+use codee::{string::JsonSerdeCodec, Decoder, Encoder};
+use or_poisoned::OrPoisoned;
+use reactive_graph::{
+    signal::guards::{Plain, ReadGuard, WriteGuard},
+    traits::UntrackableGuard,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    collections::hash_map::RandomState,
+    fmt::Debug,
+    hash::{BuildHasher, Hasher},
+    marker::PhantomData,
+    ops::DerefMut,
+    panic::Location,
+    sync::{Arc, Mutex, RwLock},
+};
+
+/// A callback that is called with each message received by a [`Transport`].
+pub type OnMessage = Arc<dyn Fn(String) + Send + Sync>;
+
+/// A way of sending messages to, and receiving messages from, other contexts.
+///
+/// Messages sent through a transport should be received by every other context connected to it,
+/// but not by the sender itself, like a
+/// [`BroadcastChannel`](https://developer.mozilla.org/en-US/docs/Web/API/BroadcastChannel).
+pub trait Transport: Send + Sync + 'static {
+    /// Sends a message to the other contexts.
+    fn send(&self, message: &str);
+
+    /// Calls `on_message` with every message received from the other contexts, until the returned
+    /// function is called.
+    fn subscribe(
+        &self,
+        on_message: OnMessage,
+    ) -> Box<dyn FnOnce() + Send + Sync>;
+}
+
+/// The version of a write to a [`SharedSignal`].
+///
+/// Versions are ordered by a logical clock, which is advanced past every version a signal has
+/// seen whenever it is written to. Writes with the same clock are ordered by the randomly-chosen
+/// ID of the signal that made them.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+)]
+pub struct Version {
+    /// The logical clock.
+    pub clock: u64,
+    /// The ID of the signal that made the write.
+    pub origin: u64,
+}
+
+/// A message sent between [`SharedSignal`]s.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SharedMessage<T> {
+    /// A signal has a new value.
+    Update {
+        /// The key of the signal.
+        key: String,
+        /// The version of the write.
+        version: Version,
+        /// The new value.
+        value: T,
+    },
+    /// A new signal is asking for the current value.
+    Sync {
+        /// The key of the signal.
+        key: String,
+    },
+}
+
+/// Sends messages between [`SharedSignal`]s in the same process, which is useful for tests and
+/// native applications.
+///
+/// Each transport is one end of a connection; use [`connect`](MemoryTransport::connect) to create
+/// another end. Messages sent by one end are delivered immediately to every other end.
+#[derive(Clone)]
+pub struct MemoryTransport {
+    bus: Arc<RwLock<MemoryBus>>,
+    endpoint: usize,
+}
+
+#[derive(Default)]
+struct MemoryBus {
+    subscribers: Vec<(usize, usize, OnMessage)>,
+    next_id: usize,
+}
+
+impl Debug for MemoryTransport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MemoryTransport")
+            .field("endpoint", &self.endpoint)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for MemoryTransport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MemoryTransport {
+    /// Creates a new transport, which is not connected to any others.
+    pub fn new() -> Self {
+        Self {
+            bus: Default::default(),
+            endpoint: 0,
+        }
+    }
+
+    /// Creates another end of the connection, which receives the messages sent by this end and
+    /// all the others.
+    pub fn connect(&self) -> Self {
+        let mut bus = self.bus.write().or_poisoned();
+        bus.next_id += 1;
+        Self {
+            bus: Arc::clone(&self.bus),
+            endpoint: bus.next_id,
+        }
+    }
+}
+
+impl Transport for MemoryTransport {
+    fn send(&self, message: &str) {
+        let subscribers = self
+            .bus
+            .read()
+            .or_poisoned()
+            .subscribers
+            .iter()
+            .filter(|(endpoint, _, _)| *endpoint != self.endpoint)
+            .map(|(_, _, on_message)| Arc::clone(on_message))
+            .collect::<Vec<_>>();
+        for on_message in subscribers {
+            on_message(message.to_string());
+        }
+    }
+
+    fn subscribe(
+        &self,
+        on_message: OnMessage,
+    ) -> Box<dyn FnOnce() + Send + Sync> {
+        let id = {
+            let mut bus = self.bus.write().or_poisoned();
+            bus.next_id += 1;
+            let id = bus.next_id;
+            bus.subscribers.push((self.endpoint, id, on_message));
+            id
+        };
+        let bus = Arc::clone(&self.bus);
+        Box::new(move || {
+            bus.write()
+                .or_poisoned()
+                .subscribers
+                .retain(|(_, subscriber, _)| *subscriber != id);
+        })
+    }
+}
+
+/// Listens for `message` events on a JavaScript object, returning a function that removes the
+/// listener.
+fn listen(
+    target: &wasm_bindgen::JsValue,
+    on_message: OnMessage,
+) -> Box<dyn FnOnce() + Send + Sync> {
+    use send_wrapper::SendWrapper;
+    use wasm_bindgen::{closure::Closure, JsCast};
+
+    let Some(target) = target.dyn_ref::<web_sys::EventTarget>() else {
+        return Box::new(|| {});
+    };
+    let cb = Closure::<dyn Fn(web_sys::MessageEvent)>::new(
+        move |ev: web_sys::MessageEvent| {
+            if let Some(message) = ev.data().as_string() {
+                on_message(message);
+            }
+        },
+    )
+    .into_js_value();
+    _ = target.add_event_listener_with_callback("message", cb.unchecked_ref());
+    // a `MessagePort` only starts delivering messages to listeners once it is started
+    if let Some(port) = target.dyn_ref::<web_sys::MessagePort>() {
+        port.start();
+    }
+    let target = SendWrapper::new(target.clone());
+    let cb = SendWrapper::new(cb);
+    Box::new(move || {
+        _ = target
+            .remove_event_listener_with_callback("message", cb.unchecked_ref());
+    })
+}
+
+/// Sends messages to other tabs, windows, and workers with the same origin through a
+/// [`BroadcastChannel`](https://developer.mozilla.org/en-US/docs/Web/API/BroadcastChannel).
+///
+/// Outside the browser, messages are not sent anywhere.
+#[derive(Debug, Clone)]
+pub struct BroadcastChannelTransport {
+    channel: Option<send_wrapper::SendWrapper<web_sys::BroadcastChannel>>,
+}
+
+impl BroadcastChannelTransport {
+    /// Connects to the broadcast channel with the given name.
+    pub fn new(name: &str) -> Self {
+        let channel = cfg!(target_arch = "wasm32")
+            .then(|| web_sys::BroadcastChannel::new(name).ok())
+            .flatten()
+            .map(send_wrapper::SendWrapper::new);
+        Self { channel }
+    }
+}
+
+impl Transport for BroadcastChannelTransport {
+    fn send(&self, message: &str) {
+        if let Some(channel) = &self.channel {
+            _ = channel.post_message(&wasm_bindgen::JsValue::from_str(message));
+        }
+    }
+
+    fn subscribe(
+        &self,
+        on_message: OnMessage,
+    ) -> Box<dyn FnOnce() + Send + Sync> {
+        match &self.channel {
+            Some(channel) => listen(channel, on_message),
+            None => Box::new(|| {}),
+        }
+    }
+}
+
+/// Sends messages through any JavaScript object with a `postMessage` method that dispatches
+/// `message` events, like a
+/// [`MessagePort`](https://developer.mozilla.org/en-US/docs/Web/API/MessagePort), a
+/// [`Worker`](https://developer.mozilla.org/en-US/docs/Web/API/Worker), or the global scope
+/// inside a worker.
+#[derive(Debug, Clone)]
+pub struct PostMessageTransport {
+    target: send_wrapper::SendWrapper<wasm_bindgen::JsValue>,
+}
+
+impl PostMessageTransport {
+    /// Creates a transport that sends messages with `target.postMessage()`.
+    pub fn new(target: impl Into<wasm_bindgen::JsValue>) -> Self {
+        Self {
+            target: send_wrapper::SendWrapper::new(target.into()),
+        }
+    }
+}
+
+impl Transport for PostMessageTransport {
+    fn send(&self, message: &str) {
+        use wasm_bindgen::{JsCast, JsValue};
+        use web_sys::js_sys::{Function, Reflect};
+
+        if let Some(post_message) =
+            Reflect::get(&self.target, &JsValue::from_str("postMessage"))
+                .ok()
+                .and_then(|f| f.dyn_into::<Function>().ok())
+        {
+            _ = post_message.call1(&self.target, &JsValue::from_str(message));
+        }
+    }
+
+    fn subscribe(
+        &self,
+        on_message: OnMessage,
+    ) -> Box<dyn FnOnce() + Send + Sync> {
+        listen(&self.target, on_message)
+    }
+}
+
+/// A signal whose writes are sent to other signals with the same key over a [`Transport`], and
+/// which applies the writes it receives from them.
+///
+/// Messages are converted to and from strings with the [`codee`] codec `C`. When it is created, the
+/// signal asks the other contexts for their current value, and uses it if one of them has been
+/// written to.
+///
+/// See the [module documentation](crate::shared) for more details.
+pub struct SharedSignal<T, C = JsonSerdeCodec> {
+    signal: ArcRwSignal<T>,
+    inner: Arc<SharedInner>,
+    ty: PhantomData<fn() -> C>,
+}
+
+struct SharedInner {
+    key: String,
+    transport: Box<dyn Transport>,
+    origin: u64,
+    version: Mutex<Version>,
+}
+
+impl<T, C> Clone for SharedSignal<T, C> {
+    fn clone(&self) -> Self {
+        Self {
+            signal: self.signal.clone(),
+            inner: Arc::clone(&self.inner),
+            ty: PhantomData,
+        }
+    }
+}
+
+impl<T, C> Debug for SharedSignal<T, C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SharedSignal")
+            .field("key", &self.inner.key)
+            .field("version", &*self.inner.version.lock().or_poisoned())
+            .field("signal", &self.signal)
+            .finish()
+    }
+}
+
+impl<T> SharedSignal<T>
+where
+    T: Clone + Send + Sync + Serialize + DeserializeOwned + 'static,
+{
+    /// Creates a signal that is shared with other signals with the same key over the given
+    /// transport, encoding its messages as JSON.
+    #[track_caller]
+    pub fn new(
+        transport: impl Transport,
+        key: impl Into<String>,
+        initial: T,
+    ) -> Self {
+        Self::new_with_codec(transport, key, initial)
+    }
+}
+
+impl<T, C> SharedSignal<T, C>
+where
+    T: Clone + Send + Sync + 'static,
+    C: Encoder<SharedMessage<T>, Encoded = String>
+        + Decoder<SharedMessage<T>, Encoded = str>,
+{
+    /// Creates a signal that is shared with other signals with the same key over the given
+    /// transport, using the codec `C` to encode its messages.
+    #[track_caller]
+    pub fn new_with_codec(
+        transport: impl Transport,
+        key: impl Into<String>,
+        initial: T,
+    ) -> Self {
+        let signal = ArcRwSignal::new(initial);
+        let inner = Arc::new(SharedInner {
+            key: key.into(),
+            transport: Box::new(transport),
+            origin: random_origin(),
+            version: Mutex::new(Version::default()),
+        });
+
+        let unsubscribe = inner.transport.subscribe(Arc::new({
+            let signal = signal.clone();
+            let inner = Arc::downgrade(&inner);
+            move |message| {
+                let Some(inner) = inner.upgrade() else {
+                    return;
+                };
+                match C::decode(&message) {
+                    Ok(SharedMessage::Update {
+                        key,
+                        version,
+                        value,
+                    }) if key == inner.key => {
+                        let mut current = inner.version.lock().or_poisoned();
+                        if version > *current {
+                            *current = version;
+                            drop(current);
+                            signal.set(value);
+                        }
+                    }
+                    Ok(SharedMessage::Sync { key }) if key == inner.key => {
+                        let version = *inner.version.lock().or_poisoned();
+                        if version != Version::default() {
+                            let value = signal.get_untracked();
+                            send::<T, C>(&inner, version, value);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }));
+        Owner::on_cleanup(unsubscribe);
+
+        if let Ok(message) = C::encode(&SharedMessage::Sync {
+            key: inner.key.clone(),
+        }) {
+            inner.transport.send(&message);
+        }
+
+        Self {
+            signal,
+            inner,
+            ty: PhantomData,
+        }
+    }
+
+    /// The key that this signal is shared under.
+    pub fn key(&self) -> &str {
+        &self.inner.key
+    }
+
+    /// The version of the current value.
+    pub fn version(&self) -> Version {
+        *self.inner.version.lock().or_poisoned()
+    }
+
+    fn broadcast(&self) {
+        let version = {
+            let mut version = self.inner.version.lock().or_poisoned();
+            *version = Version {
+                clock: version.clock + 1,
+                origin: self.inner.origin,
+            };
+            *version
+        };
+        let value = self.signal.get_untracked();
+        send::<T, C>(&self.inner, version, value);
+    }
+}
+
+/// Returns a random ID for a signal, which is used to order writes with the same clock.
+fn random_origin() -> u64 {
+    if cfg!(target_arch = "wasm32") {
+        // `RandomState` is not random in the browser
+        (web_sys::js_sys::Math::random() * u64::MAX as f64) as u64
+    } else {
+        RandomState::new().build_hasher().finish()
+    }
+}
+
+fn send<T, C>(inner: &SharedInner, version: Version, value: T)
+where
+    C: Encoder<SharedMessage<T>, Encoded = String>,
+{
+    let message = SharedMessage::Update {
+        key: inner.key.clone(),
+        version,
+        value,
+    };
+    if let Ok(message) = C::encode(&message) {
+        inner.transport.send(&message);
+    }
+}
+
+impl<T, C> DefinedAt for SharedSignal<T, C> {
+    fn defined_at(&self) -> Option<&'static Location<'static>> {
+        self.signal.defined_at()
+    }
+}
+
+impl<T, C> IsDisposed for SharedSignal<T, C> {
+    fn is_disposed(&self) -> bool {
+        false
+    }
+}
+
+impl<T: 'static, C> ReadUntracked for SharedSignal<T, C> {
+    type Value = ReadGuard<T, Plain<T>>;
+
+    fn try_read_untracked(&self) -> Option<Self::Value> {
+        self.signal.try_read_untracked()
+    }
+}
+
+impl<T: 'static, C> Track for SharedSignal<T, C> {
+    fn track(&self) {
+        self.signal.track();
+    }
+}
+
+impl<T, C> Notify for SharedSignal<T, C>
+where
+    T: Clone + Send + Sync + 'static,
+    C: Encoder<SharedMessage<T>, Encoded = String>
+        + Decoder<SharedMessage<T>, Encoded = str>,
+{
+    fn notify(&self) {
+        self.broadcast();
+        self.signal.notify();
+    }
+}
+
+impl<T, C> Write for SharedSignal<T, C>
+where
+    T: Clone + Send + Sync + 'static,
+    C: Encoder<SharedMessage<T>, Encoded = String>
+        + Decoder<SharedMessage<T>, Encoded = str>,
+{
+    type Value = T;
+
+    fn try_write(&self) -> Option<impl UntrackableGuard<Target = Self::Value>> {
+        self.signal
+            .try_write_untracked()
+            .map(|guard| WriteGuard::new(self.clone(), guard))
+    }
+
+    fn try_write_untracked(
+        &self,
+    ) -> Option<impl DerefMut<Target = Self::Value>> {
+        self.signal.try_write_untracked()
+    }
+}
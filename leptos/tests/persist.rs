@@ -0,0 +1,114 @@
+use codee::string::{FromToStringCodec, JsonSerdeCodec};
+use leptos::{
+    persist::{
+        Cookie, MemoryStorage, PersistedSignal, RequestCookies, StorageBackend,
+    },
+    prelude::*,
+};
+
+#[test]
+fn persisted_signal_reads_and_writes_storage() {
+    let owner = Owner::new();
+    owner.set();
+
+    let storage = MemoryStorage::default();
+    storage.set("count", "3");
+    let count = PersistedSignal::<i32, FromToStringCodec>::new(
+        storage.clone(),
+        "count",
+        0,
+    );
+    assert_eq!(count.get_untracked(), 3);
+
+    count.update(|n| *n += 1);
+    assert_eq!(storage.get("count").as_deref(), Some("4"));
+
+    // untracked writes are not persisted
+    *count.write_untracked() = 10;
+    assert_eq!(storage.get("count").as_deref(), Some("4"));
+
+    count.clear();
+    assert_eq!(count.get_untracked(), 0);
+    assert_eq!(storage.get("count"), None);
+}
+
+#[test]
+fn invalid_stored_value_uses_default() {
+    let owner = Owner::new();
+    owner.set();
+
+    let storage = MemoryStorage::default();
+    storage.set("count", "not a number");
+    let count =
+        PersistedSignal::<i32, FromToStringCodec>::new(storage, "count", 7);
+    assert_eq!(count.get_untracked(), 7);
+}
+
+#[test]
+fn signals_sharing_storage_stay_in_sync() {
+    let owner = Owner::new();
+    owner.set();
+
+    let storage = MemoryStorage::default();
+    let a = PersistedSignal::<Vec<String>, JsonSerdeCodec>::new(
+        storage.clone(),
+        "todos",
+        vec![],
+    );
+    let b = PersistedSignal::<Vec<String>, JsonSerdeCodec>::new(
+        storage.clone(),
+        "todos",
+        vec![],
+    );
+
+    a.update(|todos| todos.push("write tests".to_string()));
+    assert_eq!(storage.get("todos").as_deref(), Some(r#"["write tests"]"#));
+    assert_eq!(b.get_untracked(), vec!["write tests".to_string()]);
+
+    storage.remove("todos");
+    assert!(a.get_untracked().is_empty());
+    assert!(b.get_untracked().is_empty());
+}
+
+#[test]
+fn signals_stop_syncing_when_owner_is_cleaned_up() {
+    let owner = Owner::new();
+    owner.set();
+
+    let storage = MemoryStorage::default();
+    let child = owner.child();
+    let count = child.with(|| {
+        PersistedSignal::<i32, FromToStringCodec>::new(
+            storage.clone(),
+            "count",
+            0,
+        )
+    });
+    child.cleanup();
+
+    storage.set("count", "1");
+    assert_eq!(count.get_untracked(), 0);
+}
+
+#[test]
+fn cookies_are_read_from_request() {
+    let owner = Owner::new();
+    owner.set();
+
+    provide_context(RequestCookies(Some(
+        "session=abc; theme=%22dark%20mode%22".to_string(),
+    )));
+    let theme = PersistedSignal::<String, JsonSerdeCodec>::new(
+        Cookie::new(),
+        "theme",
+        String::new(),
+    );
+    assert_eq!(theme.get_untracked(), "dark mode");
+
+    let missing = PersistedSignal::<String, JsonSerdeCodec>::new(
+        Cookie::new(),
+        "missing",
+        "light".into(),
+    );
+    assert_eq!(missing.get_untracked(), "light");
+}
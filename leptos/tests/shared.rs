@@ -0,0 +1,98 @@
+use codee::{string::JsonSerdeCodec, Encoder};
+use leptos::{
+    prelude::*,
+    shared::{
+        MemoryTransport, SharedMessage, SharedSignal, Transport, Version,
+    },
+};
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn writes_are_mirrored_to_other_signals() {
+    let owner = Owner::new();
+    owner.set();
+
+    let page = MemoryTransport::new();
+    let worker = page.connect();
+    let a = SharedSignal::new(page, "todos", Vec::<String>::new());
+    let b = SharedSignal::new(worker, "todos", Vec::<String>::new());
+
+    a.update(|todos| todos.push("write tests".to_string()));
+    assert_eq!(b.get_untracked(), ["write tests"]);
+    assert_eq!(a.version(), b.version());
+
+    b.write().push("ship it".to_string());
+    assert_eq!(a.get_untracked(), ["write tests", "ship it"]);
+
+    // untracked writes are not sent
+    a.write_untracked().clear();
+    assert_eq!(b.get_untracked().len(), 2);
+}
+
+#[test]
+fn new_signals_receive_the_current_value() {
+    let owner = Owner::new();
+    owner.set();
+
+    let page = MemoryTransport::new();
+    let a = SharedSignal::new(page.clone(), "count", 0);
+    a.set(3);
+
+    // a signal with a different key is not affected
+    let other = SharedSignal::new(page.connect(), "other", 0);
+    let b = SharedSignal::new(page.connect(), "count", 0);
+    assert_eq!(b.get_untracked(), 3);
+    assert_eq!(other.get_untracked(), 0);
+}
+
+#[test]
+fn last_writer_wins() {
+    let owner = Owner::new();
+    owner.set();
+
+    let page = MemoryTransport::new();
+    let a = SharedSignal::new(page.connect(), "count", 0);
+    a.set(1);
+    assert_eq!(a.version().clock, 1);
+
+    // a message from another context, sent with the same clock at the same time
+    let remote = page.connect();
+    let send = |version: Version, value: i32| {
+        let message = SharedMessage::Update {
+            key: "count".to_string(),
+            version,
+            value,
+        };
+        remote.send(&JsonSerdeCodec::encode(&message).unwrap());
+    };
+    let received = Arc::new(Mutex::new(Vec::new()));
+    _ = page.subscribe(Arc::new({
+        let received = Arc::clone(&received);
+        move |message| received.lock().unwrap().push(message)
+    }));
+
+    // an older write is ignored
+    send(
+        Version {
+            clock: 0,
+            origin: 0,
+        },
+        10,
+    );
+    assert_eq!(a.get_untracked(), 1);
+
+    // a concurrent write with the same clock is ordered by its origin
+    send(
+        Version {
+            clock: 1,
+            origin: u64::MAX,
+        },
+        30,
+    );
+    assert_eq!(a.get_untracked(), 30);
+
+    // later writes have a later clock than any write that has been seen
+    a.set(40);
+    assert_eq!(a.version().clock, 2);
+    assert_eq!(received.lock().unwrap().len(), 3);
+}
@@ -135,3 +135,108 @@ fn ssr_option() {
 
     assert_eq!(rendered.to_html(), "<option></option>");
 }
+
+#[cfg(all(feature = "ssr", feature = "islands"))]
+#[test]
+fn ssr_island_with_hydrate_strategy() {
+    use leptos::prelude::*;
+
+    #[island(hydrate = "visible")]
+    fn Counter(initial: i32) -> impl IntoView {
+        let (value, set_value) = signal(initial);
+        view! {
+            <button on:click=move |_| set_value.update(|value| *value += 1)>
+                {value}
+            </button>
+        }
+    }
+
+    let owner = Owner::new();
+    owner.set();
+    let rendered = view! { <Counter initial=1/> };
+
+    let html = rendered.to_html();
+    assert!(html.starts_with("<leptos-island data-component=\"Counter_"));
+    assert!(html.contains(
+        "data-props=\"{&quot;initial&quot;:1}\" data-hydrate=\"visible\">"
+    ));
+}
+
+#[cfg(all(feature = "ssr", feature = "islands"))]
+#[test]
+fn ssr_islands_share_props_payload() {
+    use leptos::{
+        config::LeptosOptions, hydration::HydrationScripts, prelude::*,
+    };
+
+    #[island(shared_props, codec = codee::string::JsonSerdeCodec)]
+    fn Chart(points: Vec<u32>) -> impl IntoView {
+        view! { <span>{points.len()}</span> }
+    }
+
+    let owner = Owner::new();
+    owner.set();
+    let options = LeptosOptions::builder().output_name("app").build();
+    let rendered = view! {
+        <HydrationScripts options islands=true/>
+        <Chart points=vec![1, 2, 3]/>
+        <Chart points=vec![1, 2, 3]/>
+        <Chart points=vec![4]/>
+    };
+
+    let html = rendered.to_html();
+    assert_eq!(
+        html.matches("<script type=\"application/leptos-island-props\"")
+            .count(),
+        2
+    );
+    assert!(html.contains(">{\"points\":[1,2,3]}</script>"));
+    assert_eq!(html.matches("data-props-ref=").count(), 3);
+    assert!(!html.contains("data-props=\""));
+}
+
+#[cfg(all(feature = "ssr", feature = "islands"))]
+#[test]
+fn ssr_island_context_is_serialized_once() {
+    use leptos::{config::LeptosOptions, hydration::HydrationScripts, prelude::*};
+
+    #[island]
+    fn CartBadge() -> impl IntoView {
+        let count = use_island_context::<RwSignal<u32>>().unwrap();
+        view! { <span>{count}</span> }
+    }
+
+    #[island]
+    fn AddToCart() -> impl IntoView {
+        let count = use_island_context::<RwSignal<u32>>().unwrap();
+        view! { <button on:click=move |_| *count.write() += 1>"Add"</button> }
+    }
+
+    #[component]
+    fn Page() -> impl IntoView {
+        provide_island_context(RwSignal::new(2u32));
+        view! {
+            <CartBadge/>
+            <AddToCart/>
+        }
+    }
+
+    let owner = Owner::new();
+    owner.set();
+    let options = LeptosOptions::builder().output_name("app").build();
+    let rendered = view! {
+        <HydrationScripts options islands=true/>
+        <Page/>
+    };
+
+    let html = rendered.to_html();
+    assert_eq!(
+        html.matches(
+            "<script type=\"application/leptos-island-context\" \
+             data-island-context=\"reactive_graph::signal::rw::RwSignal&lt;u32&gt;\">2</script>"
+        )
+        .count(),
+        1
+    );
+    assert!(html.contains("<span>2</span>"));
+}
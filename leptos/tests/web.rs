@@ -0,0 +1,71 @@
+// these tests render to the DOM, so they only run in a browser, with
+// `wasm-pack test --headless --firefox -- --features csr`
+#![allow(dead_code)]
+
+use leptos::{prelude::*, reactive::collections::ReactiveMap, task::tick};
+use std::sync::{Arc, Mutex};
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::*;
+
+#[cfg(target_arch = "wasm32")]
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+async fn keyed_list_of_reactive_map_only_updates_changed_row() {
+    let scores = ReactiveMap::from_iter([("alice", 1), ("bob", 2)]);
+    let list_runs = Arc::new(Mutex::new(0));
+    let row_runs = Arc::new(Mutex::new(Vec::new()));
+
+    mount_to_body({
+        let scores = scores.clone();
+        let list_runs = Arc::clone(&list_runs);
+        let row_runs = Arc::clone(&row_runs);
+        move || {
+            let names = {
+                let scores = scores.clone();
+                move || {
+                    *list_runs.lock().unwrap() += 1;
+                    let mut names = scores.keys();
+                    names.sort();
+                    names
+                }
+            };
+            view! {
+                <ul>
+                    <For each=names key=|name| *name let:name>
+                        <li>
+                            {
+                                let scores = scores.clone();
+                                let row_runs = Arc::clone(&row_runs);
+                                move || {
+                                    row_runs.lock().unwrap().push(name);
+                                    scores.get(name).unwrap_or_default()
+                                }
+                            }
+                        </li>
+                    </For>
+                </ul>
+            }
+        }
+    });
+
+    let list = document().query_selector("ul").unwrap().unwrap();
+    assert_eq!(list.inner_html(), "<li>1</li><li>2</li><!---->");
+    assert_eq!(*list_runs.lock().unwrap(), 1);
+    row_runs.lock().unwrap().clear();
+
+    // changing a value only updates its own row
+    scores.insert("bob", 20);
+    tick().await;
+    assert_eq!(list.inner_html(), "<li>1</li><li>20</li><!---->");
+    assert_eq!(*list_runs.lock().unwrap(), 1);
+    assert_eq!(*row_runs.lock().unwrap(), ["bob"]);
+
+    // adding a key updates the list, but not the existing rows
+    row_runs.lock().unwrap().clear();
+    scores.insert("carol", 3);
+    tick().await;
+    assert_eq!(list.inner_html(), "<li>1</li><li>20</li><li>3</li><!---->");
+    assert_eq!(*list_runs.lock().unwrap(), 2);
+    assert_eq!(*row_runs.lock().unwrap(), ["carol"]);
+}
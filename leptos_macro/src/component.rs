@@ -17,9 +17,21 @@ use syn::{
     TypeParam, TypePath, Visibility,
 };
 
+/// The options that can be given to `#[island(...)]`.
+#[derive(Default)]
+pub struct IslandOptions {
+    /// The strategy the island loader uses to decide when to hydrate the island.
+    pub hydrate: Option<String>,
+    /// The codec used to encode the island's props, instead of JSON.
+    pub codec: Option<Path>,
+    /// Whether the props are sent in a shared `<script>`, rather than an attribute.
+    pub shared_props: bool,
+}
+
 pub struct Model {
     is_transparent: bool,
     island: Option<String>,
+    island_options: IslandOptions,
     docs: Docs,
     unknown_attrs: UnknownAttrs,
     vis: Visibility,
@@ -65,6 +77,7 @@ impl Parse for Model {
         Ok(Self {
             is_transparent: false,
             island: None,
+            island_options: IslandOptions::default(),
             docs,
             unknown_attrs,
             vis: item.vis.clone(),
@@ -106,6 +119,7 @@ impl ToTokens for Model {
         let Self {
             is_transparent,
             island,
+            island_options,
             docs,
             unknown_attrs,
             vis,
@@ -264,20 +278,45 @@ impl ToTokens for Model {
             Ident::new(&format!("{component_id}_{caller:?}"), name.span())
         });
 
+        let IslandOptions {
+            hydrate: island_hydrate,
+            codec: island_codec,
+            shared_props: island_shared_props,
+        } = island_options;
+        let island_codec = island_codec
+            .as_ref()
+            .map(|codec| quote! { #codec })
+            .unwrap_or_else(
+                || quote! { ::leptos::codee::string::JsonSerdeCodec },
+            );
         let island_serialize_props = if is_island_with_other_props {
             quote! {
-                let _leptos_ser_props = ::leptos::serde_json::to_string(&props).expect("couldn't serialize island props");
+                let _leptos_ser_props = ::leptos::hydration::island_props::encode::<#island_codec, _>(&props);
             }
         } else {
             quote! {}
         };
-        let island_serialized_props = if is_island_with_other_props {
+        let island_serialized_props = if !is_island_with_other_props {
+            quote! {}
+        } else if *island_shared_props {
             quote! {
-                .with_props( _leptos_ser_props)
+                .with_shared_props(::leptos::hydration::island_props::share(_leptos_ser_props))
             }
         } else {
-            quote! {}
+            quote! {
+                .with_props( _leptos_ser_props)
+            }
         };
+        let island_hydrate = island_hydrate.as_ref().map(|hydrate| {
+            quote! {
+                .with_hydrate(#hydrate)
+            }
+        });
+        let island_contexts = cfg!(feature = "islands").then(|| {
+            quote! {
+                .with_contexts(::leptos::hydration::pending_island_contexts())
+            }
+        });
 
         let body_name = unmodified_fn_name_from_fn_name(&body_name);
         let body_expr = if is_island {
@@ -340,6 +379,8 @@ impl ToTokens for Model {
                                 #component
                             )
                              #island_serialized_props
+                             #island_contexts
+                             #island_hydrate
                         )
                     }
                 }
@@ -500,8 +541,7 @@ impl ToTokens for Model {
             };
             let deserialize_island_props = if is_island_with_other_props {
                 quote! {
-                    let props = el.dataset().get(::leptos::wasm_bindgen::intern("props"))
-                        .and_then(|data| ::leptos::serde_json::from_str::<#props_serialized_name>(&data).ok())
+                    let props = ::leptos::hydration::island_props::decode::<#island_codec, #props_serialized_name>(&el)
                         .expect("could not deserialize props");
                 }
             } else {
@@ -595,6 +635,13 @@ impl Model {
 
         self
     }
+
+    #[allow(clippy::wrong_self_convention)]
+    pub fn with_island_options(mut self, options: IslandOptions) -> Self {
+        self.island_options = options;
+
+        self
+    }
 }
 
 /// A model that is more lenient in case of a syntax error in the function body,
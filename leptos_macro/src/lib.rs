@@ -12,7 +12,7 @@
 #[macro_use]
 extern crate proc_macro_error2;
 
-use component::DummyModel;
+use component::{DummyModel, IslandOptions};
 use proc_macro::TokenStream;
 use proc_macro2::{Span, TokenTree};
 use quote::{quote, ToTokens};
@@ -556,7 +556,7 @@ pub fn component(args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
         false
     };
 
-    component_macro(s, is_transparent, None)
+    component_macro(s, is_transparent, None, Default::default())
 }
 
 /// Defines a component as an interactive island when you are using the
@@ -590,6 +590,34 @@ pub fn component(args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
 /// HTML isn't present in the DOM, even if hidden, it is never sent and not available
 /// to the client at all.
 ///
+/// ## Lazy Hydration
+/// By default, every island is hydrated as soon as the WASM module has loaded and the browser
+/// is idle. `#[island(hydrate = "...")]` lets the island loader wait until the island is needed:
+/// - `"idle"`: the default
+/// - `"visible"`: when the island scrolls into view
+/// - `"interaction"`: when the user first interacts with the island, for example by clicking
+///   or focusing it. Events that happen before the island has hydrated are replayed afterwards,
+///   and a form submission that happened before then is submitted again with `requestSubmit()`.
+/// - `"media(<query>)"`: when the given CSS media query matches, like `"media(min-width: 800px)"`
+///
+/// ## Sharing State Between Islands
+/// Each island is hydrated separately, so a context provided inside one island isn't available
+/// to the others. A context provided on the server with `provide_island_context` is serialized
+/// into the page once, and every island that calls `use_island_context` in the browser shares
+/// the same value. For example, providing a signal this way lets a cart badge and an "add to cart"
+/// button in different islands stay in sync.
+///
+/// ## Props Encoding
+/// Island props are serialized with JSON by default. `#[island(codec = <Codec>)]` uses another
+/// [`codee`](https://docs.rs/codee) codec for the island's props instead, like
+/// `codee::binary::MsgpackSerdeCodec`. The props are serialized as a struct with `serde`, so the
+/// codec must support types that implement `Serialize` and `Deserialize`.
+///
+/// Props are usually sent in a `data-props` attribute of the island. With
+/// `#[island(shared_props)]`, they are sent in a `<script>` instead, which is only included once
+/// in the page for each distinct set of encoded props, so islands with the same large props
+/// don't repeat them.
+///
 /// ## Example
 /// ```rust,ignore
 /// use leptos::prelude::*;
@@ -633,36 +661,81 @@ pub fn component(args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
 #[proc_macro_error2::proc_macro_error]
 #[proc_macro_attribute]
 pub fn island(args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
-    let is_transparent = if !args.is_empty() {
-        let transparent = parse_macro_input!(args as syn::Ident);
-
-        if transparent != "transparent" {
-            abort!(
-                transparent,
-                "only `transparent` is supported";
-                help = "try `#[island(transparent)]` or `#[island]`"
-            );
+    let args = parse_macro_input!(
+        args with syn::punctuated::Punctuated::<syn::Meta, syn::Token![,]>::parse_terminated
+    );
+    let mut is_transparent = false;
+    let mut options = IslandOptions::default();
+    for arg in args {
+        match &arg {
+            syn::Meta::Path(path) if path.is_ident("transparent") => {
+                is_transparent = true;
+            }
+            syn::Meta::NameValue(syn::MetaNameValue {
+                path,
+                value:
+                    syn::Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Str(strategy),
+                        ..
+                    }),
+                ..
+            }) if path.is_ident("hydrate") => {
+                let value = strategy.value();
+                let is_media =
+                    value.starts_with("media(") && value.ends_with(')');
+                if !is_media
+                    && !matches!(
+                        value.as_str(),
+                        "idle" | "visible" | "interaction"
+                    )
+                {
+                    abort!(
+                        strategy,
+                        "unknown hydration strategy";
+                        help = "try `\"idle\"`, `\"visible\"`, `\"interaction\"`, or `\"media(<query>)\"`"
+                    );
+                }
+                options.hydrate = Some(value);
+            }
+            syn::Meta::NameValue(syn::MetaNameValue {
+                path,
+                value: syn::Expr::Path(codec),
+                ..
+            }) if path.is_ident("codec") => {
+                options.codec = Some(codec.path.clone());
+            }
+            syn::Meta::Path(path) if path.is_ident("shared_props") => {
+                options.shared_props = true;
+            }
+            _ => {
+                abort!(
+                    arg,
+                    "only `transparent`, `hydrate`, `codec`, and `shared_props` are supported";
+                    help = "try `#[island(transparent)]`, `#[island(hydrate = \"visible\")]`, or `#[island]`"
+                );
+            }
         }
-
-        true
-    } else {
-        false
-    };
+    }
 
     let island_src = s.to_string();
-    component_macro(s, is_transparent, Some(island_src))
+    component_macro(s, is_transparent, Some(island_src), options)
 }
 
 fn component_macro(
     s: TokenStream,
     is_transparent: bool,
     island: Option<String>,
+    island_options: IslandOptions,
 ) -> TokenStream {
     let mut dummy = syn::parse::<DummyModel>(s.clone());
     let parse_result = syn::parse::<component::Model>(s);
 
     if let (Ok(ref mut unexpanded), Ok(model)) = (&mut dummy, parse_result) {
-        let expanded = model.is_transparent(is_transparent).with_island(island).into_token_stream();
+        let expanded = model
+            .is_transparent(is_transparent)
+            .with_island(island)
+            .with_island_options(island_options)
+            .into_token_stream();
         if !matches!(unexpanded.vis, Visibility::Public(_)) {
             unexpanded.vis = Visibility::Public(Pub {
                 span: unexpanded.vis.span(),
@@ -12,12 +12,15 @@ use reactive_graph::{
         guards::{AsyncPlain, ReadGuard},
         ArcRwSignal, RwSignal,
     },
-    traits::{DefinedAt, IsDisposed, ReadUntracked, Track, Update, Write},
+    traits::{
+        DefinedAt, IsDisposed, ReadUntracked, Track, Update, WithName, Write,
+    },
 };
 use send_wrapper::SendWrapper;
 use std::{
     future::{pending, Future, IntoFuture},
     panic::Location,
+    sync::Arc,
 };
 
 /// A reference-counted resource that only loads its data locally on the client.
@@ -110,9 +113,10 @@ where
             notifier.notify();
         } else if cfg!(feature = "ssr") {
             panic!(
-                "Reading from a LocalResource outside Suspense in `ssr` mode \
-                 will cause the response to hang, because LocalResources are \
-                 always pending on the server."
+                "Reading from a LocalResource{} outside Suspense in `ssr` \
+                 mode will cause the response to hang, because \
+                 LocalResources are always pending on the server.",
+                reactive_graph::diagnostics::describe_value(&self)
             );
         }
         self.data.into_future().map(|value| (*value).clone())
@@ -130,6 +134,17 @@ impl<T> DefinedAt for ArcLocalResource<T> {
             None
         }
     }
+
+    fn debug_name(&self) -> Option<Arc<str>> {
+        self.data.debug_name()
+    }
+}
+
+impl<T> WithName for ArcLocalResource<T> {
+    fn with_name(mut self, name: impl Into<Arc<str>>) -> Self {
+        self.data = self.data.with_name(name);
+        self
+    }
 }
 
 impl<T> ReadUntracked for ArcLocalResource<T>
@@ -144,9 +159,10 @@ where
             notifier.notify();
         } else if cfg!(feature = "ssr") {
             panic!(
-                "Reading from a LocalResource outside Suspense in `ssr` mode \
-                 will cause the response to hang, because LocalResources are \
-                 always pending on the server."
+                "Reading from a LocalResource{} outside Suspense in `ssr` \
+                 mode will cause the response to hang, because \
+                 LocalResources are always pending on the server.",
+                reactive_graph::diagnostics::describe_value(self)
             );
         }
         self.data.try_read_untracked()
@@ -302,9 +318,10 @@ where
             notifier.notify();
         } else if cfg!(feature = "ssr") {
             panic!(
-                "Reading from a LocalResource outside Suspense in `ssr` mode \
-                 will cause the response to hang, because LocalResources are \
-                 always pending on the server."
+                "Reading from a LocalResource{} outside Suspense in `ssr` \
+                 mode will cause the response to hang, because \
+                 LocalResources are always pending on the server.",
+                reactive_graph::diagnostics::describe_value(&self)
             );
         }
         self.data.into_future().map(|value| (*value).clone())
@@ -322,6 +339,17 @@ impl<T> DefinedAt for LocalResource<T> {
             None
         }
     }
+
+    fn debug_name(&self) -> Option<Arc<str>> {
+        self.data.debug_name()
+    }
+}
+
+impl<T: 'static> WithName for LocalResource<T> {
+    fn with_name(mut self, name: impl Into<Arc<str>>) -> Self {
+        self.data = self.data.with_name(name);
+        self
+    }
 }
 
 impl<T> ReadUntracked for LocalResource<T>
@@ -336,9 +364,10 @@ where
             notifier.notify();
         } else if cfg!(feature = "ssr") {
             panic!(
-                "Reading from a LocalResource outside Suspense in `ssr` mode \
-                 will cause the response to hang, because LocalResources are \
-                 always pending on the server."
+                "Reading from a LocalResource{} outside Suspense in `ssr` \
+                 mode will cause the response to hang, because \
+                 LocalResources are always pending on the server.",
+                reactive_graph::diagnostics::describe_value(self)
             );
         }
         self.data.try_read_untracked()
@@ -192,6 +192,17 @@ impl<T, Ser> DefinedAt for ArcOnceResource<T, Ser> {
             Some(self.defined_at)
         }
     }
+
+    fn debug_name(&self) -> Option<Arc<str>> {
+        self.trigger.debug_name()
+    }
+}
+
+impl<T, Ser> WithName for ArcOnceResource<T, Ser> {
+    fn with_name(self, name: impl Into<Arc<str>>) -> Self {
+        self.trigger.clone().with_name(name);
+        self
+    }
 }
 
 impl<T, Ser> IsDisposed for ArcOnceResource<T, Ser> {
@@ -560,6 +571,25 @@ impl<T, Ser> DefinedAt for OnceResource<T, Ser> {
             Some(self.defined_at)
         }
     }
+
+    fn debug_name(&self) -> Option<Arc<str>> {
+        self.inner.debug_name()
+    }
+}
+
+impl<T, Ser> WithName for OnceResource<T, Ser>
+where
+    T: Send + Sync + 'static,
+    Ser: 'static,
+{
+    fn with_name(self, name: impl Into<Arc<str>>) -> Self {
+        let name = name.into();
+        if let Some(inner) = self.inner.try_get_value() {
+            inner.with_name(Arc::clone(&name));
+        }
+        self.inner.with_name(name);
+        self
+    }
 }
 
 impl<T, Ser> IsDisposed for OnceResource<T, Ser> {
@@ -17,7 +17,7 @@ use hydration_context::{SerializedDataId, SharedContext};
 use reactive_graph::{
     computed::{
         ArcAsyncDerived, ArcMemo, AsyncDerived, AsyncDerivedFuture,
-        AsyncDerivedRefFuture,
+        AsyncDerivedOptions, AsyncDerivedRefFuture,
     },
     graph::{Source, ToAnySubscriber},
     owner::Owner,
@@ -131,6 +131,17 @@ impl<T, Ser> DefinedAt for ArcResource<T, Ser> {
             None
         }
     }
+
+    fn debug_name(&self) -> Option<Arc<str>> {
+        self.data.debug_name()
+    }
+}
+
+impl<T, Ser> WithName for ArcResource<T, Ser> {
+    fn with_name(mut self, name: impl Into<Arc<str>>) -> Self {
+        self.data = self.data.with_name(name);
+        self
+    }
 }
 
 impl<T, Ser> Clone for ArcResource<T, Ser> {
@@ -179,10 +190,12 @@ where
             if !in_effect_scope() && use_context::<SuspenseContext>().is_none()
             {
                 let location = std::panic::Location::caller();
+                let resource =
+                    reactive_graph::diagnostics::describe_value(self);
                 reactive_graph::log_warning(format_args!(
-                    "At {location}, you are reading a resource in `hydrate` \
-                     mode outside a <Suspense/> or <Transition/> or effect. \
-                     This can cause hydration mismatch errors and loses out \
+                    "At {location}, you are reading a resource{resource} in \
+                     `hydrate` mode outside a <Suspense/> or \
+                     <Transition/> or effect. This can cause hydration mismatch errors and loses out \
                      on a significant performance optimization. To fix this \
                      issue, you can either: \n1. Wrap the place where you \
                      read the resource in a <Suspense/> or <Transition/> \
@@ -223,10 +236,37 @@ where
     /// needs to appear in HTTP headers.
     #[track_caller]
     pub fn new_with_options<S, Fut>(
+        source: impl Fn() -> S + Send + Sync + 'static,
+        fetcher: impl Fn(S) -> Fut + Send + Sync + 'static,
+        blocking: bool,
+    ) -> ArcResource<T, Ser>
+    where
+        S: PartialEq + Clone + Send + Sync + 'static,
+        T: Send + Sync + 'static,
+        Fut: Future<Output = T> + Send + 'static,
+    {
+        Self::with_rerun_options(
+            source,
+            fetcher,
+            blocking,
+            AsyncDerivedOptions::default(),
+        )
+    }
+
+    /// Creates a new resource with the encoding `Ser`, with options that control how the
+    /// `fetcher` re-runs when the `source` changes: for example, to debounce it while the user is
+    /// typing.
+    ///
+    /// Whenever the `fetcher` re-runs, the previous `Future` is dropped if it has not finished
+    /// yet. See [`AbortSignal`](reactive_graph::computed::AbortSignal) for cancelling any work it
+    /// started elsewhere.
+    #[track_caller]
+    pub fn with_rerun_options<S, Fut>(
         source: impl Fn() -> S + Send + Sync + 'static,
         fetcher: impl Fn(S) -> Fut + Send + Sync + 'static,
         #[allow(unused)] // this is used with `feature = "ssr"`
         blocking: bool,
+        rerun: AsyncDerivedOptions,
     ) -> ArcResource<T, Ser>
     where
         S: PartialEq + Clone + Send + Sync + 'static,
@@ -262,8 +302,8 @@ where
             }
         };
 
-        let data = ArcAsyncDerived::new_with_manual_dependencies(
-            initial, fun, &source,
+        let data = ArcAsyncDerived::new_with_manual_dependencies_and_options(
+            initial, fun, &source, rerun,
         );
         if is_ready {
             source.with_untracked(|_| ());
@@ -812,6 +852,20 @@ where
             None
         }
     }
+
+    fn debug_name(&self) -> Option<Arc<str>> {
+        self.data.debug_name()
+    }
+}
+
+impl<T, Ser> WithName for Resource<T, Ser>
+where
+    T: Send + Sync + 'static,
+{
+    fn with_name(mut self, name: impl Into<Arc<str>>) -> Self {
+        self.data = self.data.with_name(name);
+        self
+    }
 }
 
 impl<T: Send + Sync + 'static, Ser> Copy for Resource<T, Ser> {}
@@ -859,10 +913,12 @@ where
             if !in_effect_scope() && use_context::<SuspenseContext>().is_none()
             {
                 let location = std::panic::Location::caller();
+                let resource =
+                    reactive_graph::diagnostics::describe_value(self);
                 reactive_graph::log_warning(format_args!(
-                    "At {location}, you are reading a resource in `hydrate` \
-                     mode outside a <Suspense/> or <Transition/> or effect. \
-                     This can cause hydration mismatch errors and loses out \
+                    "At {location}, you are reading a resource{resource} in \
+                     `hydrate` mode outside a <Suspense/> or \
+                     <Transition/> or effect. This can cause hydration mismatch errors and loses out \
                      on a significant performance optimization. To fix this \
                      issue, you can either: \n1. Wrap the place where you \
                      read the resource in a <Suspense/> or <Transition/> \
@@ -1259,13 +1315,40 @@ where
         fetcher: impl Fn(S) -> Fut + Send + Sync + 'static,
         blocking: bool,
     ) -> Resource<T, Ser>
+    where
+        S: Send + Sync + Clone + PartialEq + 'static,
+        T: Send + Sync + 'static,
+        Fut: Future<Output = T> + Send + 'static,
+    {
+        Self::with_rerun_options(
+            source,
+            fetcher,
+            blocking,
+            AsyncDerivedOptions::default(),
+        )
+    }
+
+    /// Creates a new resource with the encoding `Ser`, with options that control how the
+    /// `fetcher` re-runs when the `source` changes: for example, to debounce it while the user is
+    /// typing.
+    ///
+    /// Whenever the `fetcher` re-runs, the previous `Future` is dropped if it has not finished
+    /// yet. See [`AbortSignal`](reactive_graph::computed::AbortSignal) for cancelling any work it
+    /// started elsewhere.
+    #[track_caller]
+    pub fn with_rerun_options<S, Fut>(
+        source: impl Fn() -> S + Send + Sync + 'static,
+        fetcher: impl Fn(S) -> Fut + Send + Sync + 'static,
+        blocking: bool,
+        rerun: AsyncDerivedOptions,
+    ) -> Resource<T, Ser>
     where
         S: Send + Sync + Clone + PartialEq + 'static,
         T: Send + Sync + 'static,
         Fut: Future<Output = T> + Send + 'static,
     {
         let ArcResource { data, refetch, .. }: ArcResource<T, Ser> =
-            ArcResource::new_with_options(source, fetcher, blocking);
+            ArcResource::with_rerun_options(source, fetcher, blocking, rerun);
         Resource {
             ser: PhantomData,
             data: data.into(),
@@ -1,23 +1,75 @@
+use crate::effect::{scheduler::Ticket, Priority};
 use core::sync::atomic::Ordering::Relaxed;
 use futures::{task::AtomicWaker, Stream};
+use or_poisoned::OrPoisoned;
 use std::{
     fmt::Debug,
+    future::Future,
     hash::Hash,
     pin::Pin,
-    sync::{atomic::AtomicBool, Arc, Weak},
+    sync::{
+        atomic::{AtomicBool, AtomicU8},
+        Arc, Mutex, Weak,
+    },
     task::{Context, Poll},
 };
 
-#[derive(Debug)]
+/// Marks a channel whose notifications are not scheduled with any priority.
+const UNSCHEDULED: u8 = u8::MAX;
+
+#[derive(Debug, Clone)]
 pub(crate) struct Sender(Arc<Inner>);
 
-#[derive(Debug)]
-pub(crate) struct Receiver(Weak<Inner>);
+pub(crate) struct Receiver {
+    inner: Weak<Inner>,
+    /// Whether this receiver has taken a notification, but is waiting for higher-priority
+    /// effects to run before it yields it.
+    holding: bool,
+    yielding: Option<Pin<Box<dyn Future<Output = ()> + Send>>>,
+}
 
-#[derive(Debug, Default)]
+impl Debug for Receiver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Receiver")
+            .field("inner", &self.inner)
+            .field("holding", &self.holding)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Debug)]
 struct Inner {
     waker: AtomicWaker,
     set: AtomicBool,
+    /// The priority with which notifications are scheduled.
+    priority: AtomicU8,
+    /// Whether the receiver yields to the browser before it receives a scheduled notification.
+    yields: AtomicBool,
+    /// The current notification, if it is waiting to be received.
+    ticket: Mutex<Option<Ticket>>,
+    /// Whether the receiver has been dropped, or the sender has been closed.
+    closed: AtomicBool,
+}
+
+impl Inner {
+    fn queue(&self) {
+        let Some(priority) = Priority::from_u8(self.priority.load(Relaxed))
+        else {
+            return;
+        };
+        if !self.closed.load(Relaxed) {
+            let mut ticket = self.ticket.lock().or_poisoned();
+            if ticket.is_none() {
+                *ticket = Some(Ticket::queue(priority));
+            }
+        }
+    }
+
+    fn release(&self) {
+        // dropping the ticket releases it
+        let ticket = self.ticket.lock().or_poisoned().take();
+        drop(ticket);
+    }
 }
 
 impl Drop for Inner {
@@ -43,39 +95,128 @@ pub fn channel() -> (Sender, Receiver) {
     let inner = Arc::new(Inner {
         waker: AtomicWaker::new(),
         set: AtomicBool::new(false),
+        priority: AtomicU8::new(UNSCHEDULED),
+        yields: AtomicBool::new(false),
+        ticket: Mutex::new(None),
+        closed: AtomicBool::new(false),
     });
     let rx = Arc::downgrade(&inner);
-    (Sender(inner), Receiver(rx))
+    (
+        Sender(inner),
+        Receiver {
+            inner: rx,
+            holding: false,
+            yielding: None,
+        },
+    )
 }
 
 impl Sender {
     pub fn notify(&mut self) {
         self.0.set.store(true, Relaxed);
+        self.0.queue();
         self.0.waker.wake();
     }
+
+    /// Schedules the notifications sent through this channel with the given priority, so that
+    /// the receiver waits for any higher-priority receivers that have been notified on the same
+    /// thread.
+    pub fn set_priority(&self, priority: Priority) {
+        self.0.priority.store(priority.to_u8(), Relaxed);
+        let mut ticket = self.0.ticket.lock().or_poisoned();
+        if let Some(ticket) = &mut *ticket {
+            if ticket.priority() != priority {
+                ticket.requeue(priority);
+            }
+        } else if self.0.set.load(Relaxed) && !self.0.closed.load(Relaxed) {
+            *ticket = Some(Ticket::queue(priority));
+        }
+    }
+
+    pub fn priority(&self) -> Option<Priority> {
+        Priority::from_u8(self.0.priority.load(Relaxed))
+    }
+
+    /// Sets whether the receiver yields to the browser before it receives a scheduled
+    /// notification. See [`Ticket::yield_before`].
+    pub fn set_yielding(&self, yields: bool) {
+        self.0.yields.store(yields, Relaxed);
+    }
+
+    /// Stops scheduling notifications, and releases the current notification, so that lower-priority
+    /// receivers do not wait for a receiver that will never run.
+    pub fn close(&self) {
+        self.0.closed.store(true, Relaxed);
+        self.0.release();
+    }
 }
 
 impl Stream for Receiver {
     type Item = ();
 
     fn poll_next(
-        self: Pin<&mut Self>,
+        mut self: Pin<&mut Self>,
         cx: &mut Context<'_>,
     ) -> Poll<Option<Self::Item>> {
-        if let Some(inner) = self.0.upgrade() {
+        let this = &mut *self;
+        if let Some(inner) = this.inner.upgrade() {
             inner.waker.register(cx.waker());
 
-            if inner.set.swap(false, Relaxed) {
-                Poll::Ready(Some(()))
-            } else {
-                Poll::Pending
+            if !this.holding {
+                if inner.set.swap(false, Relaxed) {
+                    this.holding = true;
+                } else {
+                    return Poll::Pending;
+                }
             }
+
+            // wait until any higher-priority effects have run
+            let priority = {
+                let ticket = inner.ticket.lock().or_poisoned();
+                match &*ticket {
+                    Some(ticket) if !ticket.can_run(cx.waker()) => {
+                        return Poll::Pending;
+                    }
+                    Some(ticket) => Some(ticket.priority()),
+                    None => None,
+                }
+            };
+            if let Some(priority) =
+                priority.filter(|_| inner.yields.load(Relaxed))
+            {
+                if this.yielding.is_none() {
+                    this.yielding = Ticket::yield_before(priority);
+                }
+                if let Some(yielding) = &mut this.yielding {
+                    if yielding.as_mut().poll(cx).is_pending() {
+                        return Poll::Pending;
+                    }
+                    this.yielding = None;
+                }
+            }
+
+            this.holding = false;
+            inner.release();
+            // if it was notified again while waiting, queue the next notification
+            if inner.set.load(Relaxed) {
+                inner.queue();
+            }
+            Poll::Ready(Some(()))
         } else {
             Poll::Ready(None)
         }
     }
 }
 
+impl Drop for Receiver {
+    fn drop(&mut self) {
+        if let Some(inner) = self.inner.upgrade() {
+            inner.closed.store(true, Relaxed);
+            inner.release();
+        }
+    }
+}
+
 impl Hash for Sender {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
         Arc::as_ptr(&self.0).hash(state)
@@ -92,13 +233,13 @@ impl Eq for Sender {}
 
 impl Hash for Receiver {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        Weak::as_ptr(&self.0).hash(state)
+        Weak::as_ptr(&self.inner).hash(state)
     }
 }
 
 impl PartialEq for Receiver {
     fn eq(&self, other: &Self) -> bool {
-        Weak::ptr_eq(&self.0, &other.0)
+        Weak::ptr_eq(&self.inner, &other.inner)
     }
 }
 
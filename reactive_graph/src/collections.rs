@@ -0,0 +1,60 @@
+//! Reactive collections with fine-grained tracking of their entries.
+//!
+//! Reading a single entry of an [`ArcRwSignal<Vec<T>>`](crate::signal::ArcRwSignal) or
+//! `ArcRwSignal<HashMap<K, V>>` subscribes to the whole collection, so every change to any entry
+//! notifies every subscriber. [`ReactiveVec`] and [`ReactiveMap`] track their length (or set of
+//! keys), each individual index or key, and the collection as a whole separately, and their
+//! mutation methods only notify the parts that changed.
+//!
+//! ```rust
+//! # use reactive_graph::prelude::*;
+//! # let owner = reactive_graph::owner::Owner::new(); owner.set();
+//! use reactive_graph::{collections::ReactiveMap, computed::ArcMemo};
+//!
+//! let scores = ReactiveMap::<&str, u32>::new();
+//! let alice = ArcMemo::new({
+//!     let scores = scores.clone();
+//!     move |_| scores.get(&"alice").unwrap_or_default()
+//! });
+//! assert_eq!(alice.get(), 0);
+//!
+//! scores.insert("alice", 10);
+//! assert_eq!(alice.get(), 10);
+//!
+//! // updating another key does not notify `alice`
+//! scores.insert("bob", 5);
+//! ```
+//!
+//! ## Rendering lists
+//! To render a collection with a keyed list (like the `<For/>` component in `leptos`), iterate
+//! over the keys of a [`ReactiveMap`] with [`keys`](ReactiveMap::keys), which only tracks when
+//! keys are added or removed, and read each value in the row itself with
+//! [`get`](ReactiveMap::get). Changing a value then only updates its own row, rather than
+//! re-running the whole list. In the same way, a [`ReactiveVec`] can be rendered by iterating
+//! over its [`indices`](ReactiveVec::indices), which only tracks its length.
+//!
+//! Both collections also implement the same read traits as signals, like
+//! [`With`](crate::traits::With) and [`Get`](crate::traits::Get), which subscribe to every
+//! change.
+
+mod map;
+mod vec;
+
+pub use map::*;
+pub use vec::*;
+
+use crate::{signal::ArcTrigger, traits::Notify};
+use or_poisoned::OrPoisoned;
+
+/// Returns `true` if nothing is currently subscribed to the trigger.
+fn is_unused(trigger: &ArcTrigger) -> bool {
+    trigger.inner.read().or_poisoned().len() == 0
+}
+
+/// Notifies each trigger. This should only be called once the collection's locks have been
+/// released, so that subscribers can read from it.
+fn notify_all(triggers: impl IntoIterator<Item = ArcTrigger>) {
+    for trigger in triggers {
+        trigger.notify();
+    }
+}
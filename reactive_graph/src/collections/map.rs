@@ -0,0 +1,398 @@
+use super::{is_unused, notify_all};
+use crate::{
+    signal::{
+        guards::{Plain, ReadGuard},
+        ArcTrigger,
+    },
+    traits::{DefinedAt, ReadUntracked, Track, With},
+};
+use or_poisoned::OrPoisoned;
+use rustc_hash::FxHashMap;
+use std::{
+    borrow::Borrow,
+    collections::HashMap,
+    fmt::Debug,
+    hash::Hash,
+    panic::Location,
+    sync::{Arc, Mutex, RwLock},
+};
+
+/// A reactive [`HashMap`] that tracks its set of keys and each of its values separately.
+///
+/// Reading a value with [`get`](ReactiveMap::get) or [`with_key`](ReactiveMap::with_key) only
+/// subscribes to that key, and [`keys`](ReactiveMap::keys) and [`len`](ReactiveMap::len) only
+/// subscribe to keys being added or removed.
+/// Reading the whole collection, with the [`Read`](crate::traits::Read), [`With`] or
+/// [`Get`](crate::traits::Get) traits or with [`iter`](ReactiveMap::iter), subscribes to every
+/// change.
+///
+/// To render the entries in a keyed list, iterate over [`keys`](ReactiveMap::keys), and read each
+/// value in its own row with [`get`](ReactiveMap::get).
+///
+/// ```rust
+/// # use reactive_graph::prelude::*;
+/// # let owner = reactive_graph::owner::Owner::new(); owner.set();
+/// use reactive_graph::{collections::ReactiveMap, computed::ArcMemo};
+///
+/// let stock = ReactiveMap::from_iter([("apples", 3), ("pears", 0)]);
+/// let apples = ArcMemo::new({
+///     let stock = stock.clone();
+///     move |_| stock.get("apples")
+/// });
+/// let products = ArcMemo::new({
+///     let stock = stock.clone();
+///     move |_| {
+///         let mut keys = stock.keys();
+///         keys.sort();
+///         keys
+///     }
+/// });
+/// assert_eq!(apples.get(), Some(3));
+/// assert_eq!(products.get(), ["apples", "pears"]);
+///
+/// // notifies `apples`, but not `products`
+/// stock.insert("apples", 2);
+/// assert_eq!(apples.get(), Some(2));
+///
+/// // notifies `products`, but not `apples`
+/// stock.insert("plums", 5);
+/// assert_eq!(products.get(), ["apples", "pears", "plums"]);
+/// ```
+pub struct ReactiveMap<K, V> {
+    #[cfg(any(debug_assertions, leptos_debuginfo))]
+    defined_at: &'static Location<'static>,
+    inner: Arc<MapInner<K, V>>,
+}
+
+struct MapInner<K, V> {
+    entries: Arc<RwLock<HashMap<K, V>>>,
+    keys: ArcTrigger,
+    all: ArcTrigger,
+    triggers: Mutex<FxHashMap<K, ArcTrigger>>,
+}
+
+impl<K, V> Clone for ReactiveMap<K, V> {
+    #[track_caller]
+    fn clone(&self) -> Self {
+        Self {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: self.defined_at,
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+impl<K: Debug, V: Debug> Debug for ReactiveMap<K, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReactiveMap")
+            .field("entries", &*self.inner.entries.read().or_poisoned())
+            .finish()
+    }
+}
+
+impl<K, V> Default for ReactiveMap<K, V> {
+    #[track_caller]
+    fn default() -> Self {
+        Self::from(HashMap::new())
+    }
+}
+
+impl<K, V> From<HashMap<K, V>> for ReactiveMap<K, V> {
+    #[track_caller]
+    fn from(entries: HashMap<K, V>) -> Self {
+        Self {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: Location::caller(),
+            inner: Arc::new(MapInner {
+                entries: Arc::new(RwLock::new(entries)),
+                keys: ArcTrigger::new(),
+                all: ArcTrigger::new(),
+                triggers: Default::default(),
+            }),
+        }
+    }
+}
+
+impl<K: Eq + Hash, V> FromIterator<(K, V)> for ReactiveMap<K, V> {
+    #[track_caller]
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Self::from(iter.into_iter().collect::<HashMap<_, _>>())
+    }
+}
+
+impl<K, V> DefinedAt for ReactiveMap<K, V> {
+    fn defined_at(&self) -> Option<&'static Location<'static>> {
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        {
+            Some(self.defined_at)
+        }
+        #[cfg(not(any(debug_assertions, leptos_debuginfo)))]
+        {
+            None
+        }
+    }
+}
+
+impl<K, V> Track for ReactiveMap<K, V> {
+    fn track(&self) {
+        self.inner.all.track();
+    }
+}
+
+impl<K: 'static, V: 'static> ReadUntracked for ReactiveMap<K, V> {
+    type Value = ReadGuard<HashMap<K, V>, Plain<HashMap<K, V>>>;
+
+    fn try_read_untracked(&self) -> Option<Self::Value> {
+        Plain::try_new(Arc::clone(&self.inner.entries)).map(ReadGuard::new)
+    }
+}
+
+impl<K: Eq + Hash + Clone, V> ReactiveMap<K, V> {
+    /// Creates a new, empty collection.
+    #[track_caller]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of entries, subscribing to keys being added or removed.
+    pub fn len(&self) -> usize {
+        self.inner.keys.track();
+        self.inner.entries.read().or_poisoned().len()
+    }
+
+    /// Returns `true` if there are no entries, subscribing to keys being added or removed.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if there is a value for the key, subscribing only to changes for that key.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.with_key(key, |_| ()).is_some()
+    }
+
+    /// Applies a function to the value for the key, if there is one, subscribing only to changes
+    /// for that key.
+    pub fn with_key<Q, U>(
+        &self,
+        key: &Q,
+        fun: impl FnOnce(&V) -> U,
+    ) -> Option<U>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.track_key(key);
+        self.with_key_untracked(key, fun)
+    }
+
+    /// Applies a function to the value for the key, if there is one, without tracking it.
+    pub fn with_key_untracked<Q, U>(
+        &self,
+        key: &Q,
+        fun: impl FnOnce(&V) -> U,
+    ) -> Option<U>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.inner.entries.read().or_poisoned().get(key).map(fun)
+    }
+
+    /// Returns a copy of every key, subscribing to keys being added or removed, but not to
+    /// changes in their values.
+    pub fn keys(&self) -> Vec<K> {
+        self.inner.keys.track();
+        self.keys_untracked()
+    }
+
+    /// Returns a copy of every key, without tracking them.
+    pub fn keys_untracked(&self) -> Vec<K> {
+        self.inner
+            .entries
+            .read()
+            .or_poisoned()
+            .keys()
+            .cloned()
+            .collect()
+    }
+
+    /// Inserts a value for the key and returns the previous one, if any, notifying that key, as
+    /// well as the set of keys if it is new.
+    pub fn insert(&self, key: K, value: V) -> Option<V> {
+        let prev = self
+            .inner
+            .entries
+            .write()
+            .or_poisoned()
+            .insert(key.clone(), value);
+        self.notify_changed([key], prev.is_none());
+        prev
+    }
+
+    /// Removes the value for the key and returns it, if there was one, notifying that key and the
+    /// set of keys.
+    pub fn remove<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        let (key, value) =
+            self.inner.entries.write().or_poisoned().remove_entry(key)?;
+        self.notify_changed([key], true);
+        Some(value)
+    }
+
+    /// Updates the value for the key in place, if there is one, notifying only that key.
+    ///
+    /// Returns `true` if there was a value to update.
+    pub fn update<Q>(&self, key: &Q, fun: impl FnOnce(&mut V)) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        let key = {
+            let mut entries = self.inner.entries.write().or_poisoned();
+            let Some(value) = entries.get_mut(key) else {
+                return false;
+            };
+            fun(value);
+            entries.get_key_value(key).map(|(key, _)| key.clone())
+        };
+        self.notify_changed(key, false);
+        true
+    }
+
+    /// Inserts every entry from the iterator, notifying each of their keys, as well as the set of
+    /// keys if any of them are new.
+    pub fn extend(&self, iter: impl IntoIterator<Item = (K, V)>) {
+        let mut keys = Vec::new();
+        let mut added = false;
+        {
+            let mut entries = self.inner.entries.write().or_poisoned();
+            for (key, value) in iter {
+                added |= entries.insert(key.clone(), value).is_none();
+                keys.push(key);
+            }
+        }
+        if !keys.is_empty() {
+            self.notify_changed(keys, added);
+        }
+    }
+
+    /// Removes every entry, notifying each of their keys and the set of keys.
+    pub fn clear(&self) {
+        self.retain(|_, _| false);
+    }
+
+    /// Keeps only the entries for which the predicate returns `true`, notifying each removed key
+    /// and the set of keys.
+    pub fn retain(&self, mut fun: impl FnMut(&K, &V) -> bool) {
+        let mut removed = Vec::new();
+        self.inner
+            .entries
+            .write()
+            .or_poisoned()
+            .retain(|key, value| {
+                let keep = fun(key, value);
+                if !keep {
+                    removed.push(key.clone());
+                }
+                keep
+            });
+        if !removed.is_empty() {
+            self.notify_changed(removed, true);
+        }
+    }
+
+    /// Subscribes to changes for the key. While there is no value for it, this subscribes to the
+    /// set of keys instead, which will be notified when it is inserted.
+    fn track_key<Q>(&self, key: &Q)
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        let trigger = {
+            let mut triggers = self.inner.triggers.lock().or_poisoned();
+            match triggers.get(key) {
+                Some(trigger) => trigger.clone(),
+                None => {
+                    match self
+                        .inner
+                        .entries
+                        .read()
+                        .or_poisoned()
+                        .get_key_value(key)
+                    {
+                        Some((key, _)) => {
+                            triggers.entry(key.clone()).or_default().clone()
+                        }
+                        None => self.inner.keys.clone(),
+                    }
+                }
+            }
+        };
+        trigger.track();
+    }
+
+    /// Notifies the given keys and the whole collection, as well as the set of keys if it has
+    /// changed.
+    fn notify_changed(
+        &self,
+        keys: impl IntoIterator<Item = K>,
+        keys_changed: bool,
+    ) {
+        let triggers = {
+            let mut triggers = self.inner.triggers.lock().or_poisoned();
+            let entries = self.inner.entries.read().or_poisoned();
+            let changed = keys
+                .into_iter()
+                .filter_map(|key| triggers.get(&key).cloned())
+                .chain(keys_changed.then(|| self.inner.keys.clone()))
+                .chain([self.inner.all.clone()])
+                .collect::<Vec<_>>();
+            // forget the triggers for keys that have been removed, once nothing is tracking them
+            if keys_changed {
+                triggers.retain(|key, trigger| {
+                    entries.contains_key(key) || !is_unused(trigger)
+                });
+            }
+            changed
+        };
+        notify_all(triggers);
+    }
+}
+
+impl<K: Eq + Hash + Clone + 'static, V: Clone + 'static> ReactiveMap<K, V> {
+    /// Clones the value for the key, if there is one, subscribing only to changes for that key.
+    pub fn get<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.with_key(key, V::clone)
+    }
+
+    /// Clones the value for the key, if there is one, without tracking it.
+    pub fn get_untracked<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.with_key_untracked(key, V::clone)
+    }
+
+    /// Returns an iterator over clones of all the entries, subscribing to any change.
+    pub fn iter(&self) -> std::vec::IntoIter<(K, V)> {
+        self.with(|entries| {
+            entries
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect::<Vec<_>>()
+        })
+        .into_iter()
+    }
+}
@@ -0,0 +1,416 @@
+use super::{is_unused, notify_all};
+use crate::{
+    signal::{
+        guards::{Plain, ReadGuard},
+        ArcTrigger,
+    },
+    traits::{DefinedAt, ReadUntracked, Track, With, WithUntracked},
+};
+use or_poisoned::OrPoisoned;
+use rustc_hash::FxHashMap;
+use std::{
+    fmt::Debug,
+    ops::Range,
+    panic::Location,
+    sync::{Arc, Mutex, RwLock},
+};
+
+/// A reactive [`Vec`] that tracks its length and each of its items separately.
+///
+/// Reading an item with [`get`](ReactiveVec::get) or [`with_item`](ReactiveVec::with_item) only
+/// subscribes to that index, and [`len`](ReactiveVec::len) only subscribes to the length. Reading
+/// the whole collection, with the [`Read`](crate::traits::Read), [`With`] or
+/// [`Get`](crate::traits::Get) traits or with [`iter`](ReactiveVec::iter), subscribes to every
+/// change.
+///
+/// To render the items in a keyed list, iterate over [`indices`](ReactiveVec::indices), which
+/// only tracks the length, and read each item in its own row with [`get`](ReactiveVec::get).
+///
+/// Mutations notify the indices whose items have changed: [`set`](ReactiveVec::set) only notifies
+/// that index, [`push`](ReactiveVec::push) notifies the length and the new index, and
+/// [`insert`](ReactiveVec::insert) or [`remove`](ReactiveVec::remove) notify the length and every
+/// index from the one that has changed to the end, because those items have moved.
+///
+/// ```rust
+/// # use reactive_graph::prelude::*;
+/// # let owner = reactive_graph::owner::Owner::new(); owner.set();
+/// use reactive_graph::{collections::ReactiveVec, computed::ArcMemo};
+///
+/// let todos = ReactiveVec::from(vec!["wash dishes", "walk the dog"]);
+/// let first = ArcMemo::new({
+///     let todos = todos.clone();
+///     move |_| todos.get(0)
+/// });
+/// let count = ArcMemo::new({
+///     let todos = todos.clone();
+///     move |_| todos.len()
+/// });
+/// assert_eq!(first.get(), Some("wash dishes"));
+/// assert_eq!(count.get(), 2);
+///
+/// // notifies `count`, but not `first`
+/// todos.push("do homework");
+/// assert_eq!(count.get(), 3);
+///
+/// // notifies `first`, but not `count`
+/// todos.set(0, "dry dishes");
+/// assert_eq!(first.get(), Some("dry dishes"));
+/// ```
+pub struct ReactiveVec<T> {
+    #[cfg(any(debug_assertions, leptos_debuginfo))]
+    defined_at: &'static Location<'static>,
+    inner: Arc<VecInner<T>>,
+}
+
+struct VecInner<T> {
+    items: Arc<RwLock<Vec<T>>>,
+    len: ArcTrigger,
+    all: ArcTrigger,
+    indices: Mutex<FxHashMap<usize, ArcTrigger>>,
+}
+
+impl<T> Clone for ReactiveVec<T> {
+    #[track_caller]
+    fn clone(&self) -> Self {
+        Self {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: self.defined_at,
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+impl<T: Debug> Debug for ReactiveVec<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReactiveVec")
+            .field("items", &*self.inner.items.read().or_poisoned())
+            .finish()
+    }
+}
+
+impl<T> Default for ReactiveVec<T> {
+    #[track_caller]
+    fn default() -> Self {
+        Self::from(Vec::new())
+    }
+}
+
+impl<T> From<Vec<T>> for ReactiveVec<T> {
+    #[track_caller]
+    fn from(items: Vec<T>) -> Self {
+        Self {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: Location::caller(),
+            inner: Arc::new(VecInner {
+                items: Arc::new(RwLock::new(items)),
+                len: ArcTrigger::new(),
+                all: ArcTrigger::new(),
+                indices: Default::default(),
+            }),
+        }
+    }
+}
+
+impl<T> FromIterator<T> for ReactiveVec<T> {
+    #[track_caller]
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from(iter.into_iter().collect::<Vec<_>>())
+    }
+}
+
+impl<T> DefinedAt for ReactiveVec<T> {
+    fn defined_at(&self) -> Option<&'static Location<'static>> {
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        {
+            Some(self.defined_at)
+        }
+        #[cfg(not(any(debug_assertions, leptos_debuginfo)))]
+        {
+            None
+        }
+    }
+}
+
+impl<T> Track for ReactiveVec<T> {
+    fn track(&self) {
+        self.inner.all.track();
+    }
+}
+
+impl<T: 'static> ReadUntracked for ReactiveVec<T> {
+    type Value = ReadGuard<Vec<T>, Plain<Vec<T>>>;
+
+    fn try_read_untracked(&self) -> Option<Self::Value> {
+        Plain::try_new(Arc::clone(&self.inner.items)).map(ReadGuard::new)
+    }
+}
+
+impl<T> ReactiveVec<T> {
+    /// Creates a new, empty collection.
+    #[track_caller]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of items, subscribing to changes in the length.
+    pub fn len(&self) -> usize {
+        self.inner.len.track();
+        self.inner.items.read().or_poisoned().len()
+    }
+
+    /// Returns `true` if there are no items, subscribing to changes in the length.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the range of indices that have an item, subscribing to changes in the length.
+    ///
+    /// This can be used as the items of a keyed list (like the `<For/>` component in `leptos`),
+    /// using the index as the key and reading the item inside the row with
+    /// [`get`](ReactiveVec::get), so that changing an item only updates its own row.
+    pub fn indices(&self) -> Range<usize> {
+        0..self.len()
+    }
+
+    /// Applies a function to the item at the given index, if there is one, subscribing only to
+    /// changes at that index.
+    pub fn with_item<U>(
+        &self,
+        index: usize,
+        fun: impl FnOnce(&T) -> U,
+    ) -> Option<U> {
+        self.index_trigger(index).track();
+        self.with_item_untracked(index, fun)
+    }
+
+    /// Applies a function to the item at the given index, if there is one, without tracking it.
+    pub fn with_item_untracked<U>(
+        &self,
+        index: usize,
+        fun: impl FnOnce(&T) -> U,
+    ) -> Option<U> {
+        self.inner.items.read().or_poisoned().get(index).map(fun)
+    }
+
+    /// Appends an item to the end, notifying the length and the new index.
+    pub fn push(&self, value: T) {
+        let index = {
+            let mut items = self.inner.items.write().or_poisoned();
+            items.push(value);
+            items.len() - 1
+        };
+        self.notify_changed(index..index + 1, true);
+    }
+
+    /// Removes the last item and returns it, if there is one, notifying the length and the index
+    /// it was removed from.
+    pub fn pop(&self) -> Option<T> {
+        let (value, len) = {
+            let mut items = self.inner.items.write().or_poisoned();
+            (items.pop()?, items.len())
+        };
+        self.notify_changed(len..len + 1, true);
+        Some(value)
+    }
+
+    /// Inserts an item at the given index, notifying the length and every index from there to
+    /// the end.
+    ///
+    /// # Panics
+    /// Panics if `index > len`.
+    #[track_caller]
+    pub fn insert(&self, index: usize, value: T) {
+        let len = {
+            let mut items = self.inner.items.write().or_poisoned();
+            items.insert(index, value);
+            items.len()
+        };
+        self.notify_changed(index..len, true);
+    }
+
+    /// Removes and returns the item at the given index, notifying the length and every index from
+    /// there to the end.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds.
+    #[track_caller]
+    pub fn remove(&self, index: usize) -> T {
+        let (value, len) = {
+            let mut items = self.inner.items.write().or_poisoned();
+            let len = items.len();
+            (items.remove(index), len)
+        };
+        self.notify_changed(index..len, true);
+        value
+    }
+
+    /// Replaces the item at the given index and returns the previous one, notifying only that
+    /// index.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds.
+    #[track_caller]
+    pub fn set(&self, index: usize, value: T) -> T {
+        let prev = std::mem::replace(
+            &mut self.inner.items.write().or_poisoned()[index],
+            value,
+        );
+        self.notify_changed(index..index + 1, false);
+        prev
+    }
+
+    /// Updates the item at the given index in place, if there is one, notifying only that index.
+    ///
+    /// Returns `true` if there was an item to update.
+    pub fn update(&self, index: usize, fun: impl FnOnce(&mut T)) -> bool {
+        let updated = self
+            .inner
+            .items
+            .write()
+            .or_poisoned()
+            .get_mut(index)
+            .map(fun)
+            .is_some();
+        if updated {
+            self.notify_changed(index..index + 1, false);
+        }
+        updated
+    }
+
+    /// Swaps the items at the two indices, notifying only those indices.
+    ///
+    /// # Panics
+    /// Panics if either index is out of bounds.
+    #[track_caller]
+    pub fn swap(&self, a: usize, b: usize) {
+        self.inner.items.write().or_poisoned().swap(a, b);
+        if a != b {
+            let triggers = {
+                let indices = self.inner.indices.lock().or_poisoned();
+                [a, b]
+                    .iter()
+                    .filter_map(|index| indices.get(index).cloned())
+                    .chain([self.inner.all.clone()])
+                    .collect::<Vec<_>>()
+            };
+            notify_all(triggers);
+        }
+    }
+
+    /// Shortens the collection to the given length, notifying the length and every removed
+    /// index. Does nothing if it is already shorter.
+    pub fn truncate(&self, len: usize) {
+        let prev_len = {
+            let mut items = self.inner.items.write().or_poisoned();
+            let prev_len = items.len();
+            items.truncate(len);
+            prev_len
+        };
+        if len < prev_len {
+            self.notify_changed(len..prev_len, true);
+        }
+    }
+
+    /// Removes every item, notifying the length and every index.
+    pub fn clear(&self) {
+        self.truncate(0);
+    }
+
+    /// Keeps only the items for which the predicate returns `true`, notifying the length and every
+    /// index from the first one that was removed to the end.
+    pub fn retain(&self, mut fun: impl FnMut(&T) -> bool) {
+        let mut first_removed = None;
+        let prev_len = {
+            let mut items = self.inner.items.write().or_poisoned();
+            let prev_len = items.len();
+            let mut index = 0;
+            items.retain(|item| {
+                let keep = fun(item);
+                if !keep && first_removed.is_none() {
+                    first_removed = Some(index);
+                }
+                index += 1;
+                keep
+            });
+            prev_len
+        };
+        if let Some(first_removed) = first_removed {
+            self.notify_changed(first_removed..prev_len, true);
+        }
+    }
+
+    fn index_trigger(&self, index: usize) -> ArcTrigger {
+        self.inner
+            .indices
+            .lock()
+            .or_poisoned()
+            .entry(index)
+            .or_default()
+            .clone()
+    }
+
+    /// Notifies the indices in the given range and the whole collection, as well as the length if
+    /// it has changed.
+    fn notify_changed(&self, range: Range<usize>, len_changed: bool) {
+        let triggers = {
+            let len = self.inner.items.read().or_poisoned().len();
+            let mut indices = self.inner.indices.lock().or_poisoned();
+            let triggers = range
+                .filter_map(|index| indices.get(&index).cloned())
+                .chain(len_changed.then(|| self.inner.len.clone()))
+                .chain([self.inner.all.clone()])
+                .collect::<Vec<_>>();
+            // forget the triggers for indices that no longer exist, once nothing is tracking them
+            if len_changed {
+                indices.retain(|index, trigger| {
+                    *index < len || !is_unused(trigger)
+                });
+            }
+            triggers
+        };
+        notify_all(triggers);
+    }
+}
+
+impl<T: Clone + 'static> ReactiveVec<T> {
+    /// Clones the item at the given index, if there is one, subscribing only to changes at that
+    /// index.
+    pub fn get(&self, index: usize) -> Option<T> {
+        self.with_item(index, T::clone)
+    }
+
+    /// Clones the item at the given index, if there is one, without tracking it.
+    pub fn get_untracked(&self, index: usize) -> Option<T> {
+        self.with_item_untracked(index, T::clone)
+    }
+
+    /// Clones all the items, subscribing to any change.
+    pub fn to_vec(&self) -> Vec<T> {
+        self.with(|items| items.to_vec())
+    }
+
+    /// Clones all the items, without tracking them.
+    pub fn to_vec_untracked(&self) -> Vec<T> {
+        self.with_untracked(|items| items.to_vec())
+    }
+
+    /// Returns an iterator over clones of all the items, subscribing to any change.
+    pub fn iter(&self) -> std::vec::IntoIter<T> {
+        self.to_vec().into_iter()
+    }
+}
+
+impl<T> ReactiveVec<T> {
+    /// Appends every item from the iterator, notifying the length and the new indices.
+    pub fn extend(&self, iter: impl IntoIterator<Item = T>) {
+        let (prev_len, len) = {
+            let mut items = self.inner.items.write().or_poisoned();
+            let prev_len = items.len();
+            items.extend(iter);
+            (prev_len, items.len())
+        };
+        if len > prev_len {
+            self.notify_changed(prev_len..len, true);
+        }
+    }
+}
@@ -9,7 +9,7 @@ use crate::{
         guards::{Mapped, Plain, ReadGuard},
         ArcReadSignal, ArcRwSignal,
     },
-    traits::{DefinedAt, Get, IsDisposed, ReadUntracked},
+    traits::{DefinedAt, Get, IsDisposed, ReadUntracked, WithName},
 };
 use core::fmt::Debug;
 use std::{
@@ -94,7 +94,7 @@ where
 {
     #[cfg(any(debug_assertions, leptos_debuginfo))]
     defined_at: &'static Location<'static>,
-    inner: Arc<MemoInner<T, S>>,
+    pub(crate) inner: Arc<MemoInner<T, S>>,
 }
 
 impl<T: 'static> ArcMemo<T, SyncStorage>
@@ -154,13 +154,32 @@ where
     pub fn new_owning(
         fun: impl Fn(Option<T>) -> (T, bool) + Send + Sync + 'static,
     ) -> Self {
+        #[cfg(any(debug_assertions, feature = "devtools"))]
+        let defined_at = Location::caller();
         let inner = Arc::new_cyclic(|weak| {
             let subscriber = AnySubscriber(
                 weak.as_ptr() as usize,
                 Weak::clone(weak) as Weak<dyn Subscriber + Send + Sync>,
             );
 
-            MemoInner::new(Arc::new(fun), subscriber)
+            #[cfg_attr(
+                not(any(debug_assertions, feature = "devtools")),
+                allow(unused_mut)
+            )]
+            let mut inner = MemoInner::new(Arc::new(fun), subscriber);
+            #[cfg(debug_assertions)]
+            {
+                inner.defined_at = Some(defined_at);
+            }
+            #[cfg(feature = "devtools")]
+            {
+                inner.devtools = Some(crate::devtools::Registration::new(
+                    weak.as_ptr() as usize,
+                    crate::devtools::NodeKind::Memo,
+                    Some(defined_at),
+                ));
+            }
+            inner
         });
         Self {
             #[cfg(any(debug_assertions, leptos_debuginfo))]
@@ -198,6 +217,20 @@ where
             None
         }
     }
+
+    fn debug_name(&self) -> Option<Arc<str>> {
+        crate::diagnostics::node_name(crate::diagnostics::arc_id(&self.inner))
+    }
+}
+
+impl<T: 'static, S> WithName for ArcMemo<T, S>
+where
+    S: Storage<T>,
+{
+    fn with_name(self, name: impl Into<Arc<str>>) -> Self {
+        crate::diagnostics::set_node_name(&self.inner, name.into());
+        self
+    }
 }
 
 impl<T, S> Debug for ArcMemo<T, S>
@@ -0,0 +1,221 @@
+use or_poisoned::OrPoisoned;
+use pin_project_lite::pin_project;
+use std::{
+    cell::RefCell,
+    fmt::Debug,
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    task::{Context, Poll, Waker},
+    time::Duration,
+};
+
+/// Signals that the `Future` of an [`ArcAsyncDerived`](super::ArcAsyncDerived) or
+/// [`AsyncDerived`](super::AsyncDerived) has been superseded by a newer run, or that the async
+/// derived value has been dropped.
+///
+/// Superseded futures are dropped and will not be polled again, but any work they have started
+/// elsewhere (for example, a request that is being handled by a server) keeps running. An
+/// `AbortSignal` can be used to cancel that work, too. It is available from [`use_abort_signal`]
+/// inside the function that creates the `Future`, or while the `Future` itself is being polled.
+///
+/// ```rust
+/// # use reactive_graph::computed::*;
+/// # use reactive_graph::signal::*; let owner = reactive_graph::owner::Owner::new(); owner.set();
+/// # use reactive_graph::prelude::*;
+/// # tokio_test::block_on(async move {
+/// # any_spawner::Executor::init_tokio(); let owner = reactive_graph::owner::Owner::new(); owner.set();
+/// # let _guard = reactive_graph::diagnostics::SpecialNonReactiveZone::enter();
+/// let query = RwSignal::new("rust".to_string());
+/// let results = ArcAsyncDerived::new(move || {
+///     let query = query.get();
+///     let abort = use_abort_signal().expect("called inside an async derived");
+///     async move {
+///         // pass the signal on to anything that can be cancelled
+///         abort.on_abort({
+///             let query = query.clone();
+///             move || println!("no longer searching for {query}")
+///         });
+///         tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+///         format!("results for {query}")
+///     }
+/// });
+/// # assert_eq!(results.await, "results for rust");
+/// # });
+/// ```
+#[derive(Clone, Default)]
+pub struct AbortSignal {
+    inner: Arc<AbortInner>,
+}
+
+#[derive(Default)]
+struct AbortInner {
+    aborted: AtomicBool,
+    wakers: Mutex<Vec<Waker>>,
+    callbacks: Mutex<Vec<Box<dyn FnOnce() + Send + Sync>>>,
+}
+
+impl Debug for AbortSignal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AbortSignal")
+            .field("aborted", &self.is_aborted())
+            .finish()
+    }
+}
+
+impl AbortSignal {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns `true` if the `Future` has been aborted.
+    pub fn is_aborted(&self) -> bool {
+        self.inner.aborted.load(Ordering::Relaxed)
+    }
+
+    /// Runs the given function when the `Future` is aborted, or immediately if it already has
+    /// been.
+    pub fn on_abort(&self, fun: impl FnOnce() + Send + Sync + 'static) {
+        if self.is_aborted() {
+            fun();
+            return;
+        }
+        self.inner
+            .callbacks
+            .lock()
+            .or_poisoned()
+            .push(Box::new(fun));
+        // it may have been aborted while the callback was being added
+        if self.is_aborted() {
+            self.run_callbacks();
+        }
+    }
+
+    /// Returns a `Future` that resolves when the `Future` is aborted.
+    pub fn aborted(&self) -> Aborted {
+        Aborted {
+            signal: self.clone(),
+        }
+    }
+
+    pub(crate) fn abort(&self) {
+        if !self.inner.aborted.swap(true, Ordering::Relaxed) {
+            for waker in
+                std::mem::take(&mut *self.inner.wakers.lock().or_poisoned())
+            {
+                waker.wake();
+            }
+            self.run_callbacks();
+        }
+    }
+
+    /// Runs `fun` with this as the signal returned by [`use_abort_signal`].
+    pub(crate) fn run<T>(&self, fun: impl FnOnce() -> T) -> T {
+        struct Restore(Option<AbortSignal>);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                CURRENT_ABORT_SIGNAL.with(|current| {
+                    *current.borrow_mut() = self.0.take();
+                });
+            }
+        }
+
+        let _restore = Restore(
+            CURRENT_ABORT_SIGNAL
+                .with(|current| current.replace(Some(self.clone()))),
+        );
+        fun()
+    }
+
+    fn run_callbacks(&self) {
+        let callbacks =
+            std::mem::take(&mut *self.inner.callbacks.lock().or_poisoned());
+        for callback in callbacks {
+            callback();
+        }
+    }
+}
+
+/// A `Future` that resolves when an [`AbortSignal`] is aborted.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Aborted {
+    signal: AbortSignal,
+}
+
+impl Future for Aborted {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.signal.is_aborted() {
+            return Poll::Ready(());
+        }
+        self.signal
+            .inner
+            .wakers
+            .lock()
+            .or_poisoned()
+            .push(cx.waker().clone());
+        if self.signal.is_aborted() {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+thread_local! {
+    static CURRENT_ABORT_SIGNAL: RefCell<Option<AbortSignal>> =
+        const { RefCell::new(None) };
+}
+
+/// Returns the [`AbortSignal`] for the current run of an async derived value, if called inside
+/// the function that creates its `Future`, or while that `Future` is being polled.
+///
+/// The signal belongs to the run itself, not to the reactive owner: it is not available from
+/// other tasks spawned by the `Future`, or from child owners once the run has been created.
+pub fn use_abort_signal() -> Option<AbortSignal> {
+    CURRENT_ABORT_SIGNAL.with(|current| current.borrow().clone())
+}
+
+pin_project! {
+    /// A `Future` that makes an [`AbortSignal`] available from [`use_abort_signal`] while it is
+    /// being polled.
+    pub(crate) struct WithAbortSignal<Fut> {
+        signal: AbortSignal,
+        #[pin]
+        fut: Fut,
+    }
+}
+
+impl<Fut> WithAbortSignal<Fut> {
+    pub(crate) fn new(signal: AbortSignal, fut: Fut) -> Self {
+        Self { signal, fut }
+    }
+}
+
+impl<Fut: Future> Future for WithAbortSignal<Fut> {
+    type Output = Fut::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        this.signal.run(|| this.fut.poll(cx))
+    }
+}
+
+/// Options that control when an [`ArcAsyncDerived`](super::ArcAsyncDerived) or
+/// [`AsyncDerived`](super::AsyncDerived) re-runs its `Future` after its dependencies change.
+///
+/// Whenever a new run starts, the previous `Future` is dropped and its [`AbortSignal`] is aborted
+/// if it has not finished yet.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AsyncDerivedOptions {
+    /// Waits until the dependencies have not changed for this long before re-running.
+    pub debounce: Option<Duration>,
+    /// Re-runs at most once in this period; later changes are handled when it has elapsed.
+    pub throttle: Option<Duration>,
+}
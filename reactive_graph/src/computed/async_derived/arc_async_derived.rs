@@ -1,11 +1,12 @@
 use super::{
+    abort::WithAbortSignal,
     inner::{ArcAsyncDerivedInner, AsyncDerivedState},
-    AsyncDerivedReadyFuture, ScopedFuture,
+    AbortSignal, AsyncDerivedOptions, AsyncDerivedReadyFuture, ScopedFuture,
 };
 #[cfg(feature = "sandboxed-arenas")]
 use crate::owner::Sandboxed;
 use crate::{
-    channel::channel,
+    channel::{channel, Receiver},
     computed::suspense::SuspenseContext,
     diagnostics::SpecialNonReactiveFuture,
     graph::{
@@ -19,14 +20,19 @@ use crate::{
     },
     traits::{
         DefinedAt, IsDisposed, Notify, ReadUntracked, Track, UntrackableGuard,
-        Write,
+        WithName, Write,
     },
     transition::AsyncTransition,
 };
 use any_spawner::Executor;
 use async_lock::RwLock as AsyncRwLock;
 use core::fmt::Debug;
-use futures::{channel::oneshot, FutureExt, StreamExt};
+use futures::{
+    channel::oneshot,
+    future::{select, Either},
+    FutureExt, StreamExt,
+};
+use futures_timer::Delay;
 use or_poisoned::OrPoisoned;
 use send_wrapper::SendWrapper;
 use std::{
@@ -39,6 +45,7 @@ use std::{
         Arc, RwLock, Weak,
     },
     task::Waker,
+    time::Duration,
 };
 
 /// A reactive value that is derived by running an asynchronous computation in response to changes
@@ -215,6 +222,17 @@ impl<T> DefinedAt for ArcAsyncDerived<T> {
             None
         }
     }
+
+    fn debug_name(&self) -> Option<Arc<str>> {
+        crate::diagnostics::node_name(crate::diagnostics::arc_id(&self.inner))
+    }
+}
+
+impl<T> WithName for ArcAsyncDerived<T> {
+    fn with_name(self, name: impl Into<Arc<str>>) -> Self {
+        crate::diagnostics::set_node_name(&self.inner, name.into());
+        self
+    }
 }
 
 // This helps create a derived async signal.
@@ -222,7 +240,8 @@ impl<T> DefinedAt for ArcAsyncDerived<T> {
 // whether `fun` returns a `Future` that is `Send`. Doing it as a function would,
 // as far as I can tell, require repeating most of the function body.
 macro_rules! spawn_derived {
-    ($spawner:expr, $initial:ident, $fun:ident, $should_spawn:literal, $force_spawn:literal, $should_track:literal, $source:expr) => {{
+    ($spawner:expr, $initial:ident, $fun:ident, $should_spawn:literal, $force_spawn:literal, $should_track:literal, $source:expr, $options:expr) => {{
+        let options: AsyncDerivedOptions = $options;
         let (notifier, mut rx) = channel();
 
         let is_ready = $initial.is_some() && !$force_spawn;
@@ -235,8 +254,19 @@ macro_rules! spawn_derived {
             subscribers: SubscriberSet::new(),
             state: AsyncDerivedState::Clean,
             version: 0,
-            suspenses: Vec::new()
+            suspenses: Vec::new(),
+            #[cfg(feature = "devtools")]
+            devtools: None,
         }));
+        #[cfg(feature = "devtools")]
+        {
+            inner.write().or_poisoned().devtools =
+                Some(crate::devtools::Registration::new(
+                    Arc::as_ptr(&inner) as usize,
+                    crate::devtools::NodeKind::AsyncDerived,
+                    Some(Location::caller()),
+                ));
+        }
         let value = Arc::new(AsyncRwLock::new($initial));
         let wakers = Arc::new(RwLock::new(Vec::new()));
 
@@ -249,15 +279,21 @@ macro_rules! spawn_derived {
             loading: Arc::new(AtomicBool::new(!is_ready)),
         };
         let any_subscriber = this.to_any_subscriber();
+        // aborted when the current run is superseded, or the async derived is dropped
+        let mut abort = AbortSignal::new();
         let initial_fut = if $should_track {
             owner.with_cleanup(|| {
-                any_subscriber
-                    .with_observer(|| ScopedFuture::new($fun()))
+                let fut = abort.run(|| {
+                    any_subscriber.with_observer(|| ScopedFuture::new($fun()))
+                });
+                WithAbortSignal::new(abort.clone(), fut)
             })
         } else {
             owner.with_cleanup(|| {
-                any_subscriber
-                    .with_observer_untracked(|| ScopedFuture::new($fun()))
+                let fut = abort.run(|| {
+                    any_subscriber.with_observer_untracked(|| ScopedFuture::new($fun()))
+                });
+                WithAbortSignal::new(abort.clone(), fut)
             })
         };
         #[cfg(feature = "sandboxed-arenas")]
@@ -319,11 +355,21 @@ macro_rules! spawn_derived {
                         .and_then(|inner| inner.read().ok())
                         .map(|inner| inner.state == AsyncDerivedState::Dirty)
                         .unwrap_or(false);
-                    if already_dirty {
-                        initial_fut.take();
+                    if already_dirty && initial_fut.take().is_some() {
+                        abort.abort();
                     }
 
-                    while rx.next().await.is_some() {
+                    let mut throttle = options.throttle.map(Delay::new);
+                    // set when a pending Future has been superseded, so that a new one should be
+                    // created without waiting for another notification
+                    let mut superseded = false;
+                    // suspense task IDs are held until a run actually finishes
+                    let mut suspense_ids = Vec::new();
+
+                    loop {
+                        if !mem::take(&mut superseded) && rx.next().await.is_none() {
+                            break;
+                        }
                         let update_if_necessary = if $should_track {
                             any_subscriber
                                 .with_observer(|| any_subscriber.update_if_necessary())
@@ -332,54 +378,110 @@ macro_rules! spawn_derived {
                                 .with_observer_untracked(|| any_subscriber.update_if_necessary())
                         };
                         if update_if_necessary || first_run.is_some() {
-                            match (value.upgrade(), inner.upgrade(), wakers.upgrade(), loading.upgrade()) {
-                                (Some(value), Some(inner), Some(wakers), Some(loading)) => {
-                                    // generate new Future
-                                    let owner = inner.read().or_poisoned().owner.clone();
-                                    let fut = initial_fut.take().unwrap_or_else(|| {
-                                        let fut = if $should_track {
-                                            owner.with_cleanup(|| {
-                                                any_subscriber
-                                                    .with_observer(|| ScopedFuture::new($fun()))
-                                            })
-                                        } else {
-                                            owner.with_cleanup(|| {
-                                                any_subscriber
-                                                    .with_observer_untracked(|| ScopedFuture::new($fun()))
-                                            })
-                                        };
-                                        #[cfg(feature = "sandboxed-arenas")]
-                                        let fut = Sandboxed::new(fut);
-                                        Box::pin(fut)
-                                    });
-
-                                    // register with global transition listener, if any
-                                    let ready_tx = first_run.take().unwrap_or_else(|| {
-                                        let (ready_tx, ready_rx) = oneshot::channel();
-                                        if !was_ready {
-                                            AsyncTransition::register(ready_rx);
-                                        }
-                                        ready_tx
-                                    });
+                            // the first run is never delayed
+                            if initial_fut.is_none() {
+                                if let Some(throttle) = throttle.take() {
+                                    if !wait_to_rerun(&mut rx, throttle, None).await {
+                                        break;
+                                    }
+                                }
+                                if let Some(debounce) = options.debounce {
+                                    if !wait_to_rerun(&mut rx, Delay::new(debounce), Some(debounce)).await {
+                                        break;
+                                    }
+                                }
+                                throttle = options.throttle.map(Delay::new);
+                            }
 
-                                    // generate and assign new value
-                                    loading.store(true, Ordering::Relaxed);
+                            let Some(owner) = inner.upgrade().map(|inner| inner.read().or_poisoned().owner.clone()) else {
+                                break;
+                            };
+
+                            // generate new Future
+                            let mut fut = initial_fut.take().unwrap_or_else(|| {
+                                abort = AbortSignal::new();
+                                let fut = if $should_track {
+                                    owner.with_cleanup(|| {
+                                        let fut = abort.run(|| {
+                                            any_subscriber.with_observer(|| ScopedFuture::new($fun()))
+                                        });
+                                        WithAbortSignal::new(abort.clone(), fut)
+                                    })
+                                } else {
+                                    owner.with_cleanup(|| {
+                                        let fut = abort.run(|| {
+                                            any_subscriber.with_observer_untracked(|| ScopedFuture::new($fun()))
+                                        });
+                                        WithAbortSignal::new(abort.clone(), fut)
+                                    })
+                                };
+                                #[cfg(feature = "sandboxed-arenas")]
+                                let fut = Sandboxed::new(fut);
+                                Box::pin(fut)
+                            });
+
+                            // register with global transition listener, if any
+                            let ready_tx = first_run.take().unwrap_or_else(|| {
+                                let (ready_tx, ready_rx) = oneshot::channel();
+                                if !was_ready {
+                                    AsyncTransition::register(ready_rx);
+                                }
+                                ready_tx
+                            });
 
-                                    let (this_version, suspense_ids) = {
-                                        let mut guard = inner.write().or_poisoned();
-                                        guard.version += 1;
-                                        let version = guard.version;
-                                        let suspense_ids = mem::take(&mut guard.suspenses)
+                            // generate and assign new value
+                            let this_version = match (inner.upgrade(), loading.upgrade()) {
+                                (Some(inner), Some(loading)) => {
+                                    loading.store(true, Ordering::Relaxed);
+                                    let mut guard = inner.write().or_poisoned();
+                                    guard.version += 1;
+                                    suspense_ids.extend(
+                                        mem::take(&mut guard.suspenses)
                                             .into_iter()
-                                            .map(|sc| sc.task_id())
-                                            .collect::<Vec<_>>();
-                                        (version, suspense_ids)
-                                    };
-
-                                    let new_value = fut.await;
+                                            .map(|sc| sc.task_id()),
+                                    );
+                                    guard.version
+                                }
+                                _ => break,
+                            };
+
+                            // wait for the new value, unless the sources change in the meantime:
+                            // in that case, the stale Future is dropped and a new one is created
+                            let new_value = loop {
+                                match select(fut, rx.next()).await {
+                                    Either::Left((new_value, _)) => break Some(new_value),
+                                    Either::Right((None, _)) => break None,
+                                    Either::Right((Some(()), pending)) => {
+                                        let update_if_necessary = if $should_track {
+                                            any_subscriber
+                                                .with_observer(|| any_subscriber.update_if_necessary())
+                                        } else {
+                                            any_subscriber
+                                                .with_observer_untracked(|| any_subscriber.update_if_necessary())
+                                        };
+                                        if update_if_necessary {
+                                            superseded = true;
+                                            break None;
+                                        }
+                                        fut = pending;
+                                    }
+                                }
+                            };
+
+                            let Some(new_value) = new_value else {
+                                abort.abort();
+                                if superseded {
+                                    // hand the transition over to the next run
+                                    first_run = Some(ready_tx);
+                                    continue;
+                                }
+                                break;
+                            };
 
-                                    drop(suspense_ids);
+                            suspense_ids.clear();
 
+                            match (value.upgrade(), inner.upgrade(), wakers.upgrade(), loading.upgrade()) {
+                                (Some(value), Some(inner), Some(wakers), Some(loading)) => {
                                     let latest_version = inner.read().or_poisoned().version;
 
                                     if latest_version == this_version {
@@ -403,6 +505,28 @@ macro_rules! spawn_derived {
     }};
 }
 
+/// Waits for the delay to elapse before re-running an async derived value. If `debounce` is set,
+/// the delay restarts whenever the value is notified again.
+///
+/// Returns `false` if the async derived value is dropped in the meantime.
+async fn wait_to_rerun(
+    rx: &mut Receiver,
+    mut delay: Delay,
+    debounce: Option<Duration>,
+) -> bool {
+    loop {
+        match select(&mut delay, rx.next()).await {
+            Either::Left(_) => return true,
+            Either::Right((None, _)) => return false,
+            Either::Right((Some(()), _)) => {
+                if let Some(debounce) = debounce {
+                    delay.reset(debounce);
+                }
+            }
+        }
+    }
+}
+
 impl<T: 'static> ArcAsyncDerived<T> {
     async fn set_inner_value(
         new_value: T,
@@ -422,6 +546,8 @@ impl<T: 'static> ArcAsyncDerived<T> {
         loading: &Arc<AtomicBool>,
         ready_tx: Option<oneshot::Sender<()>>,
     ) {
+        #[cfg(feature = "devtools")]
+        crate::devtools::notified(Arc::as_ptr(inner) as usize);
         loading.store(false, Ordering::Relaxed);
 
         let prev_state = mem::replace(
@@ -486,7 +612,57 @@ impl<T: 'static> ArcAsyncDerived<T> {
             true,
             true,
             true,
-            None::<ArcTrigger>
+            None::<ArcTrigger>,
+            AsyncDerivedOptions::default()
+        );
+        this
+    }
+
+    /// Creates a new async derived computation, with options that control how it re-runs when
+    /// its dependencies change.
+    ///
+    /// ```rust
+    /// # use reactive_graph::computed::*;
+    /// # use reactive_graph::signal::*; let owner = reactive_graph::owner::Owner::new(); owner.set();
+    /// # use reactive_graph::prelude::*;
+    /// # use std::time::Duration;
+    /// # tokio_test::block_on(async move {
+    /// # any_spawner::Executor::init_tokio(); let owner = reactive_graph::owner::Owner::new(); owner.set();
+    /// # let _guard = reactive_graph::diagnostics::SpecialNonReactiveZone::enter();
+    /// let query = RwSignal::new(String::new());
+    /// let results = ArcAsyncDerived::new_with_options(
+    ///     AsyncDerivedOptions {
+    ///         // wait until the user has stopped typing
+    ///         debounce: Some(Duration::from_millis(250)),
+    ///         ..Default::default()
+    ///     },
+    ///     move || {
+    ///         let query = query.get();
+    ///         async move { format!("results for {query:?}") }
+    ///     },
+    /// );
+    /// # assert_eq!(results.clone().await, "results for \"\"");
+    /// # });
+    /// ```
+    #[track_caller]
+    pub fn new_with_options<Fut>(
+        options: AsyncDerivedOptions,
+        fun: impl Fn() -> Fut + Send + Sync + 'static,
+    ) -> Self
+    where
+        T: Send + Sync + 'static,
+        Fut: Future<Output = T> + Send + 'static,
+    {
+        let initial_value = None;
+        let (this, _) = spawn_derived!(
+            Executor::spawn,
+            initial_value,
+            fun,
+            true,
+            true,
+            true,
+            None::<ArcTrigger>,
+            options
         );
         this
     }
@@ -503,6 +679,29 @@ impl<T: 'static> ArcAsyncDerived<T> {
         fun: impl Fn() -> Fut + Send + Sync + 'static,
         source: &S,
     ) -> Self
+    where
+        T: Send + Sync + 'static,
+        Fut: Future<Output = T> + Send + 'static,
+        S: Track,
+    {
+        Self::new_with_manual_dependencies_and_options(
+            initial_value,
+            fun,
+            source,
+            AsyncDerivedOptions::default(),
+        )
+    }
+
+    /// Creates a new async derived computation with an initial value, and does not spawn a task
+    /// initially, with options that control how it re-runs when its dependencies change.
+    #[doc(hidden)]
+    #[track_caller]
+    pub fn new_with_manual_dependencies_and_options<Fut, S>(
+        initial_value: Option<T>,
+        fun: impl Fn() -> Fut + Send + Sync + 'static,
+        source: &S,
+        options: AsyncDerivedOptions,
+    ) -> Self
     where
         T: Send + Sync + 'static,
         Fut: Future<Output = T> + Send + 'static,
@@ -515,7 +714,8 @@ impl<T: 'static> ArcAsyncDerived<T> {
             true,
             false,
             false,
-            Some(source)
+            Some(source),
+            options
         );
         this
     }
@@ -552,7 +752,8 @@ impl<T: 'static> ArcAsyncDerived<T> {
             true,
             true,
             true,
-            None::<ArcTrigger>
+            None::<ArcTrigger>,
+            AsyncDerivedOptions::default()
         );
         this
     }
@@ -590,7 +791,8 @@ impl<T: 'static> ArcAsyncDerived<SendWrapper<T>> {
             false,
             false,
             true,
-            None::<ArcTrigger>
+            None::<ArcTrigger>,
+            AsyncDerivedOptions::default()
         );
         this
     }
@@ -1,4 +1,6 @@
-use super::{ArcAsyncDerived, AsyncDerivedReadyFuture, BlockingLock};
+use super::{
+    ArcAsyncDerived, AsyncDerivedOptions, AsyncDerivedReadyFuture, BlockingLock,
+};
 use crate::{
     graph::{
         AnySource, AnySubscriber, ReactiveNode, Source, Subscriber,
@@ -8,13 +10,13 @@ use crate::{
     signal::guards::{AsyncPlain, ReadGuard, WriteGuard},
     traits::{
         DefinedAt, Dispose, IsDisposed, Notify, ReadUntracked,
-        UntrackableGuard, Write,
+        UntrackableGuard, WithName, Write,
     },
     unwrap_signal,
 };
 use core::fmt::Debug;
 use send_wrapper::SendWrapper;
-use std::{future::Future, ops::DerefMut, panic::Location};
+use std::{future::Future, ops::DerefMut, panic::Location, sync::Arc};
 
 /// A reactive value that is derived by running an asynchronous computation in response to changes
 /// in its sources.
@@ -158,6 +160,28 @@ where
         }
     }
 
+    /// Creates a new async derived computation, with options that control how it re-runs when
+    /// its dependencies change.
+    ///
+    /// See [`ArcAsyncDerived::new_with_options`].
+    #[track_caller]
+    pub fn new_with_options<Fut>(
+        options: AsyncDerivedOptions,
+        fun: impl Fn() -> Fut + Send + Sync + 'static,
+    ) -> Self
+    where
+        T: Send + Sync + 'static,
+        Fut: Future<Output = T> + Send + 'static,
+    {
+        Self {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: Location::caller(),
+            inner: ArenaItem::new_with_storage(
+                ArcAsyncDerived::new_with_options(options, fun),
+            ),
+        }
+    }
+
     /// Creates a new async derived computation with an initial value.
     ///
     /// If the initial value is `Some(_)`, the task will not be run initially.
@@ -287,6 +311,25 @@ impl<T, S> DefinedAt for AsyncDerived<T, S> {
             None
         }
     }
+
+    fn debug_name(&self) -> Option<Arc<str>> {
+        self.inner.debug_name()
+    }
+}
+
+impl<T, S> WithName for AsyncDerived<T, S>
+where
+    T: 'static,
+    S: Storage<ArcAsyncDerived<T>>,
+{
+    fn with_name(self, name: impl Into<Arc<str>>) -> Self {
+        let name = name.into();
+        if let Some(inner) = self.inner.try_get_value() {
+            inner.with_name(Arc::clone(&name));
+        }
+        self.inner.with_name(name);
+        self
+    }
 }
 
 impl<T, S> ReadUntracked for AsyncDerived<T, S>
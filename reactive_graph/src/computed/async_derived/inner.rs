@@ -22,6 +22,8 @@ pub(crate) struct ArcAsyncDerivedInner {
     pub state: AsyncDerivedState,
     pub version: usize,
     pub suspenses: Vec<SuspenseContext>,
+    #[cfg(feature = "devtools")]
+    pub devtools: Option<crate::devtools::Registration>,
 }
 
 #[derive(Debug, PartialEq, Eq)]
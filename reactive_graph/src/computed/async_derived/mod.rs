@@ -1,4 +1,6 @@
+mod abort;
 mod arc_async_derived;
+pub use abort::*;
 pub use arc_async_derived::*;
 #[allow(clippy::module_inception)] // not a pub mod, who cares?
 mod async_derived;
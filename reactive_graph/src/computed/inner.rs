@@ -1,3 +1,5 @@
+#[cfg(debug_assertions)]
+use crate::diagnostics::{self, NodeInfo};
 use crate::{
     graph::{
         AnySource, AnySubscriber, Observer, ReactiveNode, ReactiveNodeState,
@@ -6,6 +8,8 @@ use crate::{
     owner::{Owner, Storage, StorageAccess},
 };
 use or_poisoned::OrPoisoned;
+#[cfg(debug_assertions)]
+use std::panic::Location;
 use std::{
     fmt::Debug,
     sync::{Arc, RwLock},
@@ -21,6 +25,10 @@ where
     pub(crate) fun: Arc<dyn Fn(Option<T>) -> (T, bool) + Send + Sync>,
     pub(crate) owner: Owner,
     pub(crate) reactivity: RwLock<MemoInnerReactivity>,
+    #[cfg(feature = "devtools")]
+    pub(crate) devtools: Option<crate::devtools::Registration>,
+    #[cfg(debug_assertions)]
+    pub(crate) defined_at: Option<&'static Location<'static>>,
 }
 
 pub(crate) struct MemoInnerReactivity {
@@ -58,6 +66,22 @@ where
                 subscribers: SubscriberSet::new(),
                 any_subscriber,
             }),
+            #[cfg(feature = "devtools")]
+            devtools: None,
+            #[cfg(debug_assertions)]
+            defined_at: None,
+        }
+    }
+
+    #[cfg(debug_assertions)]
+    fn debug_info(&self) -> NodeInfo {
+        NodeInfo {
+            kind: Some("Memo"),
+            type_name: std::any::type_name::<T>(),
+            name: diagnostics::node_name(
+                self as *const Self as *const () as usize,
+            ),
+            defined_at: self.defined_at,
         }
     }
 }
@@ -78,6 +102,8 @@ where
                 lock.state = ReactiveNodeState::Check;
             }
         }
+        #[cfg(debug_assertions)]
+        let _notifying = diagnostics::enter_notify(self.debug_info());
         for sub in
             (&self.reactivity.read().or_poisoned().subscribers).into_iter()
         {
@@ -86,6 +112,8 @@ where
     }
 
     fn mark_subscribers_check(&self) {
+        #[cfg(debug_assertions)]
+        let _notifying = diagnostics::enter_notify(self.debug_info());
         let lock = self.reactivity.read().or_poisoned();
         for sub in (&lock.subscribers).into_iter() {
             sub.mark_check();
@@ -98,6 +126,15 @@ where
             (inner.state, inner.sources.clone())
         };
 
+        // a memo that is already being updated further up the stack depends on itself
+        #[cfg(debug_assertions)]
+        let _updating = (state != ReactiveNodeState::Clean).then(|| {
+            diagnostics::enter_update(
+                self as *const Self as *const () as usize,
+                self.debug_info(),
+            )
+        });
+
         let needs_update = match state {
             ReactiveNodeState::Clean => false,
             ReactiveNodeState::Dirty => true,
@@ -132,8 +169,12 @@ where
             reactivity_lock.state = ReactiveNodeState::Clean;
 
             if changed {
+                #[cfg(feature = "devtools")]
+                crate::devtools::notified(any_subscriber.0);
                 let subs = reactivity_lock.subscribers.clone();
                 drop(reactivity_lock);
+                #[cfg(debug_assertions)]
+                let _notifying = diagnostics::enter_notify(self.debug_info());
                 for sub in subs {
                     // don't trigger reruns of effects/memos
                     // basically: if one of the observers has triggered this memo to
@@ -5,10 +5,10 @@ use crate::{
         guards::{Mapped, Plain, ReadGuard},
         ArcReadSignal,
     },
-    traits::{DefinedAt, Dispose, Get, ReadUntracked, Track},
+    traits::{DefinedAt, Dispose, Get, ReadUntracked, Track, WithName},
     unwrap_signal,
 };
-use std::{fmt::Debug, hash::Hash, panic::Location};
+use std::{fmt::Debug, hash::Hash, panic::Location, sync::Arc};
 
 /// A memo is an efficient derived reactive value based on other reactive values.
 ///
@@ -285,6 +285,26 @@ where
             None
         }
     }
+
+    fn debug_name(&self) -> Option<Arc<str>> {
+        self.inner.debug_name()
+    }
+}
+
+impl<T, S> WithName for Memo<T, S>
+where
+    T: 'static,
+    S: Storage<T>,
+    S: Storage<ArcMemo<T, S>>,
+{
+    fn with_name(self, name: impl Into<Arc<str>>) -> Self {
+        let name = name.into();
+        if let Some(inner) = self.inner.try_get_value() {
+            inner.with_name(Arc::clone(&name));
+        }
+        self.inner.with_name(name);
+        self
+    }
 }
 
 impl<T, S> Track for Memo<T, S>
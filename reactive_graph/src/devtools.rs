@@ -0,0 +1,485 @@
+//! An inspector for the reactive graph, enabled with the `devtools` feature.
+//!
+//! Every [`Owner`](crate::owner::Owner), signal, trigger, memo, async derived value and effect
+//! created while the feature is enabled is recorded in a global registry. The registry can be
+//! read in two ways:
+//! 1. [`snapshot`] returns the current state of the graph: the owner tree, the sources and
+//!    subscribers of each node, and (where available) a debug name and the current value.
+//! 2. [`subscribe`] returns a channel that receives a [`DevtoolsEvent`] whenever a node is
+//!    created or disposed, a source notifies its subscribers, or an effect runs.
+//!
+//! Nodes are listed with the name given to them with
+//! [`WithName::with_name`](crate::traits::WithName::with_name), and with their current value if
+//! it has been opted into with [`InspectValue::inspect_value`].
+//!
+//! This is intended to be consumed by developer tooling, for example by forwarding events over
+//! a local WebSocket to an inspector UI.
+//!
+//! ```rust
+//! # use reactive_graph::prelude::*;
+//! # use reactive_graph::signal::*;
+//! use reactive_graph::devtools::{self, DevtoolsEvent, InspectValue};
+//!
+//! let events = devtools::subscribe();
+//! let count = RwSignal::new(0).with_name("count").inspect_value();
+//! count.set(1);
+//!
+//! let id = devtools::node_id(&count);
+//! let node = devtools::snapshot().node(id).cloned().unwrap();
+//! assert_eq!(node.name.as_deref(), Some("count"));
+//! assert_eq!(node.value.as_deref(), Some("1"));
+//! assert!(events
+//!     .try_iter()
+//!     .any(|event| event == DevtoolsEvent::Notified { id }));
+//! ```
+//!
+//! Node ids correspond to the ids used by [`AnySource`] and [`AnySubscriber`], and, for owners,
+//! to [`Owner::debug_id`](crate::owner::Owner::debug_id). Like those, they are intended for
+//! debugging only and are not stable between runs.
+
+use crate::{
+    computed::{ArcMemo, Memo},
+    effect::inner::EffectInner,
+    graph::{AnySource, AnySubscriber, SubscriberSet, ToAnySource},
+    owner::{Owner, Storage},
+    signal::{ArcReadSignal, ArcRwSignal, ReadSignal, RwSignal},
+    traits::{WithName, WithUntracked},
+};
+use or_poisoned::OrPoisoned;
+use rustc_hash::FxHashMap;
+use std::{
+    fmt::Debug,
+    panic::Location,
+    sync::{
+        mpsc::{channel, Receiver, Sender},
+        Arc, Mutex, OnceLock, RwLock,
+    },
+};
+
+/// The type of a node in the reactive graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NodeKind {
+    /// A reactive [`Owner`](crate::owner::Owner).
+    Owner,
+    /// A signal, or one half of a signal.
+    Signal,
+    /// A data-less trigger.
+    Trigger,
+    /// A memoized computation.
+    Memo,
+    /// An async derived value or resource.
+    AsyncDerived,
+    /// An [`Effect`](crate::effect::Effect).
+    Effect,
+    /// A [`RenderEffect`](crate::effect::RenderEffect).
+    RenderEffect,
+}
+
+/// The state of a single node, as returned by [`snapshot`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeSnapshot {
+    /// The id of this node.
+    pub id: usize,
+    /// The type of this node.
+    pub kind: NodeKind,
+    /// The name given to this node with [`WithName::with_name`](crate::traits::WithName::with_name),
+    /// if any.
+    pub name: Option<String>,
+    /// The id of the owner this node was created under. For an owner, this is its parent.
+    pub owner: Option<usize>,
+    /// The location at which this node was created, if known.
+    pub defined_at: Option<&'static Location<'static>>,
+    /// The ids of the nodes this node currently depends on.
+    pub sources: Vec<usize>,
+    /// The ids of the nodes that currently depend on this node.
+    pub subscribers: Vec<usize>,
+    /// The [`Debug`] representation of this node's current value, if it is being inspected.
+    pub value: Option<String>,
+}
+
+/// The state of the whole reactive graph, as returned by [`snapshot`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GraphSnapshot {
+    /// Every node that is currently alive, in no particular order.
+    pub nodes: Vec<NodeSnapshot>,
+}
+
+impl GraphSnapshot {
+    /// Returns the node with the given id, if it exists.
+    pub fn node(&self, id: usize) -> Option<&NodeSnapshot> {
+        self.nodes.iter().find(|node| node.id == id)
+    }
+
+    /// Returns every node that was created under the owner with the given id.
+    pub fn children(
+        &self,
+        owner: usize,
+    ) -> impl Iterator<Item = &NodeSnapshot> {
+        self.nodes
+            .iter()
+            .filter(move |node| node.owner == Some(owner))
+    }
+}
+
+/// A change in the reactive graph, as sent to receivers created with [`subscribe`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DevtoolsEvent {
+    /// A node was created.
+    Created {
+        /// The id of the new node.
+        id: usize,
+        /// The type of the new node.
+        kind: NodeKind,
+        /// The owner the node was created under.
+        owner: Option<usize>,
+        /// The location at which the node was created, if known.
+        defined_at: Option<&'static Location<'static>>,
+    },
+    /// A node was dropped or disposed.
+    Disposed {
+        /// The id of the node.
+        id: usize,
+    },
+    /// A source notified its subscribers that its value has changed.
+    Notified {
+        /// The id of the source.
+        id: usize,
+    },
+    /// An effect ran.
+    EffectRun {
+        /// The id of the effect.
+        id: usize,
+    },
+}
+
+type ValueFn = Arc<dyn Fn() -> Option<String> + Send + Sync>;
+
+struct NodeEntry {
+    kind: NodeKind,
+    owner: Option<usize>,
+    defined_at: Option<&'static Location<'static>>,
+    sources: Vec<usize>,
+    subscribers: Vec<usize>,
+    value: Option<ValueFn>,
+}
+
+#[derive(Default)]
+struct Registry {
+    nodes: FxHashMap<usize, NodeEntry>,
+    listeners: Vec<Sender<DevtoolsEvent>>,
+}
+
+impl Registry {
+    fn emit(&mut self, event: DevtoolsEvent) {
+        self.listeners
+            .retain(|listener| listener.send(event.clone()).is_ok());
+    }
+}
+
+fn with_registry<T>(fun: impl FnOnce(&mut Registry) -> T) -> T {
+    static REGISTRY: OnceLock<Mutex<Registry>> = OnceLock::new();
+    let mut registry =
+        REGISTRY.get_or_init(Default::default).lock().or_poisoned();
+    fun(&mut registry)
+}
+
+/// Returns the current state of every node in the reactive graph.
+pub fn snapshot() -> GraphSnapshot {
+    let nodes = with_registry(|registry| {
+        registry
+            .nodes
+            .iter()
+            .map(|(id, entry)| {
+                (
+                    NodeSnapshot {
+                        id: *id,
+                        kind: entry.kind,
+                        name: None,
+                        owner: entry.owner,
+                        defined_at: entry.defined_at,
+                        sources: entry.sources.clone(),
+                        subscribers: entry.subscribers.clone(),
+                        value: None,
+                    },
+                    entry.value.clone(),
+                )
+            })
+            .collect::<Vec<_>>()
+    });
+    // names and values are read after releasing the registry, because reading a value can create
+    // or dispose nodes
+    GraphSnapshot {
+        nodes: nodes
+            .into_iter()
+            .map(|(mut node, value)| {
+                node.name = crate::diagnostics::node_name(node.id)
+                    .map(|name| name.to_string());
+                node.value = value.and_then(|value| value());
+                node
+            })
+            .collect(),
+    }
+}
+
+/// Returns a receiver for every [`DevtoolsEvent`] that occurs from now on.
+///
+/// The receiver is unregistered the next time an event is sent after it has been dropped.
+pub fn subscribe() -> Receiver<DevtoolsEvent> {
+    let (tx, rx) = channel();
+    with_registry(|registry| registry.listeners.push(tx));
+    rx
+}
+
+/// Returns the devtools id of the given source.
+pub fn node_id(source: &impl ToAnySource) -> usize {
+    source.to_any_source().0
+}
+
+fn set_value(id: usize, value: ValueFn) {
+    with_registry(|registry| {
+        if let Some(entry) = registry.nodes.get_mut(&id) {
+            entry.value = Some(value);
+        }
+    });
+}
+
+/// Registers a node for as long as this guard is alive, and marks it as disposed when dropped.
+pub(crate) struct Registration(usize);
+
+impl Registration {
+    /// Registers a signal or trigger, identified by its set of subscribers.
+    #[track_caller]
+    pub(crate) fn source(
+        subscribers: &Arc<RwLock<SubscriberSet>>,
+        kind: NodeKind,
+    ) -> Arc<Self> {
+        Arc::new(Self::new(
+            Arc::as_ptr(subscribers) as usize,
+            kind,
+            Some(Location::caller()),
+        ))
+    }
+
+    pub(crate) fn new(
+        id: usize,
+        kind: NodeKind,
+        defined_at: Option<&'static Location<'static>>,
+    ) -> Self {
+        let owner = Owner::current().map(|owner| owner.debug_id());
+        Self::with_owner(id, kind, owner, defined_at)
+    }
+
+    pub(crate) fn with_owner(
+        id: usize,
+        kind: NodeKind,
+        owner: Option<usize>,
+        defined_at: Option<&'static Location<'static>>,
+    ) -> Self {
+        with_registry(|registry| {
+            registry.nodes.insert(
+                id,
+                NodeEntry {
+                    kind,
+                    owner,
+                    defined_at,
+                    sources: Vec::new(),
+                    subscribers: Vec::new(),
+                    value: None,
+                },
+            );
+            registry.emit(DevtoolsEvent::Created {
+                id,
+                kind,
+                owner,
+                defined_at,
+            });
+        });
+        Self(id)
+    }
+}
+
+impl Debug for Registration {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Registration").field(&self.0).finish()
+    }
+}
+
+impl Drop for Registration {
+    fn drop(&mut self) {
+        let id = self.0;
+        with_registry(|registry| {
+            if let Some(entry) = registry.nodes.remove(&id) {
+                for source in entry.sources {
+                    if let Some(source) = registry.nodes.get_mut(&source) {
+                        source.subscribers.retain(|sub| *sub != id);
+                    }
+                }
+                for sub in entry.subscribers {
+                    if let Some(sub) = registry.nodes.get_mut(&sub) {
+                        sub.sources.retain(|source| *source != id);
+                    }
+                }
+            }
+            registry.emit(DevtoolsEvent::Disposed { id });
+        });
+    }
+}
+
+pub(crate) fn register_effect(
+    inner: &Arc<RwLock<EffectInner>>,
+    kind: NodeKind,
+) {
+    let registration =
+        Registration::new(Arc::as_ptr(inner) as usize, kind, None);
+    inner.write().or_poisoned().devtools = Some(registration);
+}
+
+pub(crate) fn subscribed(source: &AnySource, subscriber: &AnySubscriber) {
+    let (source, subscriber) = (source.0, subscriber.0);
+    with_registry(|registry| {
+        if let Some(entry) = registry.nodes.get_mut(&source) {
+            if !entry.subscribers.contains(&subscriber) {
+                entry.subscribers.push(subscriber);
+            }
+        }
+        if let Some(entry) = registry.nodes.get_mut(&subscriber) {
+            if !entry.sources.contains(&source) {
+                entry.sources.push(source);
+            }
+        }
+    });
+}
+
+pub(crate) fn unsubscribed(subscriber: &AnySubscriber) {
+    let subscriber = subscriber.0;
+    with_registry(|registry| {
+        let sources = registry
+            .nodes
+            .get_mut(&subscriber)
+            .map(|entry| std::mem::take(&mut entry.sources))
+            .unwrap_or_default();
+        for source in sources {
+            if let Some(entry) = registry.nodes.get_mut(&source) {
+                entry.subscribers.retain(|sub| *sub != subscriber);
+            }
+        }
+    });
+}
+
+pub(crate) fn notified(id: usize) {
+    with_registry(|registry| registry.emit(DevtoolsEvent::Notified { id }));
+}
+
+pub(crate) fn effect_run(subscriber: &AnySubscriber) {
+    let id = subscriber.0;
+    with_registry(|registry| registry.emit(DevtoolsEvent::EffectRun { id }));
+}
+
+/// Includes the current value of a reactive value in each [`snapshot`].
+pub trait InspectValue: Sized {
+    /// Includes the [`Debug`] representation of this node's current value in snapshots.
+    fn inspect_value(self) -> Self;
+}
+
+/// Names a reactive value and includes its current value in each [`snapshot`].
+#[deprecated = "Name the value with `WithName::with_name` and use \
+                `InspectValue::inspect_value` to include its value."]
+pub trait Inspect: Sized {
+    /// Gives this node a debug name, and includes its current value in snapshots.
+    fn inspect(self, name: impl Into<String>) -> Self;
+}
+
+#[allow(deprecated)]
+impl<T> Inspect for T
+where
+    T: WithName + InspectValue,
+{
+    fn inspect(self, name: impl Into<String>) -> Self {
+        self.with_name(name.into()).inspect_value()
+    }
+}
+
+fn debug_value<T: Debug>(value: &T) -> String {
+    format!("{value:?}")
+}
+
+impl<T> InspectValue for ArcRwSignal<T>
+where
+    T: Debug + Send + Sync + 'static,
+{
+    fn inspect_value(self) -> Self {
+        let id = node_id(&self);
+        let value = Arc::downgrade(&self.value);
+        set_value(
+            id,
+            Arc::new(move || {
+                let value = value.upgrade()?;
+                let value = value.read().ok()?;
+                Some(debug_value(&*value))
+            }),
+        );
+        self
+    }
+}
+
+impl<T> InspectValue for ArcReadSignal<T>
+where
+    T: Debug + Send + Sync + 'static,
+{
+    fn inspect_value(self) -> Self {
+        let id = node_id(&self);
+        let value = Arc::downgrade(&self.value);
+        set_value(
+            id,
+            Arc::new(move || {
+                let value = value.upgrade()?;
+                let value = value.read().ok()?;
+                Some(debug_value(&*value))
+            }),
+        );
+        self
+    }
+}
+
+impl<T> InspectValue for ArcMemo<T>
+where
+    T: Debug + Send + Sync + 'static,
+{
+    fn inspect_value(self) -> Self {
+        let id = node_id(&self);
+        let value = Arc::downgrade(&self.inner.value);
+        set_value(
+            id,
+            Arc::new(move || {
+                let value = value.upgrade()?;
+                let value = value.read().ok()?;
+                value.as_ref().map(debug_value)
+            }),
+        );
+        self
+    }
+}
+
+macro_rules! inspect_arena_value {
+    ($ty:ident $(, $storage:ident)?) => {
+        impl<T, S> InspectValue for $ty<T, S>
+        where
+            T: Debug + 'static,
+            S: $($storage<T> +)? 'static,
+            Self: ToAnySource + WithUntracked<Value = T> + Send + Sync + Copy,
+        {
+            fn inspect_value(self) -> Self {
+                let id = node_id(&self);
+                        set_value(
+                    id,
+                    Arc::new(move || self.try_with_untracked(debug_value)),
+                );
+                self
+            }
+        }
+    };
+}
+
+inspect_arena_value!(RwSignal);
+inspect_arena_value!(ReadSignal);
+inspect_arena_value!(Memo, Storage);
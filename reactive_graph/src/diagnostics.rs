@@ -7,6 +7,20 @@
 //!
 //! This module provides utilities to suppress those warnings by entering a
 //! [`SpecialNonReactiveZone`].
+//!
+//! ## Cycles
+//! In debug mode, the reactive graph also checks for update cycles, which otherwise lead to
+//! infinite loops:
+//! 1. A memo that (directly or through other memos) reads its own value while it is being
+//!    recalculated always panics, with the chain of memos that form the cycle.
+//! 2. An effect that writes to a signal it has already read (directly or through memos) will
+//!    be notified by its own write, and so will run again. This is reported according to
+//!    the current [`CycleReporting`] mode, which logs a warning by default. It can be set to
+//!    [`CycleReporting::Panic`] with [`set_cycle_reporting`], for example in tests.
+//!
+//! Each node in a reported chain is listed with its type, its debug name (if it has been given
+//! one with [`WithName::with_name`](crate::traits::WithName::with_name)), and the location at
+//! which it was created.
 
 /// Marks an execution block that is known not to be reactive, and suppresses warnings.
 #[derive(Debug)]
@@ -16,13 +30,36 @@ pub struct SpecialNonReactiveZone;
 #[derive(Debug)]
 pub struct SpecialNonReactiveZoneGuard;
 
+#[cfg(any(debug_assertions, leptos_debuginfo, feature = "devtools"))]
+use crate::{graph::Subscriber, owner::Arena};
+use crate::{
+    graph::{AnySubscriber, Source},
+    owner::NodeId,
+    traits::DefinedAt,
+};
+#[cfg(any(debug_assertions, leptos_debuginfo, feature = "devtools"))]
+use or_poisoned::OrPoisoned;
 use pin_project_lite::pin_project;
+#[cfg(any(debug_assertions, leptos_debuginfo, feature = "devtools"))]
+use rustc_hash::FxHashMap;
+#[cfg(any(debug_assertions, leptos_debuginfo, feature = "devtools"))]
+use std::sync::{atomic::AtomicBool, OnceLock, RwLock};
 use std::{
     cell::Cell,
     future::Future,
     pin::Pin,
+    sync::{
+        atomic::{AtomicU8, Ordering},
+        Arc, Weak,
+    },
     task::{Context, Poll},
 };
+#[cfg(debug_assertions)]
+use std::{
+    cell::RefCell,
+    fmt::{Display, Write},
+    panic::Location,
+};
 
 thread_local! {
     static IS_SPECIAL_ZONE: Cell<bool> = const { Cell::new(false) };
@@ -93,3 +130,398 @@ pub fn suppress_resource_load(suppress: bool) {
 pub fn is_suppressing_resource_load() -> bool {
     SUPPRESS_RESOURCE_LOAD.with(|w| w.get())
 }
+
+/// How an effect that writes to a signal it reads is reported in debug mode.
+///
+/// This has no effect in release mode, where cycles are not checked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CycleReporting {
+    /// Does not report the cycle.
+    Ignore,
+    /// Logs a warning. This is the default.
+    #[default]
+    Warn,
+    /// Panics, making the cycle a hard error.
+    Panic,
+}
+
+static CYCLE_REPORTING: AtomicU8 = AtomicU8::new(CycleReporting::Warn as u8);
+
+/// Sets how an effect that writes to a signal it reads is reported in debug mode, for every
+/// thread.
+///
+/// ```rust
+/// use reactive_graph::diagnostics::{self, CycleReporting};
+///
+/// // fail the test if any effect triggers itself
+/// diagnostics::set_cycle_reporting(CycleReporting::Panic);
+/// # assert_eq!(diagnostics::cycle_reporting(), CycleReporting::Panic);
+/// ```
+pub fn set_cycle_reporting(mode: CycleReporting) {
+    CYCLE_REPORTING.store(mode as u8, Ordering::Relaxed);
+}
+
+/// Returns the current [`CycleReporting`] mode.
+pub fn cycle_reporting() -> CycleReporting {
+    match CYCLE_REPORTING.load(Ordering::Relaxed) {
+        0 => CycleReporting::Ignore,
+        1 => CycleReporting::Warn,
+        _ => CycleReporting::Panic,
+    }
+}
+
+/// Describes a node in the reactive graph, for diagnostics.
+#[cfg(debug_assertions)]
+#[derive(Debug, Clone)]
+pub(crate) struct NodeInfo {
+    pub kind: Option<&'static str>,
+    pub type_name: &'static str,
+    pub name: Option<Arc<str>>,
+    pub defined_at: Option<&'static Location<'static>>,
+}
+
+#[cfg(debug_assertions)]
+impl Display for NodeInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.kind, self.type_name.is_empty()) {
+            (Some(kind), true) => f.write_str(kind)?,
+            (Some(kind), false) => {
+                write!(f, "{kind}<{}>", short_type_name(self.type_name))?
+            }
+            (None, _) => f.write_str(&short_type_name(self.type_name))?,
+        }
+        if let Some(name) = &self.name {
+            write!(f, " {name:?}")?;
+        }
+        if let Some(defined_at) = self.defined_at {
+            write!(f, " (defined at {defined_at})")?;
+        }
+        Ok(())
+    }
+}
+
+/// Removes module paths from a type name, so that `alloc::string::String` becomes `String`.
+#[cfg(debug_assertions)]
+fn short_type_name(type_name: &str) -> String {
+    let mut short = String::with_capacity(type_name.len());
+    let mut segment_start = 0;
+    for (idx, c) in type_name.char_indices() {
+        if !(c.is_alphanumeric() || c == '_' || c == ':') {
+            short.push_str(last_path_segment(&type_name[segment_start..idx]));
+            short.push(c);
+            segment_start = idx + c.len_utf8();
+        }
+    }
+    short.push_str(last_path_segment(&type_name[segment_start..]));
+    short
+}
+
+#[cfg(debug_assertions)]
+fn last_path_segment(path: &str) -> &str {
+    path.rsplit("::").next().unwrap_or(path)
+}
+
+#[cfg(debug_assertions)]
+thread_local! {
+    /// Memos that are currently recalculating, along with their IDs.
+    static UPDATING: RefCell<Vec<(usize, NodeInfo)>> = const { RefCell::new(Vec::new()) };
+    /// Nodes that are currently notifying their subscribers of a change, in order.
+    static NOTIFYING: RefCell<Vec<NodeInfo>> = const { RefCell::new(Vec::new()) };
+    /// The effect that is currently running, if any.
+    static RUNNING_EFFECT: RefCell<Option<(usize, NodeInfo)>> = const { RefCell::new(None) };
+}
+
+/// Formats a chain of nodes, one per line.
+#[cfg(debug_assertions)]
+fn format_chain<'a>(chain: impl IntoIterator<Item = &'a NodeInfo>) -> String {
+    let mut formatted = String::new();
+    for (idx, node) in chain.into_iter().enumerate() {
+        _ = write!(
+            formatted,
+            "\n  {}{node}",
+            if idx == 0 { "   " } else { "-> " }
+        );
+    }
+    formatted
+}
+
+/// Marks a memo as recalculating until the guard is dropped.
+///
+/// # Panics
+/// Panics if the memo is already recalculating, which means that it depends on itself.
+#[cfg(debug_assertions)]
+pub(crate) fn enter_update(id: usize, info: NodeInfo) -> UpdateGuard {
+    let cycle = UPDATING.with_borrow_mut(|updating| {
+        match updating.iter().position(|(other, _)| *other == id) {
+            Some(start) => Some(format_chain(
+                updating[start..]
+                    .iter()
+                    .map(|(_, node)| node)
+                    .chain([&info]),
+            )),
+            None => {
+                updating.push((id, info));
+                None
+            }
+        }
+    });
+    if let Some(chain) = cycle {
+        panic!(
+            "Detected a cycle in the reactive graph: a memo depends on its \
+             own value.{chain}"
+        );
+    }
+    UpdateGuard(id)
+}
+
+/// Removes a memo from the list of recalculating memos when dropped.
+#[cfg(debug_assertions)]
+pub(crate) struct UpdateGuard(usize);
+
+#[cfg(debug_assertions)]
+impl Drop for UpdateGuard {
+    fn drop(&mut self) {
+        UPDATING.with_borrow_mut(|updating| {
+            if let Some(idx) =
+                updating.iter().rposition(|(other, _)| *other == self.0)
+            {
+                updating.truncate(idx);
+            }
+        });
+    }
+}
+
+/// Records that a node is notifying its subscribers until the guard is dropped.
+#[cfg(debug_assertions)]
+pub(crate) fn enter_notify(info: NodeInfo) -> NotifyGuard {
+    NOTIFYING.with_borrow_mut(|notifying| notifying.push(info));
+    NotifyGuard
+}
+
+/// Removes a node from the chain of notifying nodes when dropped.
+#[cfg(debug_assertions)]
+pub(crate) struct NotifyGuard;
+
+#[cfg(debug_assertions)]
+impl Drop for NotifyGuard {
+    fn drop(&mut self) {
+        NOTIFYING.with_borrow_mut(|notifying| notifying.pop());
+    }
+}
+
+/// Marks an effect as running until the guard is dropped.
+#[cfg(debug_assertions)]
+pub(crate) fn enter_effect(id: usize, info: NodeInfo) -> EffectGuard {
+    EffectGuard(RUNNING_EFFECT.replace(Some((id, info))))
+}
+
+/// Restores the previously-running effect when dropped.
+#[cfg(debug_assertions)]
+pub(crate) struct EffectGuard(Option<(usize, NodeInfo)>);
+
+#[cfg(debug_assertions)]
+impl Drop for EffectGuard {
+    fn drop(&mut self) {
+        RUNNING_EFFECT.set(self.0.take());
+    }
+}
+
+/// Reports a cycle if the effect with this ID is being notified of a change while it is running,
+/// i.e., if it has written to a signal it has already read.
+#[cfg(debug_assertions)]
+pub(crate) fn check_effect_notified(id: usize) {
+    let Some((running, info)) = RUNNING_EFFECT.with_borrow(Clone::clone) else {
+        return;
+    };
+    if running != id {
+        return;
+    }
+    let mode = cycle_reporting();
+    if mode == CycleReporting::Ignore {
+        return;
+    }
+    let chain = NOTIFYING
+        .with_borrow(|notifying| format_chain(notifying.iter().chain([&info])));
+    let message = format!(
+        "Detected a cycle in the reactive graph: an effect has written to a \
+         signal it reads, which will cause it to run again.{chain}"
+    );
+    if mode == CycleReporting::Panic {
+        panic!("{message}");
+    } else {
+        crate::log_warning(format_args!("{message}"));
+    }
+}
+
+/// Describes a reactive value for a diagnostic message, with its debug name and the location at
+/// which it was defined, if they are known: for example, ` "count" (defined at src/main.rs:3:9)`.
+#[doc(hidden)]
+pub fn describe_value(value: &impl DefinedAt) -> String {
+    describe(value.debug_name().as_deref(), value.defined_at())
+}
+
+/// Describes a reactive value from its debug name and the location at which it was defined. See
+/// [`describe_value`].
+#[doc(hidden)]
+pub fn describe(
+    debug_name: Option<&str>,
+    defined_at: Option<&'static std::panic::Location<'static>>,
+) -> String {
+    match (debug_name, defined_at) {
+        (Some(name), Some(defined_at)) => {
+            format!(" {name:?} (defined at {defined_at})")
+        }
+        (Some(name), None) => format!(" {name:?}"),
+        (None, Some(defined_at)) => format!(" (defined at {defined_at})"),
+        (None, None) => String::new(),
+    }
+}
+
+/// A reactive node that has been given a debug name.
+#[cfg(any(debug_assertions, leptos_debuginfo, feature = "devtools"))]
+enum NamedNode {
+    Source(Weak<dyn Source + Send + Sync>),
+    Subscriber(Weak<dyn Subscriber + Send + Sync>),
+    Arena(NodeId),
+}
+
+#[cfg(any(debug_assertions, leptos_debuginfo, feature = "devtools"))]
+impl NamedNode {
+    fn is_alive(&self) -> bool {
+        match self {
+            NamedNode::Source(weak) => weak.strong_count() > 0,
+            NamedNode::Subscriber(weak) => weak.strong_count() > 0,
+            NamedNode::Arena(node) => {
+                Arena::with(|arena| arena.contains_key(*node))
+            }
+        }
+    }
+}
+
+#[cfg(any(debug_assertions, leptos_debuginfo, feature = "devtools"))]
+#[derive(PartialEq, Eq, Hash)]
+enum NameKey {
+    Node(usize),
+    Arena(NodeId),
+}
+
+#[cfg(any(debug_assertions, leptos_debuginfo, feature = "devtools"))]
+#[derive(Default)]
+struct NameRegistry {
+    names: FxHashMap<NameKey, (NamedNode, Arc<str>)>,
+    /// Names of nodes that no longer exist are removed once there are this many names.
+    prune_at: usize,
+}
+
+#[cfg(any(debug_assertions, leptos_debuginfo, feature = "devtools"))]
+static HAS_NAMES: AtomicBool = AtomicBool::new(false);
+
+#[cfg(any(debug_assertions, leptos_debuginfo, feature = "devtools"))]
+static NAMES: OnceLock<RwLock<NameRegistry>> = OnceLock::new();
+
+#[cfg(any(debug_assertions, leptos_debuginfo, feature = "devtools"))]
+fn set_name(key: NameKey, node: NamedNode, name: Arc<str>) {
+    HAS_NAMES.store(true, Ordering::Relaxed);
+    let mut registry =
+        NAMES.get_or_init(Default::default).write().or_poisoned();
+    if registry.names.len() >= registry.prune_at {
+        registry.names.retain(|_, (node, _)| node.is_alive());
+        registry.prune_at = (registry.names.len() * 2).max(256);
+    }
+    registry.names.insert(key, (node, name));
+}
+
+#[cfg(any(debug_assertions, leptos_debuginfo, feature = "devtools"))]
+fn get_name(key: &NameKey, check_alive: bool) -> Option<Arc<str>> {
+    if !HAS_NAMES.load(Ordering::Relaxed) {
+        return None;
+    }
+    let registry = NAMES.get()?.read().or_poisoned();
+    let (node, name) = registry.names.get(key)?;
+    (!check_alive || node.is_alive()).then(|| Arc::clone(name))
+}
+
+/// Sets the debug name of the reactive source stored in this `Arc`.
+pub(crate) fn set_node_name<S: Source + Send + Sync + 'static>(
+    source: &Arc<S>,
+    name: Arc<str>,
+) {
+    let weak = Arc::downgrade(source) as Weak<dyn Source + Send + Sync>;
+    set_weak_source_name(arc_id(source), &weak, name);
+}
+
+/// Returns the ID used by [`AnySource`](crate::graph::AnySource) and [`AnySubscriber`] for the node stored in this `Arc`.
+pub(crate) fn arc_id<T>(node: &Arc<T>) -> usize {
+    Arc::as_ptr(node) as usize
+}
+
+#[cfg_attr(
+    not(any(debug_assertions, leptos_debuginfo, feature = "devtools")),
+    allow(unused_variables)
+)]
+fn set_weak_source_name(
+    id: usize,
+    source: &Weak<dyn Source + Send + Sync>,
+    name: Arc<str>,
+) {
+    #[cfg(any(debug_assertions, leptos_debuginfo, feature = "devtools"))]
+    set_name(NameKey::Node(id), NamedNode::Source(source.clone()), name);
+}
+
+/// Sets the debug name of a reactive subscriber, like an effect.
+#[cfg_attr(
+    not(any(debug_assertions, leptos_debuginfo, feature = "devtools")),
+    allow(unused_variables)
+)]
+pub(crate) fn set_subscriber_name(subscriber: &AnySubscriber, name: Arc<str>) {
+    #[cfg(any(debug_assertions, leptos_debuginfo, feature = "devtools"))]
+    set_name(
+        NameKey::Node(subscriber.0),
+        NamedNode::Subscriber(subscriber.1.clone()),
+        name,
+    );
+}
+
+/// Sets the debug name of an item stored in the arena. Unlike the names of other nodes, this is
+/// kept for a while after the item has been disposed, so that it can be used in the panic message
+/// when a disposed value is accessed.
+#[cfg_attr(
+    not(any(debug_assertions, leptos_debuginfo, feature = "devtools")),
+    allow(unused_variables)
+)]
+pub(crate) fn set_arena_name(node: NodeId, name: Arc<str>) {
+    #[cfg(any(debug_assertions, leptos_debuginfo, feature = "devtools"))]
+    set_name(NameKey::Arena(node), NamedNode::Arena(node), name);
+}
+
+/// Returns the debug name of the reactive node with this ID, if it has one.
+#[cfg_attr(
+    not(any(debug_assertions, leptos_debuginfo, feature = "devtools")),
+    allow(unused_variables)
+)]
+pub(crate) fn node_name(id: usize) -> Option<Arc<str>> {
+    #[cfg(any(debug_assertions, leptos_debuginfo, feature = "devtools"))]
+    {
+        get_name(&NameKey::Node(id), true)
+    }
+    #[cfg(not(any(debug_assertions, leptos_debuginfo, feature = "devtools")))]
+    {
+        None
+    }
+}
+
+/// Returns the debug name of the arena item, if it has one.
+#[cfg_attr(
+    not(any(debug_assertions, leptos_debuginfo, feature = "devtools")),
+    allow(unused_variables)
+)]
+pub(crate) fn arena_name(node: NodeId) -> Option<Arc<str>> {
+    #[cfg(any(debug_assertions, leptos_debuginfo, feature = "devtools"))]
+    {
+        get_name(&NameKey::Arena(node), false)
+    }
+    #[cfg(not(any(debug_assertions, leptos_debuginfo, feature = "devtools")))]
+    {
+        None
+    }
+}
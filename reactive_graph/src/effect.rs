@@ -1,14 +1,18 @@
 //! Side effects that run in response to changes in the reactive values they read from.
 
+mod batch;
 #[allow(clippy::module_inception)]
 mod effect;
 mod effect_function;
-mod inner;
+pub(crate) mod inner;
 mod render_effect;
+pub(crate) mod scheduler;
 
+pub use batch::*;
 pub use effect::*;
 pub use effect_function::*;
 pub use render_effect::*;
+pub use scheduler::Priority;
 
 /// Creates a new render effect, which immediately runs `fun`.
 #[inline(always)]
@@ -0,0 +1,169 @@
+use crate::channel::Sender;
+use pin_project_lite::pin_project;
+use std::{
+    cell::RefCell,
+    future::Future,
+    mem,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+thread_local! {
+    static DEFERRED: RefCell<Option<Deferred>> = const { RefCell::new(None) };
+}
+
+/// The effects that have been notified during a batch, which will be scheduled when it is dropped.
+#[derive(Debug, Default)]
+struct Deferred(Vec<Sender>);
+
+impl Drop for Deferred {
+    fn drop(&mut self) {
+        for mut observer in mem::take(&mut self.0) {
+            observer.notify();
+        }
+    }
+}
+
+/// Makes the given set of deferred effects the current batch, until dropped.
+struct BatchScope<'a>(&'a mut Option<Deferred>);
+
+impl<'a> BatchScope<'a> {
+    fn enter(deferred: &'a mut Option<Deferred>) -> Self {
+        DEFERRED.with_borrow_mut(|current| *current = deferred.take());
+        Self(deferred)
+    }
+}
+
+impl Drop for BatchScope<'_> {
+    fn drop(&mut self) {
+        *self.0 = DEFERRED.with_borrow_mut(Option::take);
+    }
+}
+
+/// Returns `true` if this is called inside [`batch`] or while polling a [`batch_async`] future.
+pub fn is_batching() -> bool {
+    DEFERRED.with_borrow(Option::is_some)
+}
+
+/// Schedules an effect to run, or defers it until the end of the current batch.
+pub(crate) fn notify(observer: &mut Sender) {
+    let deferred = DEFERRED.with_borrow_mut(|current| match current {
+        Some(deferred) => {
+            if !deferred.0.contains(observer) {
+                deferred.0.push(observer.clone());
+            }
+            true
+        }
+        None => false,
+    });
+    if !deferred {
+        observer.notify();
+    }
+}
+
+/// Runs the given function, deferring any effects and render effects that are notified until it
+/// returns.
+///
+/// Signals updated inside the batch change immediately, and memos read inside the batch will
+/// recalculate as usual. However, effects that depend on those signals are only scheduled once
+/// the batch ends, so an effect that depends on several signals that are updated in the batch
+/// will run once, with all of their new values.
+///
+/// Batches can be nested; effects are deferred until the outermost batch ends.
+///
+/// ```rust
+/// # use reactive_graph::prelude::*;
+/// # use reactive_graph::signal::*;
+/// # use reactive_graph::effect::{batch, Effect};
+/// # use std::sync::{Arc, atomic::{AtomicUsize, Ordering}};
+/// # tokio_test::block_on(async move {
+/// # tokio::task::LocalSet::new().run_until(async move {
+/// # any_spawner::Executor::init_tokio(); let owner = reactive_graph::owner::Owner::new(); owner.set();
+/// let first = RwSignal::new("Alice".to_string());
+/// let last = RwSignal::new("Smith".to_string());
+/// let runs = Arc::new(AtomicUsize::new(0));
+///
+/// Effect::new({
+///     let runs = Arc::clone(&runs);
+///     move || {
+///         println!("{} {}", first.read(), last.read());
+///         runs.fetch_add(1, Ordering::Relaxed);
+///     }
+/// });
+/// # any_spawner::Executor::tick().await;
+///
+/// batch(|| {
+///     first.set("Bob".to_string());
+///     last.set("Jones".to_string());
+/// });
+/// # any_spawner::Executor::tick().await;
+/// // prints "Bob Jones" once, rather than also printing "Bob Smith"
+/// # if cfg!(feature = "effects") { assert_eq!(runs.load(Ordering::Relaxed), 2); }
+/// # }).await;
+/// # });
+/// ```
+pub fn batch<T>(fun: impl FnOnce() -> T) -> T {
+    if is_batching() {
+        return fun();
+    }
+
+    let mut deferred = Some(Deferred::default());
+    let value = {
+        let _scope = BatchScope::enter(&mut deferred);
+        fun()
+    };
+    // dropping the deferred effects schedules them
+    drop(deferred);
+    value
+}
+
+/// Runs the given future as a batch, deferring any effects and render effects that are
+/// notified while it is being polled until it completes.
+///
+/// This is the `async` equivalent of [`batch`]. Effects are not scheduled between `.await`
+/// points, even if the async runtime gives them an opportunity to run. If the future is dropped
+/// before it completes, the deferred effects are scheduled when it is dropped.
+pub fn batch_async<Fut>(fut: Fut) -> Batch<Fut>
+where
+    Fut: Future,
+{
+    Batch {
+        inner: fut,
+        deferred: Some(Deferred::default()),
+    }
+}
+
+pin_project! {
+    /// A future that defers effects until it completes, created by [`batch_async`].
+    #[derive(Debug)]
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct Batch<Fut> {
+        #[pin]
+        inner: Fut,
+        deferred: Option<Deferred>,
+    }
+}
+
+impl<Fut> Future for Batch<Fut>
+where
+    Fut: Future,
+{
+    type Output = Fut::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        if is_batching() {
+            return this.inner.poll(cx);
+        }
+
+        let poll = {
+            let _scope = BatchScope::enter(this.deferred);
+            this.inner.poll(cx)
+        };
+        if poll.is_ready() {
+            // dropping the deferred effects schedules them
+            this.deferred.take();
+        }
+        poll
+    }
+}
@@ -1,18 +1,19 @@
 use crate::{
     channel::{channel, Receiver},
-    effect::{inner::EffectInner, EffectFunction},
+    effect::{batch, inner::EffectInner, EffectFunction, Priority},
     graph::{
         AnySubscriber, ReactiveNode, SourceSet, Subscriber, ToAnySubscriber,
         WithObserver,
     },
     owner::{ArenaItem, LocalStorage, Owner, Storage, SyncStorage},
-    traits::Dispose,
+    traits::{Dispose, WithName},
 };
 use any_spawner::Executor;
 use futures::StreamExt;
 use or_poisoned::OrPoisoned;
 use std::{
     mem,
+    panic::Location,
     sync::{atomic::AtomicBool, Arc, RwLock},
 };
 
@@ -94,18 +95,23 @@ impl<S> Dispose for Effect<S> {
 
 fn effect_base() -> (Receiver, Owner, Arc<RwLock<EffectInner>>) {
     let (mut observer, rx) = channel();
+    observer.set_priority(Priority::default());
 
     // spawn the effect asynchronously
     // we'll notify once so it runs on the next tick,
     // to register observed values
-    observer.notify();
+    batch::notify(&mut observer);
 
     let owner = Owner::new();
     let inner = Arc::new(RwLock::new(EffectInner {
         dirty: true,
         observer,
         sources: SourceSet::new(),
+        #[cfg(feature = "devtools")]
+        devtools: None,
     }));
+    #[cfg(feature = "devtools")]
+    crate::devtools::register_effect(&inner, crate::devtools::NodeKind::Effect);
 
     (rx, owner, inner)
 }
@@ -122,7 +128,25 @@ pub fn in_effect_scope() -> bool {
 
 /// Set a static to true whilst running the given function.
 /// [`is_in_effect_scope`] will return true whilst the function is running.
-fn run_in_effect_scope<T>(fun: impl FnOnce() -> T) -> T {
+///
+/// In debug mode, this also records which effect is running, so that it can be warned about if
+/// it notifies itself.
+#[cfg_attr(not(debug_assertions), allow(unused_variables))]
+fn run_in_effect_scope<T>(
+    subscriber: &AnySubscriber,
+    defined_at: &'static Location<'static>,
+    fun: impl FnOnce() -> T,
+) -> T {
+    #[cfg(debug_assertions)]
+    let _running = crate::diagnostics::enter_effect(
+        subscriber.0,
+        crate::diagnostics::NodeInfo {
+            kind: Some("Effect"),
+            type_name: "",
+            name: crate::diagnostics::node_name(subscriber.0),
+            defined_at: Some(defined_at),
+        },
+    );
     // For the theoretical nested case, set back to initial value rather than false:
     let initial = EFFECT_SCOPE_ACTIVE
         .with(|scope| scope.swap(true, std::sync::atomic::Ordering::Relaxed));
@@ -137,6 +161,71 @@ impl<S> Effect<S>
 where
     S: Storage<StoredEffect>,
 {
+    /// Sets the [`Priority`] with which this effect is scheduled when the values it depends on
+    /// change.
+    ///
+    /// Effects are scheduled with [`Priority::Normal`] by default. When effects with different
+    /// priorities are notified at the same time, the higher-priority effects run first.
+    ///
+    /// ```
+    /// # use reactive_graph::prelude::*;
+    /// # use reactive_graph::signal::RwSignal;
+    /// # use reactive_graph::effect::{Effect, Priority};
+    /// # tokio_test::block_on(async move {
+    /// # tokio::task::LocalSet::new().run_until(async move {
+    /// # any_spawner::Executor::init_tokio(); let owner = reactive_graph::owner::Owner::new(); owner.set();
+    /// let page = RwSignal::new("/");
+    ///
+    /// // analytics can wait until more important work is done
+    /// Effect::new(move || {
+    ///     println!("viewed {}", page.get());
+    /// })
+    /// .with_priority(Priority::Idle);
+    /// # }).await;
+    /// # });
+    /// ```
+    pub fn with_priority(self, priority: Priority) -> Self {
+        if let Some(inner) = self.inner {
+            inner.try_with_value(|inner| {
+                if let Some(inner) = inner {
+                    inner.read().or_poisoned().observer.set_priority(priority);
+                }
+            });
+        }
+        self
+    }
+
+    /// Sets whether this effect yields to the browser's main thread before it runs, if the
+    /// effects before it have been running for several milliseconds, or if it has
+    /// [`Priority::Idle`] and the browser is not idle yet.
+    ///
+    /// Effects do not yield by default. This has no effect outside the browser.
+    pub fn with_yielding(self, yielding: bool) -> Self {
+        if let Some(inner) = self.inner {
+            inner.try_with_value(|inner| {
+                if let Some(inner) = inner {
+                    inner.read().or_poisoned().observer.set_yielding(yielding);
+                }
+            });
+        }
+        self
+    }
+
+    /// Returns the [`Priority`] with which this effect is scheduled.
+    pub fn priority(&self) -> Priority {
+        self.inner
+            .and_then(|inner| {
+                inner
+                    .try_with_value(|inner| {
+                        inner.as_ref().and_then(|inner| {
+                            inner.read().or_poisoned().observer.priority()
+                        })
+                    })
+                    .flatten()
+            })
+            .unwrap_or_default()
+    }
+
     /// Stops this effect before it is disposed.
     pub fn stop(self) {
         if let Some(inner) = self
@@ -155,10 +244,12 @@ impl Effect<LocalStorage> {
     /// This spawns a task on the local thread using
     /// [`spawn_local`](any_spawner::Executor::spawn_local). For an effect that can be spawned on
     /// any thread, use [`new_sync`](Effect::new_sync).
+    #[track_caller]
     pub fn new<T, M>(mut fun: impl EffectFunction<T, M> + 'static) -> Self
     where
         T: 'static,
     {
+        let defined_at = Location::caller();
         let inner = cfg!(feature = "effects").then(|| {
             let (mut rx, owner, inner) = effect_base();
             let value = Arc::new(RwLock::new(None::<T>));
@@ -176,12 +267,18 @@ impl Effect<LocalStorage> {
                         {
                             first_run = false;
                             subscriber.clear_sources(&subscriber);
+                            #[cfg(feature = "devtools")]
+                            crate::devtools::effect_run(&subscriber);
 
                             let old_value =
                                 mem::take(&mut *value.write().or_poisoned());
                             let new_value = owner.with_cleanup(|| {
                                 subscriber.with_observer(|| {
-                                    run_in_effect_scope(|| fun.run(old_value))
+                                    run_in_effect_scope(
+                                        &subscriber,
+                                        defined_at,
+                                        || fun.run(old_value),
+                                    )
                                 })
                             });
                             *value.write().or_poisoned() = Some(new_value);
@@ -326,6 +423,8 @@ impl Effect<LocalStorage> {
                             || first_run
                         {
                             subscriber.clear_sources(&subscriber);
+                            #[cfg(feature = "devtools")]
+                            crate::devtools::effect_run(&subscriber);
 
                             let old_dep_value = mem::take(
                                 &mut *dep_value.write().or_poisoned(),
@@ -371,12 +470,14 @@ impl Effect<SyncStorage> {
     ///
     /// This spawns a task that can be run on any thread. For an effect that will be spawned on
     /// the current thread, use [`new`](Effect::new).
+    #[track_caller]
     pub fn new_sync<T, M>(
         mut fun: impl EffectFunction<T, M> + Send + Sync + 'static,
     ) -> Self
     where
         T: Send + Sync + 'static,
     {
+        let defined_at = Location::caller();
         let inner = cfg!(feature = "effects").then(|| {
             let (mut rx, owner, inner) = effect_base();
             let mut first_run = true;
@@ -394,12 +495,18 @@ impl Effect<SyncStorage> {
                         {
                             first_run = false;
                             subscriber.clear_sources(&subscriber);
+                            #[cfg(feature = "devtools")]
+                            crate::devtools::effect_run(&subscriber);
 
                             let old_value =
                                 mem::take(&mut *value.write().or_poisoned());
                             let new_value = owner.with_cleanup(|| {
                                 subscriber.with_observer(|| {
-                                    run_in_effect_scope(|| fun.run(old_value))
+                                    run_in_effect_scope(
+                                        &subscriber,
+                                        defined_at,
+                                        || fun.run(old_value),
+                                    )
                                 })
                             });
                             *value.write().or_poisoned() = Some(new_value);
@@ -418,12 +525,14 @@ impl Effect<SyncStorage> {
     /// that are read inside it change.
     ///
     /// This will run whether the `effects` feature is enabled or not.
+    #[track_caller]
     pub fn new_isomorphic<T, M>(
         mut fun: impl EffectFunction<T, M> + Send + Sync + 'static,
     ) -> Self
     where
         T: Send + Sync + 'static,
     {
+        let defined_at = Location::caller();
         let (mut rx, owner, inner) = effect_base();
         let mut first_run = true;
         let value = Arc::new(RwLock::new(None::<T>));
@@ -440,12 +549,18 @@ impl Effect<SyncStorage> {
                     {
                         first_run = false;
                         subscriber.clear_sources(&subscriber);
+                        #[cfg(feature = "devtools")]
+                        crate::devtools::effect_run(&subscriber);
 
                         let old_value =
                             mem::take(&mut *value.write().or_poisoned());
                         let new_value = owner.with_cleanup(|| {
                             subscriber.with_observer(|| {
-                                run_in_effect_scope(|| fun.run(old_value))
+                                run_in_effect_scope(
+                                    &subscriber,
+                                    defined_at,
+                                    || fun.run(old_value),
+                                )
                             })
                         });
                         *value.write().or_poisoned() = Some(new_value);
@@ -492,6 +607,8 @@ impl Effect<SyncStorage> {
                             || first_run
                         {
                             subscriber.clear_sources(&subscriber);
+                            #[cfg(feature = "devtools")]
+                            crate::devtools::effect_run(&subscriber);
 
                             let old_dep_value = mem::take(
                                 &mut *dep_value.write().or_poisoned(),
@@ -548,6 +665,27 @@ where
     }
 }
 
+impl<S> WithName for Effect<S>
+where
+    S: Storage<StoredEffect>,
+{
+    fn with_name(self, name: impl Into<Arc<str>>) -> Self {
+        if let Some(inner) = self.inner {
+            let name = name.into();
+            inner.try_with_value(|effect| {
+                if let Some(effect) = effect {
+                    crate::diagnostics::set_subscriber_name(
+                        &effect.to_any_subscriber(),
+                        Arc::clone(&name),
+                    );
+                }
+            });
+            inner.with_name(name);
+        }
+        self
+    }
+}
+
 /// Creates an [`Effect`].
 #[inline(always)]
 #[track_caller]
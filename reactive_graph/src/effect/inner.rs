@@ -1,3 +1,4 @@
+use super::batch;
 use crate::{
     channel::Sender,
     graph::{
@@ -14,6 +15,16 @@ pub struct EffectInner {
     pub(crate) dirty: bool,
     pub(crate) observer: Sender,
     pub(crate) sources: SourceSet,
+    #[cfg(feature = "devtools")]
+    pub(crate) devtools: Option<crate::devtools::Registration>,
+}
+
+impl Drop for EffectInner {
+    fn drop(&mut self) {
+        // the observer may outlive the effect, if it is waiting in a batch, so release any
+        // notification that is waiting to be run
+        self.observer.close();
+    }
 }
 
 impl ToAnySubscriber for Arc<RwLock<EffectInner>> {
@@ -48,13 +59,17 @@ impl ReactiveNode for RwLock<EffectInner> {
     }
 
     fn mark_check(&self) {
-        self.write().or_poisoned().observer.notify()
+        #[cfg(debug_assertions)]
+        crate::diagnostics::check_effect_notified(self as *const Self as usize);
+        batch::notify(&mut self.write().or_poisoned().observer)
     }
 
     fn mark_dirty(&self) {
+        #[cfg(debug_assertions)]
+        crate::diagnostics::check_effect_notified(self as *const Self as usize);
         let mut lock = self.write().or_poisoned();
         lock.dirty = true;
-        lock.observer.notify()
+        batch::notify(&mut lock.observer)
     }
 }
 
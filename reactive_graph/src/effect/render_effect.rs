@@ -1,11 +1,12 @@
 use crate::{
     channel::channel,
-    effect::inner::EffectInner,
+    effect::{inner::EffectInner, Priority},
     graph::{
         AnySubscriber, ReactiveNode, SourceSet, Subscriber, ToAnySubscriber,
         WithObserver,
     },
     owner::Owner,
+    traits::WithName,
 };
 use any_spawner::Executor;
 use futures::StreamExt;
@@ -67,13 +68,21 @@ where
             initial_value: Option<T>,
         ) -> RenderEffect<T> {
             let (observer, mut rx) = channel();
+            observer.set_priority(Priority::UserBlocking);
             let value = Arc::new(RwLock::new(None::<T>));
             let owner = Owner::new();
             let inner = Arc::new(RwLock::new(EffectInner {
                 dirty: false,
                 observer,
                 sources: SourceSet::new(),
+                #[cfg(feature = "devtools")]
+                devtools: None,
             }));
+            #[cfg(feature = "devtools")]
+            crate::devtools::register_effect(
+                &inner,
+                crate::devtools::NodeKind::RenderEffect,
+            );
 
             let initial_value = cfg!(feature = "effects").then(|| {
                 owner.with(|| {
@@ -95,6 +104,8 @@ where
                                 subscriber.update_if_necessary()
                             }) {
                                 subscriber.clear_sources(&subscriber);
+                                #[cfg(feature = "devtools")]
+                                crate::devtools::effect_run(&subscriber);
 
                                 let old_value = mem::take(
                                     &mut *value.write().or_poisoned(),
@@ -115,6 +126,44 @@ where
         erased(Box::new(fun), initial_value)
     }
 
+    /// Sets the [`Priority`] with which this effect is scheduled when the values it depends on
+    /// change.
+    ///
+    /// Render effects are scheduled with [`Priority::UserBlocking`] by default, so that they run
+    /// before any other effects that are notified at the same time.
+    pub fn with_priority(self, priority: Priority) -> Self {
+        self.inner
+            .read()
+            .or_poisoned()
+            .observer
+            .set_priority(priority);
+        self
+    }
+
+    /// Sets whether this effect yields to the browser's main thread before it runs, if the
+    /// effects before it have been running for several milliseconds.
+    ///
+    /// Render effects do not yield by default, so that the DOM is never left partially updated
+    /// between browser tasks. This has no effect outside the browser.
+    pub fn with_yielding(self, yielding: bool) -> Self {
+        self.inner
+            .read()
+            .or_poisoned()
+            .observer
+            .set_yielding(yielding);
+        self
+    }
+
+    /// Returns the [`Priority`] with which this effect is scheduled.
+    pub fn priority(&self) -> Priority {
+        self.inner
+            .read()
+            .or_poisoned()
+            .observer
+            .priority()
+            .unwrap_or_default()
+    }
+
     /// Mutably accesses the current value.
     pub fn with_value_mut<U>(
         &self,
@@ -141,13 +190,21 @@ where
             mut fun: Box<dyn FnMut(Option<T>) -> T + Send + Sync + 'static>,
         ) -> RenderEffect<T> {
             let (observer, mut rx) = channel();
+            observer.set_priority(Priority::UserBlocking);
             let value = Arc::new(RwLock::new(None::<T>));
             let owner = Owner::new();
             let inner = Arc::new(RwLock::new(EffectInner {
                 dirty: false,
                 observer,
                 sources: SourceSet::new(),
+                #[cfg(feature = "devtools")]
+                devtools: None,
             }));
+            #[cfg(feature = "devtools")]
+            crate::devtools::register_effect(
+                &inner,
+                crate::devtools::NodeKind::RenderEffect,
+            );
 
             let initial_value = owner
                 .with(|| inner.to_any_subscriber().with_observer(|| fun(None)));
@@ -163,6 +220,8 @@ where
                             .with_observer(|| subscriber.update_if_necessary())
                         {
                             subscriber.clear_sources(&subscriber);
+                            #[cfg(feature = "devtools")]
+                            crate::devtools::effect_run(&subscriber);
 
                             let old_value =
                                 mem::take(&mut *value.write().or_poisoned());
@@ -190,3 +249,13 @@ impl<T> ToAnySubscriber for RenderEffect<T> {
         )
     }
 }
+
+impl<T> WithName for RenderEffect<T> {
+    fn with_name(self, name: impl Into<Arc<str>>) -> Self {
+        crate::diagnostics::set_subscriber_name(
+            &self.to_any_subscriber(),
+            name.into(),
+        );
+        self
+    }
+}
@@ -0,0 +1,258 @@
+use or_poisoned::OrPoisoned;
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::Waker,
+};
+
+/// The priority with which an effect is scheduled to run.
+///
+/// When effects with different priorities are waiting to run at the same time, all of the
+/// [`UserBlocking`](Priority::UserBlocking) effects run first, then the
+/// [`Normal`](Priority::Normal) effects, and then the [`Idle`](Priority::Idle) effects. Effects
+/// with the same priority run in the order in which they were notified.
+///
+/// Priorities only order effects that are notified on the same thread. An effect that is disposed
+/// before it runs no longer holds up lower-priority effects.
+///
+/// In the browser, effects can also opt into yielding to the main thread between chunks of work
+/// with `with_yielding`, using
+/// [`scheduler.postTask()`](https://developer.mozilla.org/en-US/docs/Web/API/Scheduler/postTask)
+/// where it is available. A yielding `Idle` effect waits for an idle period before it runs, using
+/// `requestIdleCallback` if `postTask` is not available. Effects do not yield by default, so that
+/// the effects notified by a change all run in the same browser task, and on other platforms
+/// they never yield.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum Priority {
+    /// Work that responds directly to user input, like updating the DOM. This is the default for
+    /// a [`RenderEffect`](crate::effect::RenderEffect).
+    UserBlocking,
+    /// Work that the user can see, but that is not blocking their input. This is the default for
+    /// an [`Effect`](crate::effect::Effect).
+    #[default]
+    Normal,
+    /// Background work that can wait until the browser is idle, like analytics or logging.
+    Idle,
+}
+
+const LEVELS: usize = 3;
+
+impl Priority {
+    pub(crate) fn to_u8(self) -> u8 {
+        self as u8
+    }
+
+    pub(crate) fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(Priority::UserBlocking),
+            1 => Some(Priority::Normal),
+            2 => Some(Priority::Idle),
+            _ => None,
+        }
+    }
+}
+
+/// Keeps track of how many effects of each priority are waiting to run on a thread.
+#[derive(Debug, Default)]
+struct Scheduler {
+    queued: [usize; LEVELS],
+    waiting: [Vec<Waker>; LEVELS],
+}
+
+thread_local! {
+    static SCHEDULER: Arc<Mutex<Scheduler>> = Default::default();
+}
+
+impl Scheduler {
+    fn is_clear(&self, priority: Priority) -> bool {
+        self.queued[..priority as usize].iter().all(|n| *n == 0)
+    }
+
+    /// Wakes any effects that are no longer waiting on higher-priority work.
+    fn wake_ready(&mut self) {
+        for level in 0..LEVELS {
+            if !self.queued[..level].iter().all(|n| *n == 0) {
+                break;
+            }
+            for waker in self.waiting[level].drain(..) {
+                waker.wake();
+            }
+        }
+    }
+}
+
+/// Represents an effect that has been notified, but has not yet run.
+///
+/// Effects are scheduled relative to other effects that were notified on the same thread. The
+/// ticket is released when it is dropped.
+#[derive(Debug)]
+pub(crate) struct Ticket {
+    priority: Priority,
+    scheduler: Arc<Mutex<Scheduler>>,
+}
+
+impl Ticket {
+    /// Records that an effect with this priority has been notified.
+    pub fn queue(priority: Priority) -> Self {
+        let scheduler = SCHEDULER.with(Arc::clone);
+        {
+            let mut lock = scheduler.lock().or_poisoned();
+            #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+            if lock.queued.iter().all(|n| *n == 0) {
+                browser::start_chunk();
+            }
+            lock.queued[priority as usize] += 1;
+        }
+        Self {
+            priority,
+            scheduler,
+        }
+    }
+
+    pub fn priority(&self) -> Priority {
+        self.priority
+    }
+
+    /// Moves the notified effect to another priority.
+    pub fn requeue(&mut self, priority: Priority) {
+        let mut lock = self.scheduler.lock().or_poisoned();
+        lock.queued[self.priority as usize] -= 1;
+        lock.queued[priority as usize] += 1;
+        lock.wake_ready();
+        self.priority = priority;
+    }
+
+    /// Returns `true` if the effect can run now. Otherwise, registers the waker to be woken once
+    /// all the higher-priority effects have run.
+    pub fn can_run(&self, waker: &Waker) -> bool {
+        let mut lock = self.scheduler.lock().or_poisoned();
+        if lock.is_clear(self.priority) {
+            true
+        } else {
+            let waiting = &mut lock.waiting[self.priority as usize];
+            if !waiting.iter().any(|w| w.will_wake(waker)) {
+                waiting.push(waker.clone());
+            }
+            false
+        }
+    }
+}
+
+impl Drop for Ticket {
+    fn drop(&mut self) {
+        let mut lock = self.scheduler.lock().or_poisoned();
+        lock.queued[self.priority as usize] -= 1;
+        lock.wake_ready();
+    }
+}
+
+impl Ticket {
+    /// Returns a future that yields to the browser before an effect with this priority runs, if
+    /// it should do so.
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    pub fn yield_before(
+        _priority: Priority,
+    ) -> Option<Pin<Box<dyn Future<Output = ()> + Send>>> {
+        None
+    }
+
+    /// Returns a future that yields to the browser before an effect with this priority runs, if
+    /// it should do so.
+    #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+    pub fn yield_before(
+        priority: Priority,
+    ) -> Option<Pin<Box<dyn Future<Output = ()> + Send>>> {
+        browser::yield_before(priority)
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+mod browser {
+    use super::Priority;
+    use js_sys::{Function, Object, Promise, Reflect};
+    use send_wrapper::SendWrapper;
+    use std::{cell::Cell, future::Future, pin::Pin};
+    use wasm_bindgen::{JsCast, JsValue};
+    use wasm_bindgen_futures::JsFuture;
+
+    /// How long effects can run before yielding to the browser, in milliseconds.
+    const CHUNK_BUDGET: f64 = 5.0;
+
+    thread_local! {
+        static CHUNK_START: Cell<f64> = const { Cell::new(0.0) };
+    }
+
+    pub(super) fn start_chunk() {
+        CHUNK_START.with(|start| start.set(js_sys::Date::now()));
+    }
+
+    /// Returns a future that yields to the browser if the current chunk of work has run for too
+    /// long, or if the effect should wait for the browser to be idle.
+    pub(crate) fn yield_before(
+        priority: Priority,
+    ) -> Option<Pin<Box<dyn Future<Output = ()> + Send>>> {
+        let elapsed =
+            js_sys::Date::now() - CHUNK_START.with(|start| start.get());
+        if priority != Priority::Idle && elapsed < CHUNK_BUDGET {
+            return None;
+        }
+
+        let promise = Promise::new(&mut |resolve, _| {
+            if !post_task(&resolve, priority) {
+                fallback(&resolve, priority);
+            }
+        });
+        let fut = JsFuture::from(promise);
+        Some(Box::pin(SendWrapper::new(async move {
+            _ = fut.await;
+            start_chunk();
+        })))
+    }
+
+    fn post_task(resolve: &Function, priority: Priority) -> bool {
+        let global = js_sys::global();
+        let Ok(scheduler) = Reflect::get(&global, &"scheduler".into()) else {
+            return false;
+        };
+        let Some(post_task) = Reflect::get(&scheduler, &"postTask".into())
+            .ok()
+            .and_then(|f| f.dyn_into::<Function>().ok())
+        else {
+            return false;
+        };
+        let options = Object::new();
+        let priority = match priority {
+            Priority::UserBlocking => "user-blocking",
+            Priority::Normal => "user-visible",
+            Priority::Idle => "background",
+        };
+        _ = Reflect::set(&options, &"priority".into(), &priority.into());
+        post_task.call2(&scheduler, resolve, &options).is_ok()
+    }
+
+    fn fallback(resolve: &Function, priority: Priority) {
+        let global = js_sys::global();
+        let request_idle_callback = (priority == Priority::Idle)
+            .then(|| Reflect::get(&global, &"requestIdleCallback".into()).ok())
+            .flatten()
+            .and_then(|f| f.dyn_into::<Function>().ok());
+        let called = match request_idle_callback {
+            Some(request_idle_callback) => {
+                request_idle_callback.call1(&global, resolve).is_ok()
+            }
+            None => Reflect::get(&global, &"setTimeout".into())
+                .ok()
+                .and_then(|f| f.dyn_into::<Function>().ok())
+                .map(|set_timeout| {
+                    set_timeout
+                        .call2(&global, resolve, &JsValue::from(0))
+                        .is_ok()
+                })
+                .unwrap_or(false),
+        };
+        if !called {
+            _ = resolve.call0(&JsValue::UNDEFINED);
+        }
+    }
+}
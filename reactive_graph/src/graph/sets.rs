@@ -36,6 +36,8 @@ impl SourceSet {
     }
 
     pub fn clear_sources(&mut self, subscriber: &AnySubscriber) {
+        #[cfg(feature = "devtools")]
+        crate::devtools::unsubscribed(subscriber);
         for source in self.take() {
             source.remove_subscriber(subscriber);
         }
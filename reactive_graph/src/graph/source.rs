@@ -1,7 +1,10 @@
 use super::{node::ReactiveNode, AnySubscriber};
 use crate::traits::{DefinedAt, IsDisposed};
 use core::{fmt::Debug, hash::Hash};
-use std::{panic::Location, sync::Weak};
+use std::{
+    panic::Location,
+    sync::{Arc, Weak},
+};
 
 /// Abstracts over the type of any reactive source.
 pub trait ToAnySource: IsDisposed {
@@ -41,6 +44,10 @@ impl DefinedAt for AnySource {
             None
         }
     }
+
+    fn debug_name(&self) -> Option<Arc<str>> {
+        crate::diagnostics::node_name(self.0)
+    }
 }
 
 impl Debug for AnySource {
@@ -2,7 +2,11 @@ use super::{node::ReactiveNode, AnySource};
 #[cfg(debug_assertions)]
 use crate::diagnostics::SpecialNonReactiveZone;
 use core::{fmt::Debug, hash::Hash};
-use std::{cell::RefCell, mem, sync::Weak};
+use std::{
+    cell::RefCell,
+    mem,
+    sync::{Arc, Weak},
+};
 
 thread_local! {
     static OBSERVER: RefCell<Option<ObserverState>> = const { RefCell::new(None) };
@@ -161,6 +165,15 @@ pub trait Subscriber: ReactiveNode {
 #[derive(Clone)]
 pub struct AnySubscriber(pub usize, pub Weak<dyn Subscriber + Send + Sync>);
 
+impl AnySubscriber {
+    /// Returns the name given to this subscriber with
+    /// [`WithName::with_name`](crate::traits::WithName::with_name), if any. This is always `None`
+    /// in release mode.
+    pub fn debug_name(&self) -> Option<Arc<str>> {
+        crate::diagnostics::node_name(self.0)
+    }
+}
+
 impl ToAnySubscriber for AnySubscriber {
     fn to_any_subscriber(&self) -> AnySubscriber {
         self.clone()
@@ -0,0 +1,505 @@
+//! Undo and redo for reactive values.
+//!
+//! A [`History`] subscribes to a signal (or any other [`Restorable`] value, like a store) and
+//! records what is needed to undo each change to it. Changes can then be undone and redone, and
+//! reactive [`can_undo`](History::can_undo) and [`can_redo`](History::can_redo) signals make
+//! it easy to enable or disable the corresponding buttons in a user interface.
+//!
+//! ```rust
+//! # use reactive_graph::prelude::*;
+//! # use reactive_graph::signal::*; let owner = reactive_graph::owner::Owner::new(); owner.set();
+//! use reactive_graph::history::History;
+//!
+//! let text = ArcRwSignal::new(String::new());
+//! let history = History::new(text.clone());
+//!
+//! text.set("Hello".to_string());
+//! text.set("Hello, world!".to_string());
+//!
+//! history.undo();
+//! assert_eq!(text.get(), "Hello");
+//! history.undo();
+//! assert_eq!(text.get(), "");
+//! assert!(!history.can_undo().get());
+//!
+//! history.redo();
+//! assert_eq!(text.get(), "Hello");
+//! ```
+
+use crate::{
+    graph::{
+        AnySource, AnySubscriber, ReactiveNode, SourceSet, Subscriber,
+        ToAnySubscriber, WithObserver,
+    },
+    owner::Storage,
+    signal::{ArcReadSignal, ArcRwSignal, RwSignal},
+    traits::{Set, Track, WithUntracked},
+};
+use or_poisoned::OrPoisoned;
+use std::{
+    collections::VecDeque,
+    fmt::Debug,
+    mem,
+    sync::{Arc, RwLock, Weak},
+    time::Duration,
+};
+
+/// A reactive value whose changes can be recorded, undone and redone by a [`History`].
+pub trait Restorable: Clone + Send + Sync + 'static {
+    /// A record of a change to the value, holding what is needed to undo it.
+    type Change: Send + Sync + 'static;
+
+    /// Calls `on_change` with a record of every change to the value, until the returned
+    /// [`Recording`] is dropped.
+    fn record_changes(
+        &self,
+        on_change: Arc<dyn Fn(Self::Change) + Send + Sync>,
+    ) -> Recording;
+
+    /// Combines a change with the change that was made directly after it, so that they are
+    /// undone and redone together.
+    fn merge(earlier: &mut Self::Change, later: Self::Change);
+
+    /// Undoes a change, notifying subscribers, and returns the change that redoes it.
+    fn revert(&self, change: Self::Change) -> Self::Change;
+}
+
+/// Stops recording the changes to a [`Restorable`] value when dropped.
+pub struct Recording(Option<Box<dyn FnOnce() + Send + Sync>>);
+
+impl Recording {
+    /// Creates a recording that calls `stop` when it is dropped.
+    pub fn new(stop: impl FnOnce() + Send + Sync + 'static) -> Self {
+        Self(Some(Box::new(stop)))
+    }
+}
+
+impl Debug for Recording {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Recording").finish_non_exhaustive()
+    }
+}
+
+impl Drop for Recording {
+    fn drop(&mut self) {
+        if let Some(stop) = self.0.take() {
+            stop();
+        }
+    }
+}
+
+/// Signals record the value they had before each change, and restore it by setting the signal.
+impl<T> Restorable for ArcRwSignal<T>
+where
+    T: Clone + PartialEq + Send + Sync + 'static,
+{
+    type Change = T;
+
+    fn record_changes(
+        &self,
+        on_change: Arc<dyn Fn(Self::Change) + Send + Sync>,
+    ) -> Recording {
+        SignalRecorder::start(self.clone(), on_change)
+    }
+
+    fn merge(_earlier: &mut Self::Change, _later: Self::Change) {}
+
+    fn revert(&self, change: Self::Change) -> Self::Change {
+        let current = self.with_untracked(T::clone);
+        self.set(change);
+        current
+    }
+}
+
+/// Signals record the value they had before each change, and restore it by setting the signal.
+impl<T, S> Restorable for RwSignal<T, S>
+where
+    T: Clone + PartialEq + Send + Sync + 'static,
+    S: Storage<ArcRwSignal<T>> + Send + Sync + 'static,
+{
+    type Change = T;
+
+    fn record_changes(
+        &self,
+        on_change: Arc<dyn Fn(Self::Change) + Send + Sync>,
+    ) -> Recording {
+        SignalRecorder::start(*self, on_change)
+    }
+
+    fn merge(_earlier: &mut Self::Change, _later: Self::Change) {}
+
+    fn revert(&self, change: Self::Change) -> Self::Change {
+        let current = self.with_untracked(T::clone);
+        self.set(change);
+        current
+    }
+}
+
+/// Options for a [`History`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HistoryOptions {
+    /// The maximum number of undo steps to keep. Older steps are discarded. Defaults to `100`.
+    pub capacity: usize,
+    /// If set, changes that happen within this duration of the previous change are merged into
+    /// a single undo step. Defaults to `None`.
+    pub coalesce: Option<Duration>,
+}
+
+impl Default for HistoryOptions {
+    fn default() -> Self {
+        Self {
+            capacity: 100,
+            coalesce: None,
+        }
+    }
+}
+
+/// Records changes to a reactive value, allowing them to be undone and redone.
+///
+/// Every change to the value is recorded as an undo step, unless it is merged into the previous
+/// step because it happened inside the same [`group`](History::group) or within the
+/// [`coalesce`](HistoryOptions::coalesce) window. Undoing or redoing a step uses
+/// [`Restorable::revert`].
+///
+/// The history stops recording when it (and all its clones) are dropped.
+pub struct History<S: Restorable> {
+    inner: Arc<HistoryInner<S>>,
+}
+
+impl<S: Restorable> Clone for History<S> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+impl<S: Restorable> Debug for History<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("History").finish_non_exhaustive()
+    }
+}
+
+struct HistoryInner<S: Restorable> {
+    target: S,
+    state: RwLock<HistoryState<S::Change>>,
+    can_undo: ArcRwSignal<bool>,
+    can_redo: ArcRwSignal<bool>,
+    _recording: Recording,
+}
+
+struct HistoryState<T> {
+    options: HistoryOptions,
+    undo: VecDeque<T>,
+    redo: Vec<T>,
+    last_change: Option<f64>,
+    // the next change will always create a new undo step
+    sealed: bool,
+    group_depth: usize,
+    // a change has already been recorded in the current group
+    grouped: bool,
+    restoring: bool,
+}
+
+impl<S: Restorable> History<S> {
+    /// Starts recording changes to the given value, with the default [`HistoryOptions`].
+    pub fn new(target: S) -> Self {
+        Self::new_with_options(target, HistoryOptions::default())
+    }
+
+    /// Starts recording changes to the given value.
+    pub fn new_with_options(target: S, options: HistoryOptions) -> Self {
+        let inner =
+            Arc::new_cyclic(|weak: &Weak<HistoryInner<S>>| HistoryInner {
+                _recording: target.record_changes({
+                    let weak = Weak::clone(weak);
+                    Arc::new(move |change| {
+                        if let Some(inner) = weak.upgrade() {
+                            inner.record(change);
+                        }
+                    })
+                }),
+                target,
+                state: RwLock::new(HistoryState {
+                    options,
+                    undo: VecDeque::new(),
+                    redo: Vec::new(),
+                    last_change: None,
+                    sealed: true,
+                    group_depth: 0,
+                    grouped: false,
+                    restoring: false,
+                }),
+                can_undo: ArcRwSignal::new(false),
+                can_redo: ArcRwSignal::new(false),
+            });
+        Self { inner }
+    }
+
+    /// The value whose changes are being recorded.
+    pub fn target(&self) -> &S {
+        &self.inner.target
+    }
+
+    /// A signal that is `true` if there is a change that can be undone.
+    pub fn can_undo(&self) -> ArcReadSignal<bool> {
+        self.inner.can_undo.read_only()
+    }
+
+    /// A signal that is `true` if there is a change that can be redone.
+    pub fn can_redo(&self) -> ArcReadSignal<bool> {
+        self.inner.can_redo.read_only()
+    }
+
+    /// Restores the value from before the most recent undo step.
+    ///
+    /// Returns `false` if there was nothing to undo.
+    pub fn undo(&self) -> bool {
+        let change = {
+            let mut state = self.inner.state.write().or_poisoned();
+            let Some(change) = state.undo.pop_back() else {
+                return false;
+            };
+            state.sealed = true;
+            state.restoring = true;
+            change
+        };
+        let redo = self.inner.revert(change);
+        self.inner.state.write().or_poisoned().redo.push(redo);
+        self.inner.update_signals();
+        true
+    }
+
+    /// Restores the value from before the most recent undo.
+    ///
+    /// Returns `false` if there was nothing to redo.
+    pub fn redo(&self) -> bool {
+        let change = {
+            let mut state = self.inner.state.write().or_poisoned();
+            let Some(change) = state.redo.pop() else {
+                return false;
+            };
+            state.sealed = true;
+            state.restoring = true;
+            change
+        };
+        let undo = self.inner.revert(change);
+        self.inner.state.write().or_poisoned().undo.push_back(undo);
+        self.inner.update_signals();
+        true
+    }
+
+    /// Ensures that the next change starts a new undo step, even if it happens within the
+    /// coalescing window.
+    pub fn checkpoint(&self) {
+        self.inner.state.write().or_poisoned().sealed = true;
+    }
+
+    /// Runs the given function, recording all the changes made inside it as a single undo step.
+    pub fn group<T>(&self, fun: impl FnOnce() -> T) -> T {
+        {
+            let mut state = self.inner.state.write().or_poisoned();
+            if state.group_depth == 0 {
+                state.sealed = true;
+                state.grouped = false;
+            }
+            state.group_depth += 1;
+        }
+        let value = fun();
+        {
+            let mut state = self.inner.state.write().or_poisoned();
+            state.group_depth -= 1;
+            if state.group_depth == 0 {
+                state.sealed = true;
+            }
+        }
+        value
+    }
+
+    /// Discards all undo and redo steps.
+    pub fn clear(&self) {
+        {
+            let mut state = self.inner.state.write().or_poisoned();
+            state.undo.clear();
+            state.redo.clear();
+            state.sealed = true;
+        }
+        self.inner.update_signals();
+    }
+}
+
+impl<S: Restorable> HistoryInner<S> {
+    fn revert(&self, change: S::Change) -> S::Change {
+        let change = self.target.revert(change);
+        self.state.write().or_poisoned().restoring = false;
+        change
+    }
+
+    fn update_signals(&self) {
+        let (can_undo, can_redo) = {
+            let state = self.state.read().or_poisoned();
+            (!state.undo.is_empty(), !state.redo.is_empty())
+        };
+        if self.can_undo.with_untracked(|prev| *prev != can_undo) {
+            self.can_undo.set(can_undo);
+        }
+        if self.can_redo.with_untracked(|prev| *prev != can_redo) {
+            self.can_redo.set(can_redo);
+        }
+    }
+
+    fn record(&self, change: S::Change) {
+        {
+            let mut state = self.state.write().or_poisoned();
+            if state.restoring {
+                return;
+            }
+
+            let now = now();
+            let merge = if state.group_depth > 0 {
+                mem::replace(&mut state.grouped, true)
+            } else {
+                !state.sealed
+                    && matches!(
+                        (state.options.coalesce, state.last_change),
+                        (Some(window), Some(last))
+                            if now - last <= window.as_secs_f64() * 1000.0
+                    )
+            };
+            match state.undo.back_mut() {
+                Some(prev) if merge => S::merge(prev, change),
+                _ => {
+                    state.undo.push_back(change);
+                    while state.undo.len() > state.options.capacity {
+                        state.undo.pop_front();
+                    }
+                }
+            }
+            state.redo.clear();
+            state.last_change = Some(now);
+            state.sealed = false;
+        }
+        self.update_signals();
+    }
+}
+
+/// Returns the current time in milliseconds.
+fn now() -> f64 {
+    #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+    {
+        js_sys::Date::now()
+    }
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    {
+        use std::time::{SystemTime, UNIX_EPOCH};
+
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|since| since.as_secs_f64() * 1000.0)
+            .unwrap_or_default()
+    }
+}
+
+/// Subscribes to a signal, and passes the value it had before each change to a [`History`].
+struct SignalRecorder<S, T> {
+    signal: S,
+    present: RwLock<T>,
+    on_change: Arc<dyn Fn(T) + Send + Sync>,
+    any_subscriber: AnySubscriber,
+    sources: RwLock<SourceSet>,
+}
+
+impl<S, T> SignalRecorder<S, T>
+where
+    S: Track + WithUntracked<Value = T> + Send + Sync + 'static,
+    T: Clone + PartialEq + Send + Sync + 'static,
+{
+    fn start(signal: S, on_change: Arc<dyn Fn(T) + Send + Sync>) -> Recording {
+        let present = signal.with_untracked(T::clone);
+        let recorder = Arc::new_cyclic(|weak: &Weak<Self>| Self {
+            any_subscriber: AnySubscriber(
+                weak.as_ptr() as usize,
+                Weak::clone(weak) as Weak<dyn Subscriber + Send + Sync>,
+            ),
+            signal,
+            present: RwLock::new(present),
+            on_change,
+            sources: Default::default(),
+        });
+        recorder.track();
+        Recording::new(move || drop(recorder))
+    }
+
+    fn track(&self) {
+        self.any_subscriber.clear_sources(&self.any_subscriber);
+        self.any_subscriber.with_observer(|| self.signal.track());
+    }
+
+    fn record(&self) {
+        let new = self.signal.with_untracked(T::clone);
+        // sources notify each subscriber once, so we need to subscribe again
+        self.track();
+
+        let prev = {
+            let mut present = self.present.write().or_poisoned();
+            if new == *present {
+                return;
+            }
+            mem::replace(&mut *present, new)
+        };
+        (self.on_change)(prev);
+    }
+}
+
+impl<S, T> ReactiveNode for SignalRecorder<S, T>
+where
+    S: Track + WithUntracked<Value = T> + Send + Sync + 'static,
+    T: Clone + PartialEq + Send + Sync + 'static,
+{
+    fn mark_dirty(&self) {
+        self.record();
+    }
+
+    fn mark_check(&self) {
+        self.record();
+    }
+
+    fn mark_subscribers_check(&self) {}
+
+    fn update_if_necessary(&self) -> bool {
+        false
+    }
+}
+
+impl<S, T> Subscriber for SignalRecorder<S, T>
+where
+    S: Track + WithUntracked<Value = T> + Send + Sync + 'static,
+    T: Clone + PartialEq + Send + Sync + 'static,
+{
+    fn add_source(&self, source: AnySource) {
+        self.sources.write().or_poisoned().insert(source);
+    }
+
+    fn clear_sources(&self, subscriber: &AnySubscriber) {
+        self.sources.write().or_poisoned().clear_sources(subscriber);
+    }
+}
+
+impl<S, T> ToAnySubscriber for SignalRecorder<S, T>
+where
+    S: Track + WithUntracked<Value = T> + Send + Sync + 'static,
+    T: Clone + PartialEq + Send + Sync + 'static,
+{
+    fn to_any_subscriber(&self) -> AnySubscriber {
+        self.any_subscriber.clone()
+    }
+}
+
+impl<S, T> Drop for SignalRecorder<S, T> {
+    fn drop(&mut self) {
+        let subscriber = self.any_subscriber.clone();
+        if let Ok(sources) = self.sources.get_mut() {
+            sources.clear_sources(&subscriber);
+        }
+    }
+}
@@ -76,10 +76,14 @@ use std::{fmt::Arguments, future::Future};
 
 pub mod actions;
 pub(crate) mod channel;
+pub mod collections;
 pub mod computed;
+#[cfg(feature = "devtools")]
+pub mod devtools;
 pub mod diagnostics;
 pub mod effect;
 pub mod graph;
+pub mod history;
 pub mod owner;
 #[cfg(feature = "serde")]
 mod serde;
@@ -18,13 +18,13 @@ mod arena_item;
 mod context;
 mod storage;
 mod stored_value;
-use self::arena::Arena;
+pub(crate) use self::arena::Arena;
 pub use arc_stored_value::ArcStoredValue;
 #[cfg(feature = "sandboxed-arenas")]
 pub use arena::sandboxed::Sandboxed;
 #[cfg(feature = "sandboxed-arenas")]
 use arena::ArenaMap;
-use arena::NodeId;
+pub(crate) use arena::NodeId;
 pub use arena_item::*;
 pub use context::*;
 pub use storage::*;
@@ -130,10 +130,16 @@ impl Owner {
                     .and_then(|parent| parent.upgrade())
                     .map(|parent| parent.read().or_poisoned().arena.clone())
                     .unwrap_or_default(),
+                #[cfg(feature = "devtools")]
+                devtools: None,
             })),
             #[cfg(feature = "hydration")]
             shared_context,
         };
+        #[cfg(feature = "devtools")]
+        this.register_devtools(
+            parent.as_ref().map(|parent| parent.as_ptr() as usize),
+        );
         if let Some(parent) = parent.and_then(|n| n.upgrade()) {
             parent
                 .write()
@@ -163,10 +169,14 @@ impl Owner {
                 children: Default::default(),
                 #[cfg(feature = "sandboxed-arenas")]
                 arena: Default::default(),
+                #[cfg(feature = "devtools")]
+                devtools: None,
             })),
             #[cfg(feature = "hydration")]
             shared_context,
         };
+        #[cfg(feature = "devtools")]
+        this.register_devtools(None);
         this.set();
         this
     }
@@ -185,10 +195,14 @@ impl Owner {
                 children: Default::default(),
                 #[cfg(feature = "sandboxed-arenas")]
                 arena,
+                #[cfg(feature = "devtools")]
+                devtools: None,
             })),
             #[cfg(feature = "hydration")]
             shared_context: self.shared_context.clone(),
         };
+        #[cfg(feature = "devtools")]
+        child.register_devtools(Some(self.debug_id()));
         self.inner
             .write()
             .or_poisoned()
@@ -250,6 +264,17 @@ impl Owner {
         }
     }
 
+    #[cfg(feature = "devtools")]
+    fn register_devtools(&self, parent: Option<usize>) {
+        let registration = crate::devtools::Registration::with_owner(
+            self.debug_id(),
+            crate::devtools::NodeKind::Owner,
+            parent,
+            None,
+        );
+        self.inner.write().or_poisoned().devtools = Some(registration);
+    }
+
     fn register(&self, node: NodeId) {
         self.inner.write().or_poisoned().nodes.push(node);
     }
@@ -363,6 +388,8 @@ pub(crate) struct OwnerInner {
     pub children: Vec<Weak<RwLock<OwnerInner>>>,
     #[cfg(feature = "sandboxed-arenas")]
     arena: Arc<RwLock<ArenaMap>>,
+    #[cfg(feature = "devtools")]
+    devtools: Option<crate::devtools::Registration>,
 }
 
 impl Debug for OwnerInner {
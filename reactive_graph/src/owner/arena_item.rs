@@ -2,9 +2,9 @@ use super::{
     arena::{Arena, NodeId},
     LocalStorage, Storage, SyncStorage, OWNER,
 };
-use crate::traits::{Dispose, IntoInner, IsDisposed};
+use crate::traits::{Dispose, IntoInner, IsDisposed, WithName};
 use send_wrapper::SendWrapper;
-use std::{any::Any, hash::Hash, marker::PhantomData};
+use std::{any::Any, hash::Hash, marker::PhantomData, sync::Arc};
 
 /// A copyable, stable reference for any value, stored on the arena whose ownership is managed by the
 /// reactive ownership tree.
@@ -65,6 +65,22 @@ where
     }
 }
 
+impl<T, S> ArenaItem<T, S> {
+    /// Returns the name given to this item with [`WithName::with_name`], if any. This is still
+    /// available for a while after the item has been disposed, and is always `None` in release
+    /// mode.
+    pub fn debug_name(&self) -> Option<Arc<str>> {
+        crate::diagnostics::arena_name(self.node)
+    }
+}
+
+impl<T, S> WithName for ArenaItem<T, S> {
+    fn with_name(self, name: impl Into<Arc<str>>) -> Self {
+        crate::diagnostics::set_arena_name(self.node, name.into());
+        self
+    }
+}
+
 impl<T, S> Default for ArenaItem<T, S>
 where
     T: Default + 'static,
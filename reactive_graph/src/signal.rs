@@ -12,13 +12,17 @@ mod subscriber_traits;
 mod trigger;
 mod write;
 
-use crate::owner::LocalStorage;
+use crate::{
+    owner::{LocalStorage, Storage},
+    traits::WithName,
+};
 pub use arc_read::*;
 pub use arc_rw::*;
 pub use arc_trigger::*;
 pub use arc_write::*;
 pub use read::*;
 pub use rw::*;
+use std::sync::Arc;
 pub use trigger::*;
 pub use write::*;
 
@@ -196,3 +200,21 @@ pub fn create_rw_signal<T: Send + Sync + 'static>(value: T) -> RwSignal<T> {
 pub fn create_trigger() -> ArcTrigger {
     ArcTrigger::new()
 }
+
+impl<T> WithName for (ArcReadSignal<T>, ArcWriteSignal<T>) {
+    fn with_name(self, name: impl Into<Arc<str>>) -> Self {
+        let name = name.into();
+        (self.0.with_name(Arc::clone(&name)), self.1.with_name(name))
+    }
+}
+
+impl<T, S> WithName for (ReadSignal<T, S>, WriteSignal<T, S>)
+where
+    T: 'static,
+    S: Storage<ArcReadSignal<T>> + Storage<ArcWriteSignal<T>>,
+{
+    fn with_name(self, name: impl Into<Arc<str>>) -> Self {
+        let name = name.into();
+        (self.0.with_name(Arc::clone(&name)), self.1.with_name(name))
+    }
+}
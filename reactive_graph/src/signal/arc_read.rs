@@ -2,9 +2,11 @@ use super::{
     guards::{Plain, ReadGuard},
     subscriber_traits::AsSubscriberSet,
 };
+#[cfg(feature = "devtools")]
+use crate::devtools::{NodeKind, Registration};
 use crate::{
     graph::SubscriberSet,
-    traits::{DefinedAt, IntoInner, IsDisposed, ReadUntracked},
+    traits::{DefinedAt, IntoInner, IsDisposed, ReadUntracked, WithName},
 };
 use core::fmt::{Debug, Formatter, Result};
 use std::{
@@ -58,6 +60,8 @@ pub struct ArcReadSignal<T> {
     pub(crate) defined_at: &'static Location<'static>,
     pub(crate) value: Arc<RwLock<T>>,
     pub(crate) inner: Arc<RwLock<SubscriberSet>>,
+    #[cfg(feature = "devtools")]
+    pub(crate) devtools: Arc<Registration>,
 }
 
 impl<T> Clone for ArcReadSignal<T> {
@@ -68,6 +72,8 @@ impl<T> Clone for ArcReadSignal<T> {
             defined_at: self.defined_at,
             value: Arc::clone(&self.value),
             inner: Arc::clone(&self.inner),
+            #[cfg(feature = "devtools")]
+            devtools: Arc::clone(&self.devtools),
         }
     }
 }
@@ -84,11 +90,14 @@ impl<T> Debug for ArcReadSignal<T> {
 impl<T: Default> Default for ArcReadSignal<T> {
     #[track_caller]
     fn default() -> Self {
+        let inner = Arc::new(RwLock::new(SubscriberSet::new()));
         Self {
             #[cfg(any(debug_assertions, leptos_debuginfo))]
             defined_at: Location::caller(),
             value: Arc::new(RwLock::new(T::default())),
-            inner: Arc::new(RwLock::new(SubscriberSet::new())),
+            #[cfg(feature = "devtools")]
+            devtools: Registration::source(&inner, NodeKind::Signal),
+            inner,
         }
     }
 }
@@ -119,6 +128,17 @@ impl<T> DefinedAt for ArcReadSignal<T> {
             None
         }
     }
+
+    fn debug_name(&self) -> Option<Arc<str>> {
+        crate::diagnostics::node_name(crate::diagnostics::arc_id(&self.inner))
+    }
+}
+
+impl<T> WithName for ArcReadSignal<T> {
+    fn with_name(self, name: impl Into<Arc<str>>) -> Self {
+        crate::diagnostics::set_node_name(&self.inner, name.into());
+        self
+    }
 }
 
 impl<T> IsDisposed for ArcReadSignal<T> {
@@ -3,10 +3,14 @@ use super::{
     subscriber_traits::AsSubscriberSet,
     ArcReadSignal, ArcWriteSignal,
 };
+#[cfg(feature = "devtools")]
+use crate::devtools::{NodeKind, Registration};
 use crate::{
     graph::{ReactiveNode, SubscriberSet},
     prelude::{IsDisposed, Notify},
-    traits::{DefinedAt, IntoInner, ReadUntracked, UntrackableGuard, Write},
+    traits::{
+        DefinedAt, IntoInner, ReadUntracked, UntrackableGuard, WithName, Write,
+    },
 };
 use core::fmt::{Debug, Formatter, Result};
 use std::{
@@ -98,6 +102,8 @@ pub struct ArcRwSignal<T> {
     pub(crate) defined_at: &'static Location<'static>,
     pub(crate) value: Arc<RwLock<T>>,
     pub(crate) inner: Arc<RwLock<SubscriberSet>>,
+    #[cfg(feature = "devtools")]
+    pub(crate) devtools: Arc<Registration>,
 }
 
 impl<T> Clone for ArcRwSignal<T> {
@@ -108,6 +114,8 @@ impl<T> Clone for ArcRwSignal<T> {
             defined_at: self.defined_at,
             value: Arc::clone(&self.value),
             inner: Arc::clone(&self.inner),
+            #[cfg(feature = "devtools")]
+            devtools: Arc::clone(&self.devtools),
         }
     }
 }
@@ -153,11 +161,14 @@ impl<T> ArcRwSignal<T> {
     )]
     #[track_caller]
     pub fn new(value: T) -> Self {
+        let inner = Arc::new(RwLock::new(SubscriberSet::new()));
         Self {
             #[cfg(any(debug_assertions, leptos_debuginfo))]
             defined_at: Location::caller(),
             value: Arc::new(RwLock::new(value)),
-            inner: Arc::new(RwLock::new(SubscriberSet::new())),
+            #[cfg(feature = "devtools")]
+            devtools: Registration::source(&inner, NodeKind::Signal),
+            inner,
         }
     }
 
@@ -169,6 +180,8 @@ impl<T> ArcRwSignal<T> {
             defined_at: Location::caller(),
             value: Arc::clone(&self.value),
             inner: Arc::clone(&self.inner),
+            #[cfg(feature = "devtools")]
+            devtools: Arc::clone(&self.devtools),
         }
     }
 
@@ -180,6 +193,8 @@ impl<T> ArcRwSignal<T> {
             defined_at: Location::caller(),
             value: Arc::clone(&self.value),
             inner: Arc::clone(&self.inner),
+            #[cfg(feature = "devtools")]
+            devtools: Arc::clone(&self.devtools),
         }
     }
 
@@ -202,6 +217,8 @@ impl<T> ArcRwSignal<T> {
                 defined_at: Location::caller(),
                 value: read.value,
                 inner: read.inner,
+                #[cfg(feature = "devtools")]
+                devtools: read.devtools,
             })
         } else {
             None
@@ -221,6 +238,17 @@ impl<T> DefinedAt for ArcRwSignal<T> {
             None
         }
     }
+
+    fn debug_name(&self) -> Option<Arc<str>> {
+        crate::diagnostics::node_name(crate::diagnostics::arc_id(&self.inner))
+    }
+}
+
+impl<T> WithName for ArcRwSignal<T> {
+    fn with_name(self, name: impl Into<Arc<str>>) -> Self {
+        crate::diagnostics::set_node_name(&self.inner, name.into());
+        self
+    }
 }
 
 impl<T> IsDisposed for ArcRwSignal<T> {
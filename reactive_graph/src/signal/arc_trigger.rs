@@ -1,7 +1,9 @@
 use super::subscriber_traits::AsSubscriberSet;
+#[cfg(feature = "devtools")]
+use crate::devtools::{NodeKind, Registration};
 use crate::{
     graph::{ReactiveNode, SubscriberSet},
-    traits::{DefinedAt, IsDisposed, Notify},
+    traits::{DefinedAt, IsDisposed, Notify, WithName},
 };
 use std::{
     fmt::{Debug, Formatter, Result},
@@ -16,16 +18,21 @@ pub struct ArcTrigger {
     #[cfg(any(debug_assertions, leptos_debuginfo))]
     pub(crate) defined_at: &'static Location<'static>,
     pub(crate) inner: Arc<RwLock<SubscriberSet>>,
+    #[cfg(feature = "devtools")]
+    pub(crate) devtools: Arc<Registration>,
 }
 
 impl ArcTrigger {
     /// Creates a new trigger.
     #[track_caller]
     pub fn new() -> Self {
+        let inner: Arc<RwLock<SubscriberSet>> = Default::default();
         Self {
             #[cfg(any(debug_assertions, leptos_debuginfo))]
             defined_at: Location::caller(),
-            inner: Default::default(),
+            #[cfg(feature = "devtools")]
+            devtools: Registration::source(&inner, NodeKind::Trigger),
+            inner,
         }
     }
 }
@@ -43,6 +50,8 @@ impl Clone for ArcTrigger {
             #[cfg(any(debug_assertions, leptos_debuginfo))]
             defined_at: self.defined_at,
             inner: Arc::clone(&self.inner),
+            #[cfg(feature = "devtools")]
+            devtools: Arc::clone(&self.devtools),
         }
     }
 }
@@ -81,6 +90,17 @@ impl DefinedAt for ArcTrigger {
             None
         }
     }
+
+    fn debug_name(&self) -> Option<Arc<str>> {
+        crate::diagnostics::node_name(crate::diagnostics::arc_id(&self.inner))
+    }
+}
+
+impl WithName for ArcTrigger {
+    fn with_name(self, name: impl Into<Arc<str>>) -> Self {
+        crate::diagnostics::set_node_name(&self.inner, name.into());
+        self
+    }
 }
 
 impl Notify for ArcTrigger {
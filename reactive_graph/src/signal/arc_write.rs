@@ -1,8 +1,10 @@
 use super::guards::{UntrackedWriteGuard, WriteGuard};
+#[cfg(feature = "devtools")]
+use crate::devtools::Registration;
 use crate::{
     graph::{ReactiveNode, SubscriberSet},
     prelude::{IsDisposed, Notify},
-    traits::{DefinedAt, IntoInner, UntrackableGuard, Write},
+    traits::{DefinedAt, IntoInner, UntrackableGuard, WithName, Write},
 };
 use core::fmt::{Debug, Formatter, Result};
 use std::{
@@ -58,6 +60,8 @@ pub struct ArcWriteSignal<T> {
     pub(crate) defined_at: &'static Location<'static>,
     pub(crate) value: Arc<RwLock<T>>,
     pub(crate) inner: Arc<RwLock<SubscriberSet>>,
+    #[cfg(feature = "devtools")]
+    pub(crate) devtools: Arc<Registration>,
 }
 
 impl<T> Clone for ArcWriteSignal<T> {
@@ -68,6 +72,8 @@ impl<T> Clone for ArcWriteSignal<T> {
             defined_at: self.defined_at,
             value: Arc::clone(&self.value),
             inner: Arc::clone(&self.inner),
+            #[cfg(feature = "devtools")]
+            devtools: Arc::clone(&self.devtools),
         }
     }
 }
@@ -107,6 +113,17 @@ impl<T> DefinedAt for ArcWriteSignal<T> {
             None
         }
     }
+
+    fn debug_name(&self) -> Option<Arc<str>> {
+        crate::diagnostics::node_name(crate::diagnostics::arc_id(&self.inner))
+    }
+}
+
+impl<T> WithName for ArcWriteSignal<T> {
+    fn with_name(self, name: impl Into<Arc<str>>) -> Self {
+        crate::diagnostics::set_node_name(&self.inner, name.into());
+        self
+    }
 }
 
 impl<T> IsDisposed for ArcWriteSignal<T> {
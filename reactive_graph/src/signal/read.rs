@@ -6,7 +6,9 @@ use super::{
 use crate::{
     graph::SubscriberSet,
     owner::{ArenaItem, FromLocal, LocalStorage, Storage, SyncStorage},
-    traits::{DefinedAt, Dispose, IntoInner, IsDisposed, ReadUntracked},
+    traits::{
+        DefinedAt, Dispose, IntoInner, IsDisposed, ReadUntracked, WithName,
+    },
     unwrap_signal,
 };
 use core::fmt::Debug;
@@ -114,6 +116,25 @@ impl<T, S> DefinedAt for ReadSignal<T, S> {
             None
         }
     }
+
+    fn debug_name(&self) -> Option<Arc<str>> {
+        self.inner.debug_name()
+    }
+}
+
+impl<T, S> WithName for ReadSignal<T, S>
+where
+    T: 'static,
+    S: Storage<ArcReadSignal<T>>,
+{
+    fn with_name(self, name: impl Into<Arc<str>>) -> Self {
+        let name = name.into();
+        if let Some(inner) = self.inner.try_get_value() {
+            inner.with_name(Arc::clone(&name));
+        }
+        self.inner.with_name(name);
+        self
+    }
 }
 
 impl<T, S> IsDisposed for ReadSignal<T, S> {
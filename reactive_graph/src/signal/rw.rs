@@ -9,7 +9,7 @@ use crate::{
     signal::guards::{UntrackedWriteGuard, WriteGuard},
     traits::{
         DefinedAt, Dispose, IntoInner, IsDisposed, Notify, ReadUntracked,
-        UntrackableGuard, Write,
+        UntrackableGuard, WithName, Write,
     },
     unwrap_signal,
 };
@@ -238,6 +238,8 @@ where
                             defined_at: Location::caller(),
                             value: Arc::clone(&read.value),
                             inner: Arc::clone(&read.inner),
+                            #[cfg(feature = "devtools")]
+                            devtools: Arc::clone(&read.devtools),
                         }),
                     })
                 } else {
@@ -305,6 +307,25 @@ impl<T, S> DefinedAt for RwSignal<T, S> {
             None
         }
     }
+
+    fn debug_name(&self) -> Option<Arc<str>> {
+        self.inner.debug_name()
+    }
+}
+
+impl<T, S> WithName for RwSignal<T, S>
+where
+    T: 'static,
+    S: Storage<ArcRwSignal<T>>,
+{
+    fn with_name(self, name: impl Into<Arc<str>>) -> Self {
+        let name = name.into();
+        if let Some(inner) = self.inner.try_get_value() {
+            inner.with_name(Arc::clone(&name));
+        }
+        self.inner.with_name(name);
+        self
+    }
 }
 
 impl<T: 'static, S> IsDisposed for RwSignal<T, S> {
@@ -54,7 +54,17 @@ impl<T: AsSubscriberSet + DefinedAt> ReactiveNode for T {
     fn mark_check(&self) {}
 
     fn mark_subscribers_check(&self) {
+        #[cfg(debug_assertions)]
+        let _notifying =
+            crate::diagnostics::enter_notify(crate::diagnostics::NodeInfo {
+                kind: None,
+                type_name: std::any::type_name::<T>(),
+                name: self.debug_name(),
+                defined_at: self.defined_at(),
+            });
         if let Some(inner) = self.as_subscriber_set() {
+            #[cfg(feature = "devtools")]
+            crate::devtools::notified(inner.borrow() as *const _ as usize);
             let subs = inner.borrow().write().unwrap().take();
             for sub in subs {
                 sub.mark_dirty();
@@ -121,6 +131,8 @@ impl ReactiveNode for RwLock<SubscriberSet> {
     fn mark_check(&self) {}
 
     fn mark_subscribers_check(&self) {
+        #[cfg(feature = "devtools")]
+        crate::devtools::notified(self as *const _ as usize);
         let subs = self.write().unwrap().take();
         for sub in subs {
             sub.mark_dirty();
@@ -2,7 +2,7 @@ use super::{subscriber_traits::AsSubscriberSet, ArcTrigger};
 use crate::{
     graph::{ReactiveNode, SubscriberSet},
     owner::ArenaItem,
-    traits::{DefinedAt, Dispose, IsDisposed, Notify},
+    traits::{DefinedAt, Dispose, IsDisposed, Notify, WithName},
 };
 use std::{
     fmt::{Debug, Formatter, Result},
@@ -92,6 +92,21 @@ impl DefinedAt for Trigger {
             None
         }
     }
+
+    fn debug_name(&self) -> Option<Arc<str>> {
+        self.inner.debug_name()
+    }
+}
+
+impl WithName for Trigger {
+    fn with_name(self, name: impl Into<Arc<str>>) -> Self {
+        let name = name.into();
+        if let Some(inner) = self.inner.try_get_value() {
+            inner.with_name(Arc::clone(&name));
+        }
+        self.inner.with_name(name);
+        self
+    }
 }
 
 impl Notify for Trigger {
@@ -3,7 +3,7 @@ use crate::{
     owner::{ArenaItem, FromLocal, LocalStorage, Storage, SyncStorage},
     traits::{
         DefinedAt, Dispose, IntoInner, IsDisposed, Notify, UntrackableGuard,
-        Write,
+        WithName, Write,
     },
 };
 use core::fmt::Debug;
@@ -109,6 +109,25 @@ impl<T, S> DefinedAt for WriteSignal<T, S> {
             None
         }
     }
+
+    fn debug_name(&self) -> Option<Arc<str>> {
+        self.inner.debug_name()
+    }
+}
+
+impl<T, S> WithName for WriteSignal<T, S>
+where
+    T: 'static,
+    S: Storage<ArcWriteSignal<T>>,
+{
+    fn with_name(self, name: impl Into<Arc<str>>) -> Self {
+        let name = name.into();
+        if let Some(inner) = self.inner.try_get_value() {
+            inner.with_name(Arc::clone(&name));
+        }
+        self.inner.with_name(name);
+        self
+    }
 }
 
 impl<T> From<ArcWriteSignal<T>> for WriteSignal<T>
@@ -11,6 +11,8 @@
 //! - [`DefinedAt`] is used for debugging in the case of errors and should be implemented for all
 //!   signal types.
 //! - [`IsDisposed`] checks whether a signal is currently accessible.
+//! - [`WithName`] gives a signal a name, which is included in those errors along with
+//!   [`DefinedAt`].
 //!
 //! ## Base Traits
 //! | Trait             | Mode  | Description                                                                           |
@@ -60,6 +62,7 @@ use futures::{Stream, StreamExt};
 use std::{
     ops::{Deref, DerefMut},
     panic::Location,
+    sync::Arc,
 };
 
 #[doc(hidden)]
@@ -76,6 +79,7 @@ macro_rules! unwrap_signal {
                     "{}",
                     $crate::traits::panic_getting_disposed_signal(
                         $signal.defined_at(),
+                        $signal.debug_name().as_deref(),
                         location
                     )
                 );
@@ -115,7 +119,10 @@ impl<T: Source + ToAnySource + DefinedAt> Track for T {
         }
 
         if let Some(subscriber) = Observer::get() {
-            subscriber.add_source(self.to_any_source());
+            let source = self.to_any_source();
+            #[cfg(feature = "devtools")]
+            crate::devtools::subscribed(&source, &subscriber);
+            subscriber.add_source(source);
             self.add_subscriber(subscriber);
         } else {
             #[cfg(all(debug_assertions, feature = "effects"))]
@@ -129,8 +136,12 @@ impl<T: Source + ToAnySource + DefinedAt> Track for T {
                         .defined_at()
                         .map(ToString::to_string)
                         .unwrap_or_else(|| String::from("{unknown}"));
+                    let name = self
+                        .debug_name()
+                        .map(|name| format!(" {name:?}"))
+                        .unwrap_or_default();
                     crate::log_warning(format_args!(
-                        "At {called_at}, you access a {ty} (defined at \
+                        "At {called_at}, you access a {ty}{name} (defined at \
                          {defined_at}) outside a reactive tracking context. \
                          This might mean your app is not responding to \
                          changes in signal values in the way you \
@@ -650,22 +661,51 @@ pub trait DefinedAt {
     /// Returns the location at which the signal was defined. This is usually simply `None` in
     /// release mode.
     fn defined_at(&self) -> Option<&'static Location<'static>>;
+
+    /// Returns the name given to the signal with [`WithName::with_name`], if any. This is always
+    /// `None` in release mode.
+    fn debug_name(&self) -> Option<Arc<str>> {
+        None
+    }
+}
+
+/// Gives a reactive value a name, which is included in debug-mode diagnostics (like warnings
+/// about reading a signal outside a tracking context, or panics when reading a disposed signal)
+/// and in the [`devtools`](crate::devtools), alongside the location at which it was defined.
+///
+/// ```rust
+/// # use reactive_graph::prelude::*;
+/// # use reactive_graph::signal::*; let owner = reactive_graph::owner::Owner::new(); owner.set();
+/// let total = RwSignal::new(0).with_name("cart.total");
+/// # #[cfg(debug_assertions)]
+/// assert_eq!(total.debug_name().as_deref(), Some("cart.total"));
+/// ```
+pub trait WithName: Sized {
+    /// Sets the name of this reactive value, and returns it.
+    ///
+    /// Names are only kept in debug mode (or with `--cfg=leptos_debuginfo`), so this does nothing
+    /// in release mode.
+    fn with_name(self, name: impl Into<Arc<str>>) -> Self;
 }
 
 #[doc(hidden)]
 pub fn panic_getting_disposed_signal(
     defined_at: Option<&'static Location<'static>>,
+    debug_name: Option<&str>,
     location: &'static Location<'static>,
 ) -> String {
+    let name = debug_name
+        .map(|name| format!(" {name:?}"))
+        .unwrap_or_default();
     if let Some(defined_at) = defined_at {
         format!(
-            "At {location}, you tried to access a reactive value which was \
-             defined at {defined_at}, but it has already been disposed."
+            "At {location}, you tried to access a reactive value{name} which \
+             was defined at {defined_at}, but it has already been disposed."
         )
     } else {
         format!(
-            "At {location}, you tried to access a reactive value, but it has \
-             already been disposed."
+            "At {location}, you tried to access a reactive value{name}, but \
+             it has already been disposed."
         )
     }
 }
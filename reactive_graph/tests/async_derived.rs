@@ -137,3 +137,206 @@ async fn async_derived_with_initial() {
     signal2.set(1);
     assert_eq!(derived.await, 2);
 }
+
+#[tokio::test]
+async fn superseded_future_is_dropped_and_aborted() {
+    use reactive_graph::computed::use_abort_signal;
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    _ = Executor::init_tokio();
+    let owner = Owner::new();
+    owner.set();
+
+    let aborted = Arc::new(AtomicUsize::new(0));
+    let signal = RwSignal::new(0);
+    let value = ArcAsyncDerived::new({
+        let aborted = Arc::clone(&aborted);
+        move || {
+            let n = signal.get();
+            let abort = use_abort_signal().unwrap();
+            let aborted = Arc::clone(&aborted);
+            async move {
+                abort.on_abort(move || {
+                    aborted.fetch_add(1, Ordering::Relaxed);
+                });
+                // this run would never finish if it were not cancelled
+                if n == 1 {
+                    pending::<()>().await;
+                }
+                n
+            }
+        }
+    });
+
+    assert_eq!(value.clone().await, 0);
+    signal.set(1);
+    Executor::tick().await;
+    Executor::tick().await;
+    assert_eq!(aborted.load(Ordering::Relaxed), 0);
+
+    signal.set(2);
+    assert_eq!(value.clone().await, 2);
+    assert_eq!(aborted.load(Ordering::Relaxed), 1);
+}
+
+#[tokio::test]
+async fn pending_future_is_aborted_when_dropped() {
+    use reactive_graph::computed::use_abort_signal;
+
+    _ = Executor::init_tokio();
+    let owner = Owner::new();
+    owner.set();
+
+    let signal = RwSignal::new(0);
+    let (tx, rx) = futures::channel::oneshot::channel();
+    let tx = std::sync::Mutex::new(Some(tx));
+    let value = ArcAsyncDerived::new(move || {
+        let n = signal.get();
+        let abort = use_abort_signal().unwrap();
+        if n == 1 {
+            if let Some(tx) = tx.lock().unwrap().take() {
+                _ = tx.send(abort);
+            }
+        }
+        async move {
+            if n == 1 {
+                pending::<()>().await;
+            }
+            n
+        }
+    });
+    assert_eq!(value.clone().await, 0);
+
+    signal.set(1);
+    let abort = rx.await.unwrap();
+    assert!(!abort.is_aborted());
+    drop(value);
+    abort.aborted().await;
+}
+
+#[tokio::test]
+async fn abort_signal_is_scoped_to_its_run() {
+    use reactive_graph::computed::use_abort_signal;
+    use std::sync::{Arc, Mutex};
+
+    _ = Executor::init_tokio();
+    let owner = Owner::new();
+    owner.set();
+
+    let child = Arc::new(Mutex::new(None));
+    let value = ArcAsyncDerived::new({
+        let child = Arc::clone(&child);
+        move || {
+            *child.lock().unwrap() =
+                Owner::current().map(|owner| owner.child());
+            let abort = use_abort_signal().unwrap();
+            async move {
+                Executor::tick().await;
+                // the signal is still available after the Future has been suspended
+                let current = use_abort_signal().unwrap();
+                !current.is_aborted() && !abort.is_aborted()
+            }
+        }
+    });
+    assert!(value.await);
+    assert!(use_abort_signal().is_none());
+
+    // an owner created during the run does not inherit its signal
+    let child = child.lock().unwrap().take().unwrap();
+    assert!(child.with(use_abort_signal).is_none());
+}
+
+#[tokio::test]
+async fn debounced_async_derived_reruns_once() {
+    use reactive_graph::computed::AsyncDerivedOptions;
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+
+    _ = Executor::init_tokio();
+    let owner = Owner::new();
+    owner.set();
+
+    let runs = Arc::new(AtomicUsize::new(0));
+    let signal = RwSignal::new(0);
+    let value = AsyncDerived::new_with_options(
+        AsyncDerivedOptions {
+            debounce: Some(Duration::from_millis(50)),
+            ..Default::default()
+        },
+        {
+            let runs = Arc::clone(&runs);
+            move || {
+                runs.fetch_add(1, Ordering::Relaxed);
+                let n = signal.get();
+                async move { n }
+            }
+        },
+    );
+    assert_eq!(value.await, 0);
+
+    for n in 1..=3 {
+        signal.set(n);
+        tokio::time::sleep(Duration::from_millis(5)).await;
+    }
+    assert_eq!(runs.load(Ordering::Relaxed), 1);
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(value.await, 3);
+    assert_eq!(runs.load(Ordering::Relaxed), 2);
+}
+
+#[tokio::test]
+async fn throttled_async_derived_reruns_while_changing() {
+    use reactive_graph::computed::AsyncDerivedOptions;
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+
+    _ = Executor::init_tokio();
+    let owner = Owner::new();
+    owner.set();
+
+    let runs = Arc::new(AtomicUsize::new(0));
+    let signal = RwSignal::new(0);
+    let value = AsyncDerived::new_with_options(
+        AsyncDerivedOptions {
+            throttle: Some(Duration::from_millis(100)),
+            ..Default::default()
+        },
+        {
+            let runs = Arc::clone(&runs);
+            move || {
+                runs.fetch_add(1, Ordering::Relaxed);
+                let n = signal.get();
+                async move { n }
+            }
+        },
+    );
+    assert_eq!(value.await, 0);
+
+    // unlike a debounce, a throttle keeps re-running while the dependencies keep changing, but
+    // at most once in each period
+    for n in 1..=10 {
+        signal.set(n);
+        tokio::time::sleep(Duration::from_millis(30)).await;
+    }
+    let reruns = runs.load(Ordering::Relaxed) - 1;
+    assert!((2..=4).contains(&reruns), "re-ran {reruns} times");
+
+    // the last change is handled by the end of the period
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    assert_eq!(value.await, 10);
+    assert!(runs.load(Ordering::Relaxed) <= reruns + 2);
+}
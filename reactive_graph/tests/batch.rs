@@ -0,0 +1,106 @@
+#[cfg(feature = "effects")]
+pub mod imports {
+    pub use any_spawner::Executor;
+    pub use reactive_graph::{
+        effect::{batch, batch_async, is_batching, Effect, RenderEffect},
+        owner::Owner,
+        prelude::*,
+        signal::RwSignal,
+    };
+    pub use std::sync::{Arc, RwLock};
+    pub use tokio::task;
+}
+
+#[cfg(feature = "effects")]
+#[tokio::test]
+async fn batch_runs_effect_once() {
+    use imports::*;
+
+    _ = Executor::init_tokio();
+    let owner = Owner::new();
+    owner.set();
+
+    task::LocalSet::new()
+        .run_until(async {
+            let a = RwSignal::new(0);
+            let b = RwSignal::new(0);
+            let runs = Arc::new(RwLock::new(Vec::new()));
+
+            Effect::new({
+                let runs = Arc::clone(&runs);
+                move || {
+                    runs.write().unwrap().push((a.get(), b.get()));
+                }
+            });
+            Executor::tick().await;
+            assert_eq!(*runs.read().unwrap(), [(0, 0)]);
+
+            let value = batch(|| {
+                assert!(is_batching());
+                a.set(1);
+                b.set(2);
+                // nested batches are deferred until the outer batch ends
+                batch(|| a.set(3));
+                a.get() + b.get()
+            });
+            assert_eq!(value, 5);
+            assert!(!is_batching());
+
+            Executor::tick().await;
+            assert_eq!(*runs.read().unwrap(), [(0, 0), (3, 2)]);
+        })
+        .await
+}
+
+#[cfg(feature = "effects")]
+#[tokio::test]
+async fn batch_async_defers_effects_across_await_points() {
+    use imports::*;
+
+    _ = Executor::init_tokio();
+    let owner = Owner::new();
+    owner.set();
+
+    task::LocalSet::new()
+        .run_until(async {
+            let a = RwSignal::new(0);
+            let b = RwSignal::new(0);
+            let effect_runs = Arc::new(RwLock::new(Vec::new()));
+            let render_effect_runs = Arc::new(RwLock::new(Vec::new()));
+
+            Effect::new({
+                let runs = Arc::clone(&effect_runs);
+                move || {
+                    runs.write().unwrap().push((a.get(), b.get()));
+                }
+            });
+            let _render_effect = RenderEffect::new({
+                let runs = Arc::clone(&render_effect_runs);
+                move |_| {
+                    runs.write().unwrap().push((a.get(), b.get()));
+                }
+            });
+            Executor::tick().await;
+            assert_eq!(*effect_runs.read().unwrap(), [(0, 0)]);
+            assert_eq!(*render_effect_runs.read().unwrap(), [(0, 0)]);
+
+            batch_async({
+                let effect_runs = Arc::clone(&effect_runs);
+                let render_effect_runs = Arc::clone(&render_effect_runs);
+                async move {
+                    a.set(1);
+                    Executor::tick().await;
+                    // effects have not run, even though the runtime had the chance to run them
+                    assert_eq!(effect_runs.read().unwrap().len(), 1);
+                    assert_eq!(render_effect_runs.read().unwrap().len(), 1);
+                    b.set(2);
+                }
+            })
+            .await;
+
+            Executor::tick().await;
+            assert_eq!(*effect_runs.read().unwrap(), [(0, 0), (1, 2)]);
+            assert_eq!(*render_effect_runs.read().unwrap(), [(0, 0), (1, 2)]);
+        })
+        .await
+}
@@ -0,0 +1,248 @@
+use reactive_graph::{
+    collections::{ReactiveMap, ReactiveVec},
+    computed::ArcMemo,
+    owner::Owner,
+    prelude::*,
+};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+/// Creates a memo that counts how many times it has run.
+fn counted<T: PartialEq + Send + Sync + 'static>(
+    fun: impl Fn() -> T + Send + Sync + 'static,
+) -> (ArcMemo<T>, Arc<AtomicUsize>) {
+    let runs = Arc::new(AtomicUsize::new(0));
+    let memo = ArcMemo::new({
+        let runs = Arc::clone(&runs);
+        move |_| {
+            runs.fetch_add(1, Ordering::Relaxed);
+            fun()
+        }
+    });
+    (memo, runs)
+}
+
+#[test]
+fn vec_set_only_notifies_that_index() {
+    let owner = Owner::new();
+    owner.set();
+
+    let items = ReactiveVec::from(vec![1, 2, 3]);
+    let (first, first_runs) = counted({
+        let items = items.clone();
+        move || items.get(0)
+    });
+    let (second, second_runs) = counted({
+        let items = items.clone();
+        move || items.get(1)
+    });
+    let (len, len_runs) = counted({
+        let items = items.clone();
+        move || items.len()
+    });
+    let (sum, sum_runs) = counted({
+        let items = items.clone();
+        move || items.with(|items| items.iter().sum::<i32>())
+    });
+    assert_eq!(first.get(), Some(1));
+    assert_eq!(second.get(), Some(2));
+    assert_eq!(len.get(), 3);
+    assert_eq!(sum.get(), 6);
+
+    items.set(1, 20);
+    assert_eq!(first.get(), Some(1));
+    assert_eq!(second.get(), Some(20));
+    assert_eq!(len.get(), 3);
+    assert_eq!(sum.get(), 24);
+    assert_eq!(first_runs.load(Ordering::Relaxed), 1);
+    assert_eq!(second_runs.load(Ordering::Relaxed), 2);
+    assert_eq!(len_runs.load(Ordering::Relaxed), 1);
+    assert_eq!(sum_runs.load(Ordering::Relaxed), 2);
+
+    items.update(0, |n| *n += 10);
+    assert_eq!(first.get(), Some(11));
+    assert_eq!(second.get(), Some(20));
+    assert_eq!(first_runs.load(Ordering::Relaxed), 2);
+    assert_eq!(second_runs.load(Ordering::Relaxed), 2);
+}
+
+#[test]
+fn vec_structural_changes_notify_moved_indices() {
+    let owner = Owner::new();
+    owner.set();
+
+    let items = ReactiveVec::from(vec!["a", "b", "c"]);
+    let (first, first_runs) = counted({
+        let items = items.clone();
+        move || items.get(0)
+    });
+    let (last, last_runs) = counted({
+        let items = items.clone();
+        move || items.get(2)
+    });
+    let (len, len_runs) = counted({
+        let items = items.clone();
+        move || items.len()
+    });
+    assert_eq!(first.get(), Some("a"));
+    assert_eq!(last.get(), Some("c"));
+    assert_eq!(len.get(), 3);
+
+    // pushing only affects the length and the new index
+    items.push("d");
+    assert_eq!(first.get(), Some("a"));
+    assert_eq!(last.get(), Some("c"));
+    assert_eq!(len.get(), 4);
+    assert_eq!(first_runs.load(Ordering::Relaxed), 1);
+    assert_eq!(last_runs.load(Ordering::Relaxed), 1);
+    assert_eq!(len_runs.load(Ordering::Relaxed), 2);
+
+    // removing shifts every later item
+    assert_eq!(items.remove(1), "b");
+    assert_eq!(first.get(), Some("a"));
+    assert_eq!(last.get(), Some("d"));
+    assert_eq!(len.get(), 3);
+    assert_eq!(first_runs.load(Ordering::Relaxed), 1);
+    assert_eq!(last_runs.load(Ordering::Relaxed), 2);
+
+    // inserting at the start shifts everything
+    items.insert(0, "z");
+    assert_eq!(first.get(), Some("z"));
+    assert_eq!(last.get(), Some("c"));
+    assert_eq!(first_runs.load(Ordering::Relaxed), 2);
+
+    items.clear();
+    assert_eq!(first.get(), None);
+    assert_eq!(last.get(), None);
+    assert_eq!(len.get(), 0);
+    assert_eq!(items.to_vec_untracked(), Vec::<&str>::new());
+}
+
+#[test]
+fn map_insert_only_notifies_that_key() {
+    let owner = Owner::new();
+    owner.set();
+
+    let map = ReactiveMap::from_iter([("a", 1), ("b", 2)]);
+    let (a, a_runs) = counted({
+        let map = map.clone();
+        move || map.get("a")
+    });
+    let (keys, keys_runs) = counted({
+        let map = map.clone();
+        move || {
+            let mut keys = map.keys();
+            keys.sort();
+            keys
+        }
+    });
+    let (total, total_runs) = counted({
+        let map = map.clone();
+        move || map.iter().map(|(_, value)| value).sum::<i32>()
+    });
+    assert_eq!(a.get(), Some(1));
+    assert_eq!(keys.get(), ["a", "b"]);
+    assert_eq!(total.get(), 3);
+
+    // updating an existing key does not change the set of keys
+    map.insert("b", 20);
+    assert_eq!(a.get(), Some(1));
+    assert_eq!(keys.get(), ["a", "b"]);
+    assert_eq!(total.get(), 21);
+    assert_eq!(a_runs.load(Ordering::Relaxed), 1);
+    assert_eq!(keys_runs.load(Ordering::Relaxed), 1);
+    assert_eq!(total_runs.load(Ordering::Relaxed), 2);
+
+    // adding a new key does not notify other keys
+    map.insert("c", 3);
+    assert_eq!(a.get(), Some(1));
+    assert_eq!(keys.get(), ["a", "b", "c"]);
+    assert_eq!(a_runs.load(Ordering::Relaxed), 1);
+    assert_eq!(keys_runs.load(Ordering::Relaxed), 2);
+
+    map.update("a", |n| *n += 1);
+    assert_eq!(a.get(), Some(2));
+    assert_eq!(keys.get(), ["a", "b", "c"]);
+    assert_eq!(a_runs.load(Ordering::Relaxed), 2);
+    assert_eq!(keys_runs.load(Ordering::Relaxed), 2);
+}
+
+#[test]
+fn map_tracks_keys_that_are_removed_and_inserted_again() {
+    let owner = Owner::new();
+    owner.set();
+
+    let map = ReactiveMap::<String, i32>::new();
+    let (missing, missing_runs) = counted({
+        let map = map.clone();
+        move || map.get("key")
+    });
+    assert_eq!(missing.get(), None);
+
+    map.insert("key".to_string(), 1);
+    assert_eq!(missing.get(), Some(1));
+
+    assert_eq!(map.remove("key"), Some(1));
+    assert_eq!(missing.get(), None);
+
+    map.insert("key".to_string(), 2);
+    assert_eq!(missing.get(), Some(2));
+    assert_eq!(missing_runs.load(Ordering::Relaxed), 4);
+
+    map.retain(|_, value| *value > 5);
+    assert_eq!(missing.get(), None);
+    assert!(map.is_empty());
+}
+
+#[test]
+fn collections_implement_read_traits() {
+    let owner = Owner::new();
+    owner.set();
+
+    let items = ReactiveVec::from(vec![1, 2]);
+    let (all, all_runs) = counted({
+        let items = items.clone();
+        move || Get::get(&items)
+    });
+    assert_eq!(all.get(), vec![1, 2]);
+    assert_eq!(items.read_untracked().len(), 2);
+
+    items.set(0, 10);
+    assert_eq!(all.get(), vec![10, 2]);
+    assert_eq!(all_runs.load(Ordering::Relaxed), 2);
+    assert_eq!(GetUntracked::get_untracked(&items), vec![10, 2]);
+
+    let map = ReactiveMap::from_iter([("a", 1)]);
+    let (total, total_runs) = counted({
+        let map = map.clone();
+        move || map.with(|map| map.values().sum::<i32>())
+    });
+    assert_eq!(total.get(), 1);
+
+    map.update("a", |n| *n += 1);
+    assert_eq!(total.get(), 2);
+    assert_eq!(total_runs.load(Ordering::Relaxed), 2);
+}
+
+#[test]
+fn vec_indices_only_track_the_length() {
+    let owner = Owner::new();
+    owner.set();
+
+    let items = ReactiveVec::from(vec!["a", "b"]);
+    let (indices, indices_runs) = counted({
+        let items = items.clone();
+        move || items.indices()
+    });
+    assert_eq!(indices.get(), 0..2);
+
+    items.set(1, "c");
+    assert_eq!(indices.get(), 0..2);
+    assert_eq!(indices_runs.load(Ordering::Relaxed), 1);
+
+    items.push("d");
+    assert_eq!(indices.get(), 0..3);
+    assert_eq!(indices_runs.load(Ordering::Relaxed), 2);
+}
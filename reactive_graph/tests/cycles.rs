@@ -0,0 +1,82 @@
+#![cfg(debug_assertions)]
+
+use reactive_graph::{computed::ArcMemo, owner::Owner, prelude::*};
+use std::{
+    panic::{self, AssertUnwindSafe},
+    sync::{Arc, OnceLock},
+};
+
+#[cfg(feature = "effects")]
+pub mod imports {
+    pub use any_spawner::Executor;
+    pub use reactive_graph::{
+        computed::Memo,
+        diagnostics::{self, CycleReporting},
+        effect::Effect,
+        owner::Owner,
+        prelude::*,
+        signal::RwSignal,
+    };
+    pub use tokio::task;
+}
+
+#[test]
+fn memo_that_reads_itself_panics_with_chain() {
+    let owner = Owner::new();
+    owner.set();
+
+    let slot = Arc::new(OnceLock::<ArcMemo<i32>>::new());
+    let a = ArcMemo::new({
+        let slot = Arc::clone(&slot);
+        move |_| slot.get().map(|b| b.get()).unwrap_or_default() + 1
+    });
+    let b = ArcMemo::new({
+        let a = a.clone();
+        move |_| a.get() * 2
+    });
+    _ = slot.set(b.clone());
+
+    let panic = panic::catch_unwind(AssertUnwindSafe(|| b.get()))
+        .expect_err("cycle should panic");
+    let message = panic
+        .downcast_ref::<String>()
+        .expect("panic message should be a String");
+    assert!(message.contains("Detected a cycle in the reactive graph"));
+    // the chain goes b -> a -> b, with the location at which each was created
+    assert_eq!(message.matches("Memo<i32>").count(), 3);
+    assert_eq!(message.matches(file!()).count(), 3);
+}
+
+#[cfg(feature = "effects")]
+#[tokio::test]
+async fn effect_that_writes_what_it_reads_can_panic() {
+    use imports::*;
+
+    _ = Executor::init_tokio();
+    let owner = Owner::new();
+    owner.set();
+
+    diagnostics::set_cycle_reporting(CycleReporting::Panic);
+
+    task::LocalSet::new()
+        .run_until(async {
+            let count = RwSignal::new(0);
+            let doubled = Memo::new(move |_| count.get() * 2);
+
+            // writes to `count` through `doubled`, which would run forever
+            Effect::new(move || {
+                if doubled.get() < 100 {
+                    count.update(|n| *n += 1);
+                }
+            });
+
+            for _ in 0..10 {
+                Executor::tick().await;
+            }
+            // the effect panicked on its first write, so it did not run again
+            assert_eq!(count.get_untracked(), 1);
+        })
+        .await;
+
+    diagnostics::set_cycle_reporting(CycleReporting::Warn);
+}
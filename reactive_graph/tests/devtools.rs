@@ -0,0 +1,147 @@
+#![cfg(feature = "devtools")]
+
+use reactive_graph::{
+    computed::ArcMemo,
+    devtools::{self, DevtoolsEvent, InspectValue, NodeKind},
+    owner::Owner,
+    prelude::*,
+    signal::{ArcRwSignal, RwSignal},
+};
+
+#[cfg(feature = "effects")]
+pub mod imports {
+    pub use any_spawner::Executor;
+    pub use reactive_graph::effect::Effect;
+    pub use tokio::task;
+}
+
+#[test]
+fn signal_creation_and_disposal_are_reported() {
+    let events = devtools::subscribe();
+
+    let owner = Owner::new();
+    let signal = owner.with(|| ArcRwSignal::new(0));
+    let id = devtools::node_id(&signal);
+    assert!(events.try_iter().any(|event| matches!(
+        event,
+        DevtoolsEvent::Created {
+            id: created,
+            kind: NodeKind::Signal,
+            owner: Some(owner_id),
+            ..
+        } if created == id && owner_id == owner.debug_id()
+    )));
+
+    signal.set(1);
+    assert!(events
+        .try_iter()
+        .any(|event| event == DevtoolsEvent::Notified { id }));
+
+    let clone = signal.clone();
+    drop(signal);
+    assert!(devtools::snapshot().node(id).is_some());
+    drop(clone);
+    assert!(devtools::snapshot().node(id).is_none());
+    assert!(events
+        .try_iter()
+        .any(|event| event == DevtoolsEvent::Disposed { id }));
+}
+
+#[test]
+fn snapshot_includes_owner_tree() {
+    let parent = Owner::new();
+    let child = parent.child();
+    let signal = child.with(|| RwSignal::new(0));
+
+    let snapshot = devtools::snapshot();
+    let child_node = snapshot.node(child.debug_id()).unwrap();
+    assert_eq!(child_node.kind, NodeKind::Owner);
+    assert_eq!(child_node.owner, Some(parent.debug_id()));
+    assert!(snapshot
+        .children(child.debug_id())
+        .any(|node| node.id == devtools::node_id(&signal)));
+}
+
+#[test]
+fn snapshot_includes_dependencies_names_and_values() {
+    let owner = Owner::new();
+    owner.set();
+
+    let count = RwSignal::new(1).with_name("count").inspect_value();
+    let double = ArcMemo::new(move |_| count.get() * 2)
+        .with_name("double")
+        .inspect_value();
+    assert_eq!(double.get(), 2);
+
+    let (count_id, double_id) =
+        (devtools::node_id(&count), devtools::node_id(&double));
+    let snapshot = devtools::snapshot();
+    let count_node = snapshot.node(count_id).unwrap();
+    let double_node = snapshot.node(double_id).unwrap();
+    assert_eq!(count_node.name.as_deref(), Some("count"));
+    assert_eq!(count_node.value.as_deref(), Some("1"));
+    assert_eq!(count_node.subscribers, vec![double_id]);
+    assert_eq!(double_node.kind, NodeKind::Memo);
+    assert_eq!(double_node.name.as_deref(), Some("double"));
+    assert_eq!(double_node.value.as_deref(), Some("2"));
+    assert_eq!(double_node.sources, vec![count_id]);
+
+    count.set(2);
+    let snapshot = devtools::snapshot();
+    assert_eq!(snapshot.node(count_id).unwrap().value.as_deref(), Some("2"));
+
+    assert_eq!(double.get(), 4);
+    let snapshot = devtools::snapshot();
+    assert_eq!(
+        snapshot.node(count_id).unwrap().subscribers,
+        vec![double_id]
+    );
+    assert_eq!(
+        snapshot.node(double_id).unwrap().value.as_deref(),
+        Some("4")
+    );
+}
+
+#[cfg(feature = "effects")]
+#[tokio::test]
+async fn effect_runs_are_reported() {
+    use imports::*;
+
+    _ = Executor::init_tokio();
+    let owner = Owner::new();
+    owner.set();
+
+    task::LocalSet::new()
+        .run_until(async {
+            let events = devtools::subscribe();
+            let a = RwSignal::new(0);
+            let a_id = devtools::node_id(&a);
+
+            Effect::new(move || {
+                a.track();
+            });
+            Executor::tick().await;
+
+            let effect = devtools::snapshot()
+                .nodes
+                .into_iter()
+                .find(|node| {
+                    node.kind == NodeKind::Effect && node.sources == [a_id]
+                })
+                .unwrap();
+            let runs = |events: &std::sync::mpsc::Receiver<DevtoolsEvent>| {
+                events
+                    .try_iter()
+                    .filter(|event| {
+                        *event == DevtoolsEvent::EffectRun { id: effect.id }
+                    })
+                    .count()
+            };
+            assert_eq!(runs(&events), 1);
+
+            a.set(1);
+            Executor::tick().await;
+            assert_eq!(runs(&events), 1);
+        })
+        .await
+}
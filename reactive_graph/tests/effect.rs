@@ -2,7 +2,7 @@
 pub mod imports {
     pub use any_spawner::Executor;
     pub use reactive_graph::{
-        effect::{Effect, RenderEffect},
+        effect::{Effect, Priority, RenderEffect},
         owner::Owner,
         prelude::*,
         signal::RwSignal,
@@ -196,3 +196,211 @@ async fn recursive_effect_runs_recursively() {
         })
         .await;
 }
+
+#[cfg(feature = "effects")]
+#[tokio::test]
+async fn effects_run_in_priority_order() {
+    use imports::*;
+
+    _ = Executor::init_tokio();
+    let owner = Owner::new();
+    owner.set();
+    task::LocalSet::new()
+        .run_until(async {
+            let a = RwSignal::new(0);
+            let log = Arc::new(RwLock::new(Vec::new()));
+
+            // created in the opposite order to their priority
+            for (label, priority) in [
+                ("idle", Priority::Idle),
+                ("normal", Priority::Normal),
+                ("user-blocking", Priority::UserBlocking),
+            ] {
+                let effect = Effect::new({
+                    let log = Arc::clone(&log);
+                    move || {
+                        a.track();
+                        log.write().unwrap().push(label);
+                    }
+                })
+                .with_priority(priority);
+                assert_eq!(effect.priority(), priority);
+            }
+
+            for _ in 0..5 {
+                Executor::tick().await;
+            }
+            assert_eq!(
+                mem::take(&mut *log.write().unwrap()),
+                ["user-blocking", "normal", "idle"]
+            );
+
+            a.set(1);
+            for _ in 0..5 {
+                Executor::tick().await;
+            }
+            assert_eq!(
+                mem::take(&mut *log.write().unwrap()),
+                ["user-blocking", "normal", "idle"]
+            );
+        })
+        .await;
+}
+
+#[cfg(feature = "effects")]
+#[tokio::test]
+async fn disposed_effects_do_not_block_lower_priorities() {
+    use imports::*;
+
+    _ = Executor::init_tokio();
+    let owner = Owner::new();
+    owner.set();
+    task::LocalSet::new()
+        .run_until(async {
+            let a = RwSignal::new(0);
+            let log = Arc::new(RwLock::new(Vec::new()));
+
+            let child = Owner::new();
+            child.with(|| {
+                Effect::new({
+                    let log = Arc::clone(&log);
+                    move || {
+                        log.write().unwrap().push(("user-blocking", a.get()))
+                    }
+                })
+                .with_priority(Priority::UserBlocking);
+            });
+            Effect::new({
+                let log = Arc::clone(&log);
+                move || log.write().unwrap().push(("normal", a.get()))
+            });
+
+            for _ in 0..5 {
+                Executor::tick().await;
+            }
+            assert_eq!(
+                mem::take(&mut *log.write().unwrap()),
+                [("user-blocking", 0), ("normal", 0)]
+            );
+
+            // the user-blocking effect is notified, but disposed before it can run
+            a.set(1);
+            child.cleanup();
+            for _ in 0..5 {
+                Executor::tick().await;
+            }
+            assert_eq!(mem::take(&mut *log.write().unwrap()), [("normal", 1)]);
+
+            // the same applies to a notification that was deferred by a batch
+            let child = Owner::new();
+            child.with(|| {
+                Effect::new({
+                    let log = Arc::clone(&log);
+                    move || {
+                        log.write().unwrap().push(("user-blocking", a.get()))
+                    }
+                })
+                .with_priority(Priority::UserBlocking);
+            });
+            for _ in 0..5 {
+                Executor::tick().await;
+            }
+            mem::take(&mut *log.write().unwrap());
+            reactive_graph::effect::batch(|| {
+                a.set(2);
+                child.cleanup();
+            });
+            for _ in 0..5 {
+                Executor::tick().await;
+            }
+            assert_eq!(mem::take(&mut *log.write().unwrap()), [("normal", 2)]);
+        })
+        .await;
+}
+
+#[cfg(feature = "effects")]
+#[tokio::test]
+async fn lower_priority_effects_are_not_starved() {
+    use imports::*;
+
+    _ = Executor::init_tokio();
+    let owner = Owner::new();
+    owner.set();
+    task::LocalSet::new()
+        .run_until(async {
+            let a = RwSignal::new(0);
+            let high_runs = Arc::new(RwLock::new(0));
+            let low_seen = Arc::new(RwLock::new(Vec::new()));
+
+            let _render_effect = RenderEffect::new({
+                let high_runs = Arc::clone(&high_runs);
+                move |_| {
+                    a.track();
+                    *high_runs.write().unwrap() += 1;
+                }
+            });
+            Effect::new({
+                let low_seen = Arc::clone(&low_seen);
+                move || low_seen.write().unwrap().push(a.get())
+            })
+            .with_priority(Priority::Idle);
+
+            // keep notifying the higher-priority effect while the idle effect is waiting
+            for i in 1..=10 {
+                a.set(i);
+                Executor::tick().await;
+            }
+            for _ in 0..5 {
+                Executor::tick().await;
+            }
+            assert_eq!(*high_runs.read().unwrap(), 11);
+            assert_eq!(low_seen.read().unwrap().last(), Some(&10));
+        })
+        .await;
+}
+
+#[cfg(feature = "effects")]
+#[tokio::test]
+async fn sync_effects_notified_from_another_thread() {
+    use imports::*;
+
+    _ = Executor::init_tokio();
+    let owner = Owner::new();
+    owner.set();
+    task::LocalSet::new()
+        .run_until(async {
+            // the arena can be sandboxed to a single thread, so use a reference-counted signal
+            let a = reactive_graph::signal::ArcRwSignal::new(0);
+            let b = RwSignal::new(0);
+            let seen_a = Arc::new(RwLock::new(Vec::new()));
+            let seen_b = Arc::new(RwLock::new(Vec::new()));
+
+            Effect::new_sync({
+                let seen_a = Arc::clone(&seen_a);
+                let a = a.clone();
+                move |_| seen_a.write().unwrap().push(a.get())
+            })
+            .with_priority(Priority::UserBlocking);
+            Effect::new({
+                let seen_b = Arc::clone(&seen_b);
+                move || seen_b.write().unwrap().push(b.get())
+            })
+            .with_priority(Priority::Idle);
+
+            for _ in 0..5 {
+                Executor::tick().await;
+            }
+
+            // the user-blocking effect is notified from another thread, which does not hold up
+            // the idle effect on this thread
+            std::thread::spawn(move || a.set(1)).join().unwrap();
+            b.set(1);
+            for _ in 0..20 {
+                Executor::tick().await;
+                task::yield_now().await;
+            }
+            assert_eq!(*seen_a.read().unwrap(), [0, 1]);
+            assert_eq!(*seen_b.read().unwrap(), [0, 1]);
+        })
+        .await;
+}
@@ -0,0 +1,125 @@
+use reactive_graph::{
+    history::{History, HistoryOptions},
+    owner::Owner,
+    prelude::*,
+    signal::{ArcRwSignal, RwSignal},
+};
+use std::time::Duration;
+
+#[test]
+fn undo_and_redo_changes() {
+    let owner = Owner::new();
+    owner.set();
+
+    let count = RwSignal::new(0);
+    let history = History::new(count);
+    let (can_undo, can_redo) = (history.can_undo(), history.can_redo());
+    assert!(!can_undo.get_untracked());
+    assert!(!can_redo.get_untracked());
+
+    count.set(1);
+    count.set(2);
+    assert!(can_undo.get_untracked());
+
+    assert!(history.undo());
+    assert_eq!(count.get_untracked(), 1);
+    assert!(can_redo.get_untracked());
+    assert!(history.undo());
+    assert_eq!(count.get_untracked(), 0);
+    assert!(!history.undo());
+    assert!(!can_undo.get_untracked());
+
+    assert!(history.redo());
+    assert_eq!(count.get_untracked(), 1);
+
+    // a new change discards the redo steps
+    count.set(5);
+    assert!(!can_redo.get_untracked());
+    assert!(!history.redo());
+    assert!(history.undo());
+    assert_eq!(count.get_untracked(), 1);
+}
+
+#[test]
+fn history_respects_capacity() {
+    let owner = Owner::new();
+    owner.set();
+
+    let count = ArcRwSignal::new(0);
+    let history = History::new_with_options(
+        count.clone(),
+        HistoryOptions {
+            capacity: 2,
+            ..Default::default()
+        },
+    );
+    for n in 1..=5 {
+        count.set(n);
+    }
+    assert!(history.undo());
+    assert!(history.undo());
+    assert!(!history.undo());
+    assert_eq!(count.get_untracked(), 3);
+}
+
+#[test]
+fn changes_within_window_are_coalesced() {
+    let owner = Owner::new();
+    owner.set();
+
+    let text = ArcRwSignal::new(String::new());
+    let history = History::new_with_options(
+        text.clone(),
+        HistoryOptions {
+            coalesce: Some(Duration::from_secs(60)),
+            ..Default::default()
+        },
+    );
+    text.set("a".to_string());
+    text.set("ab".to_string());
+    text.set("abc".to_string());
+    history.checkpoint();
+    text.set("abcd".to_string());
+
+    assert!(history.undo());
+    assert_eq!(text.get_untracked(), "abc");
+    assert!(history.undo());
+    assert_eq!(text.get_untracked(), "");
+    assert!(!history.undo());
+}
+
+#[test]
+fn group_records_one_step() {
+    let owner = Owner::new();
+    owner.set();
+
+    let count = RwSignal::new(0);
+    let history = History::new(count);
+    count.set(1);
+    history.group(|| {
+        count.set(2);
+        count.set(3);
+        history.group(|| count.set(4));
+    });
+    count.set(5);
+
+    assert!(history.undo());
+    assert_eq!(count.get_untracked(), 4);
+    assert!(history.undo());
+    assert_eq!(count.get_untracked(), 1);
+    assert!(history.undo());
+    assert_eq!(count.get_untracked(), 0);
+}
+
+#[test]
+fn history_stops_recording_when_dropped() {
+    let owner = Owner::new();
+    owner.set();
+
+    let count = RwSignal::new(0);
+    let history = History::new(count);
+    count.set(1);
+    drop(history);
+    count.set(2);
+    assert_eq!(count.get_untracked(), 2);
+}
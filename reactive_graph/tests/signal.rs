@@ -2,8 +2,8 @@ use reactive_graph::{
     owner::Owner,
     signal::{arc_signal, signal, ArcRwSignal, RwSignal},
     traits::{
-        Dispose, Get, GetUntracked, IntoInner, Read, Set, Update,
-        UpdateUntracked, With, WithUntracked, Write,
+        DefinedAt, Dispose, Get, GetUntracked, IntoInner, Read, Set, Update,
+        UpdateUntracked, With, WithName, WithUntracked, Write,
     },
 };
 
@@ -140,3 +140,31 @@ fn into_inner_non_arc_signal() {
     b.dispose();
     assert_eq!(a.into_inner(), Some(2));
 }
+
+#[cfg(debug_assertions)]
+#[test]
+fn debug_name_is_shared_and_shown_after_dispose() {
+    use reactive_graph::graph::ToAnySource;
+    use std::panic::{self, AssertUnwindSafe};
+
+    let owner = Owner::new();
+    owner.set();
+
+    let (total, set_total) = arc_signal(0).with_name("cart.total");
+    assert_eq!(total.debug_name().as_deref(), Some("cart.total"));
+    assert_eq!(set_total.debug_name().as_deref(), Some("cart.total"));
+    assert_eq!(
+        total.to_any_source().debug_name().as_deref(),
+        Some("cart.total")
+    );
+
+    let count = RwSignal::new(0).with_name("count");
+    assert_eq!(count.debug_name().as_deref(), Some("count"));
+    count.dispose();
+    let panic = panic::catch_unwind(AssertUnwindSafe(|| count.get()))
+        .expect_err("reading a disposed signal should panic");
+    let message = panic
+        .downcast_ref::<String>()
+        .expect("panic message should be a String");
+    assert!(message.contains(r#"reactive value "count" which was defined"#));
+}
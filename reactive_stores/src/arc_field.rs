@@ -1,4 +1,5 @@
 use crate::{
+    changes::ChangeGuard,
     path::{StorePath, StorePathSegment},
     ArcStore, AtIndex, AtKeyed, DerefedField, KeyMap, KeyedSubfield, Store,
     StoreField, StoreFieldTrigger, Subfield,
@@ -372,7 +373,7 @@ impl<T> Write for ArcField<T> {
     type Value = T;
 
     fn try_write(&self) -> Option<impl UntrackableGuard<Target = Self::Value>> {
-        (self.write)()
+        (self.write)().map(|writer| ChangeGuard::new(self, writer))
     }
 
     fn try_write_untracked(
@@ -0,0 +1,709 @@
+use crate::{path::StorePath, StoreField};
+use or_poisoned::OrPoisoned;
+use reactive_graph::traits::UntrackableGuard;
+use std::{
+    fmt::Debug,
+    ops::{Deref, DerefMut},
+    sync::{Arc, RwLock},
+};
+
+pub(crate) type ChangeFn = Arc<dyn Fn(&[StorePath]) + Send + Sync>;
+
+/// The functions that are notified of changes to the fields of a store.
+#[derive(Clone, Default)]
+pub(crate) struct StoreChanges(Arc<RwLock<Subscribers>>);
+
+#[derive(Default)]
+struct Subscribers {
+    next_id: usize,
+    subscribers: Vec<(usize, ChangeFn)>,
+}
+
+impl Debug for StoreChanges {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StoreChanges")
+            .field(
+                "subscribers",
+                &self.0.read().or_poisoned().subscribers.len(),
+            )
+            .finish()
+    }
+}
+
+impl StoreChanges {
+    /// Adds a function that is called with the paths of the fields that have changed, and
+    /// returns an ID that can be used to remove it.
+    pub fn subscribe(&self, fun: ChangeFn) -> usize {
+        let mut lock = self.0.write().or_poisoned();
+        let id = lock.next_id;
+        lock.next_id += 1;
+        lock.subscribers.push((id, fun));
+        id
+    }
+
+    pub fn unsubscribe(&self, id: usize) {
+        self.0
+            .write()
+            .or_poisoned()
+            .subscribers
+            .retain(|(sub_id, _)| *sub_id != id);
+    }
+
+    /// Notifies the subscribers that the fields at these paths have changed.
+    ///
+    /// If both a field and one of its children have changed, only the field is included, because
+    /// its new value contains the new value of the child.
+    pub fn notify(&self, paths: &[StorePath]) {
+        let subscribers = self
+            .0
+            .read()
+            .or_poisoned()
+            .subscribers
+            .iter()
+            .map(|(_, fun)| Arc::clone(fun))
+            .collect::<Vec<_>>();
+        if subscribers.is_empty() || paths.is_empty() {
+            return;
+        }
+
+        let mut changed = Vec::with_capacity(paths.len());
+        for path in paths {
+            let has_parent = paths
+                .iter()
+                .any(|other| other != path && path.starts_with(other));
+            if !has_parent && !changed.contains(path) {
+                changed.push(path.clone());
+            }
+        }
+        for fun in subscribers {
+            fun(&changed);
+        }
+    }
+}
+
+/// A write guard that notifies the change subscribers of the store after it has been dropped,
+/// and the value has been updated.
+pub(crate) struct ChangeGuard<G> {
+    changes: Option<(StoreChanges, StorePath)>,
+    guard: Option<G>,
+}
+
+impl<G> ChangeGuard<G> {
+    /// Wraps the write guard for the given field.
+    pub(crate) fn new(field: &impl StoreField, guard: G) -> Self {
+        let path = field.path().into_iter().collect::<StorePath>();
+        let changes = field.get_trigger(path.clone()).changes;
+        Self {
+            changes: Some((changes, path)),
+            guard: Some(guard),
+        }
+    }
+}
+
+impl<G: Debug> Debug for ChangeGuard<G> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChangeGuard")
+            .field("guard", &self.guard)
+            .finish()
+    }
+}
+
+impl<G: Deref> Deref for ChangeGuard<G> {
+    type Target = G::Target;
+
+    fn deref(&self) -> &Self::Target {
+        self.guard
+            .as_ref()
+            .expect("should be Some(_) until dropped")
+            .deref()
+    }
+}
+
+impl<G: DerefMut> DerefMut for ChangeGuard<G> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.guard
+            .as_mut()
+            .expect("should be Some(_) until dropped")
+            .deref_mut()
+    }
+}
+
+impl<G: UntrackableGuard> UntrackableGuard for ChangeGuard<G> {
+    fn untrack(&mut self) {
+        self.changes.take();
+        if let Some(guard) = self.guard.as_mut() {
+            guard.untrack();
+        }
+    }
+}
+
+impl<G> Drop for ChangeGuard<G> {
+    fn drop(&mut self) {
+        // release the lock on the value before the subscribers read it
+        drop(self.guard.take());
+        if let Some((changes, path)) = self.changes.take() {
+            changes.notify(&[path]);
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+pub use self::serde::StoreChange;
+#[cfg(feature = "serde")]
+pub(crate) use self::serde::{locate, subscriber, JsonKey};
+
+#[cfg(feature = "serde")]
+mod serde {
+    use super::ChangeFn;
+    use crate::{
+        path::{StorePath, StorePathSegment},
+        KeyMap,
+    };
+    use or_poisoned::OrPoisoned;
+    use serde::{ser, Serialize, Serializer};
+    use serde_json::{Error, Value};
+    use std::{
+        fmt::Display,
+        sync::{Arc, RwLock, Weak},
+    };
+
+    /// A change to one of the fields of a store.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct StoreChange {
+        /// The path of the field that changed.
+        pub path: StorePath,
+        /// A human-readable path to the field, like `todos[2].label`. This is empty if the whole
+        /// store changed.
+        pub field: String,
+        /// The new value of the field.
+        pub value: Value,
+    }
+
+    /// Creates the function that describes each change to the given value, and passes it to
+    /// `fun`.
+    pub(crate) fn subscriber<T>(
+        value: &Arc<RwLock<T>>,
+        keys: KeyMap,
+        fun: impl Fn(&StoreChange) + Send + Sync + 'static,
+    ) -> ChangeFn
+    where
+        T: Serialize + Send + Sync + 'static,
+    {
+        // the subscriber is owned by the store, so it should not keep the value alive
+        let value = Arc::downgrade(value);
+        Arc::new(move |paths| {
+            let changes = describe(&value, &keys, paths);
+            for change in changes {
+                fun(&change);
+            }
+        })
+    }
+
+    fn describe<T>(
+        value: &Weak<RwLock<T>>,
+        keys: &KeyMap,
+        paths: &[StorePath],
+    ) -> Vec<StoreChange>
+    where
+        T: Serialize,
+    {
+        let Some(value) = value.upgrade() else {
+            return Vec::new();
+        };
+        let value = value.read().or_poisoned();
+        paths
+            .iter()
+            .filter_map(|path| {
+                let segments = path.clone().into_iter().collect::<Vec<_>>();
+                let mut field = String::new();
+                let value = select(
+                    &*value,
+                    &segments,
+                    StorePath::default(),
+                    keys,
+                    &mut field,
+                    &mut Vec::new(),
+                )
+                .ok()
+                .flatten()?;
+                Some(StoreChange {
+                    path: path.clone(),
+                    field,
+                    value,
+                })
+            })
+            .collect()
+    }
+
+    /// A step from a JSON value to one of its children.
+    #[derive(Debug, Clone, PartialEq)]
+    pub(crate) enum JsonKey {
+        Field(&'static str),
+        Index(usize),
+    }
+
+    impl JsonKey {
+        /// Returns the child of `value` with this key.
+        pub fn child_mut<'a>(
+            &self,
+            value: &'a mut Value,
+        ) -> Option<&'a mut Value> {
+            match (self, value) {
+                (JsonKey::Field(key), Value::Object(map)) => map.get_mut(*key),
+                (JsonKey::Index(idx), Value::Array(items)) => {
+                    items.get_mut(*idx)
+                }
+                _ => None,
+            }
+        }
+    }
+
+    /// Serializes the part of `value` at `path`, returning it along with the keys that lead to it
+    /// in the JSON representation of `value`.
+    pub(crate) fn locate<T>(
+        value: &T,
+        path: &StorePath,
+        keys: &KeyMap,
+    ) -> Option<(Vec<JsonKey>, Value)>
+    where
+        T: Serialize,
+    {
+        let segments = path.clone().into_iter().collect::<Vec<_>>();
+        let mut location = Vec::new();
+        let value = select(
+            value,
+            &segments,
+            StorePath::default(),
+            keys,
+            &mut String::new(),
+            &mut location,
+        )
+        .ok()
+        .flatten()?;
+        Some((location, value))
+    }
+
+    /// Serializes the part of `value` at the path made up of `segments`, adding its name to
+    /// `field` and the keys that lead to it to `location`.
+    fn select<T>(
+        value: &T,
+        segments: &[StorePathSegment],
+        path: StorePath,
+        keys: &KeyMap,
+        field: &mut String,
+        location: &mut Vec<JsonKey>,
+    ) -> Result<Option<Value>, Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        if segments.is_empty() {
+            return serde_json::to_value(value).map(Some);
+        }
+        let selector = Select {
+            segments,
+            path,
+            keys,
+            field,
+            location,
+        };
+        match value.serialize(selector)? {
+            Selected::Value(value) => Ok(Some(value)),
+            // the path cannot be followed any further, so the whole value is used
+            Selected::Whole => serde_json::to_value(value).map(Some),
+            Selected::NotFound => Ok(None),
+        }
+    }
+
+    enum Selected {
+        Value(Value),
+        Whole,
+        NotFound,
+    }
+
+    /// A serializer that walks a value to find the part at some path, following the same indices
+    /// as the fields generated by `#[derive(Store)]`.
+    struct Select<'a> {
+        segments: &'a [StorePathSegment],
+        path: StorePath,
+        keys: &'a KeyMap,
+        field: &'a mut String,
+        location: &'a mut Vec<JsonKey>,
+    }
+
+    impl<'a> Select<'a> {
+        fn child<T>(
+            self,
+            value: &T,
+            name: impl Display,
+            key: Option<JsonKey>,
+        ) -> Result<Selected, Error>
+        where
+            T: Serialize + ?Sized,
+        {
+            let Select {
+                segments,
+                mut path,
+                keys,
+                field,
+                location,
+            } = self;
+            if !field.is_empty() && !name.to_string().starts_with('[') {
+                field.push('.');
+            }
+            field.push_str(&name.to_string());
+            location.extend(key);
+            path.push(segments[0]);
+            select(value, &segments[1..], path, keys, field, location).map(
+                |value| {
+                    value.map(Selected::Value).unwrap_or(Selected::NotFound)
+                },
+            )
+        }
+
+        fn fields(self) -> SelectFields<'a> {
+            // fields that are skipped by serde are not serialized at all, so the index of the
+            // field is mapped to its position among the serialized fields
+            let target =
+                self.keys.serialized_position(&self.path, self.segments[0]);
+            SelectFields {
+                idx: 0,
+                target: target.unwrap_or(usize::MAX),
+                select: Some(self),
+                selected: Selected::NotFound,
+            }
+        }
+
+        fn elements(self) -> SelectFields<'a> {
+            // keyed fields use their own path segments for each item, rather than its index
+            let segment = self.segments[0];
+            let target = self
+                .keys
+                .index_of(&self.path, segment)
+                .unwrap_or(Some(segment.0));
+            SelectFields {
+                idx: 0,
+                target: target.unwrap_or(usize::MAX),
+                select: Some(self),
+                selected: Selected::NotFound,
+            }
+        }
+    }
+
+    struct SelectFields<'a> {
+        idx: usize,
+        target: usize,
+        select: Option<Select<'a>>,
+        selected: Selected,
+    }
+
+    impl SelectFields<'_> {
+        fn field<T>(
+            &mut self,
+            value: &T,
+            name: impl Display,
+            key: JsonKey,
+        ) -> Result<(), Error>
+        where
+            T: Serialize + ?Sized,
+        {
+            if self.idx == self.target {
+                if let Some(select) = self.select.take() {
+                    self.selected = select.child(value, name, Some(key))?;
+                }
+            }
+            self.idx += 1;
+            Ok(())
+        }
+    }
+
+    macro_rules! not_found {
+        ($($method:ident($($ty:ty),*)),* $(,)?) => {
+            $(fn $method(self, $(_: $ty),*) -> Result<Selected, Error> {
+                Ok(Selected::NotFound)
+            })*
+        };
+    }
+
+    impl<'a> Serializer for Select<'a> {
+        type Ok = Selected;
+        type Error = Error;
+        type SerializeSeq = SelectFields<'a>;
+        type SerializeTuple = SelectFields<'a>;
+        type SerializeTupleStruct = SelectFields<'a>;
+        type SerializeTupleVariant = Whole;
+        type SerializeMap = Whole;
+        type SerializeStruct = SelectFields<'a>;
+        type SerializeStructVariant = Whole;
+
+        not_found! {
+            serialize_bool(bool),
+            serialize_i8(i8),
+            serialize_i16(i16),
+            serialize_i32(i32),
+            serialize_i64(i64),
+            serialize_i128(i128),
+            serialize_u8(u8),
+            serialize_u16(u16),
+            serialize_u32(u32),
+            serialize_u64(u64),
+            serialize_u128(u128),
+            serialize_f32(f32),
+            serialize_f64(f64),
+            serialize_char(char),
+            serialize_str(&str),
+            serialize_bytes(&[u8]),
+            serialize_none(),
+            serialize_unit(),
+            serialize_unit_struct(&'static str),
+            serialize_unit_variant(&'static str, u32, &'static str),
+        }
+
+        fn serialize_some<T>(self, value: &T) -> Result<Selected, Error>
+        where
+            T: Serialize + ?Sized,
+        {
+            // the inner value of an `Option` is its first field
+            if self.segments[0].0 != 0 {
+                return Ok(Selected::NotFound);
+            }
+            let Select {
+                segments,
+                mut path,
+                keys,
+                field,
+                location,
+            } = self;
+            path.push(segments[0]);
+            select(value, &segments[1..], path, keys, field, location).map(
+                |value| {
+                    value.map(Selected::Value).unwrap_or(Selected::NotFound)
+                },
+            )
+        }
+
+        fn serialize_newtype_struct<T>(
+            self,
+            _name: &'static str,
+            value: &T,
+        ) -> Result<Selected, Error>
+        where
+            T: Serialize + ?Sized,
+        {
+            if self.segments[0].0 != 0 {
+                return Ok(Selected::NotFound);
+            }
+            // newtypes are serialized as their inner value
+            self.child(value, 0, None)
+        }
+
+        fn serialize_newtype_variant<T>(
+            self,
+            _name: &'static str,
+            _variant_index: u32,
+            _variant: &'static str,
+            _value: &T,
+        ) -> Result<Selected, Error>
+        where
+            T: Serialize + ?Sized,
+        {
+            // all the fields of an enum variant share the same path
+            Ok(Selected::Whole)
+        }
+
+        fn serialize_seq(
+            self,
+            _len: Option<usize>,
+        ) -> Result<SelectFields<'a>, Error> {
+            Ok(self.elements())
+        }
+
+        fn serialize_tuple(
+            self,
+            _len: usize,
+        ) -> Result<SelectFields<'a>, Error> {
+            Ok(self.fields())
+        }
+
+        fn serialize_tuple_struct(
+            self,
+            _name: &'static str,
+            _len: usize,
+        ) -> Result<SelectFields<'a>, Error> {
+            Ok(self.fields())
+        }
+
+        fn serialize_tuple_variant(
+            self,
+            _name: &'static str,
+            _variant_index: u32,
+            _variant: &'static str,
+            _len: usize,
+        ) -> Result<Self::SerializeTupleVariant, Error> {
+            Ok(Whole)
+        }
+
+        fn serialize_map(
+            self,
+            _len: Option<usize>,
+        ) -> Result<Self::SerializeMap, Error> {
+            Ok(Whole)
+        }
+
+        fn serialize_struct(
+            self,
+            _name: &'static str,
+            _len: usize,
+        ) -> Result<SelectFields<'a>, Error> {
+            Ok(self.fields())
+        }
+
+        fn serialize_struct_variant(
+            self,
+            _name: &'static str,
+            _variant_index: u32,
+            _variant: &'static str,
+            _len: usize,
+        ) -> Result<Self::SerializeStructVariant, Error> {
+            Ok(Whole)
+        }
+    }
+
+    /// Selects the whole of a compound value whose children do not have their own paths, like a
+    /// map or an enum variant.
+    struct Whole;
+
+    impl ser::SerializeMap for Whole {
+        type Ok = Selected;
+        type Error = Error;
+
+        fn serialize_key<T>(&mut self, _key: &T) -> Result<(), Error>
+        where
+            T: Serialize + ?Sized,
+        {
+            Ok(())
+        }
+
+        fn serialize_value<T>(&mut self, _value: &T) -> Result<(), Error>
+        where
+            T: Serialize + ?Sized,
+        {
+            Ok(())
+        }
+
+        fn end(self) -> Result<Selected, Error> {
+            Ok(Selected::Whole)
+        }
+    }
+
+    impl ser::SerializeTupleVariant for Whole {
+        type Ok = Selected;
+        type Error = Error;
+
+        fn serialize_field<T>(&mut self, _value: &T) -> Result<(), Error>
+        where
+            T: Serialize + ?Sized,
+        {
+            Ok(())
+        }
+
+        fn end(self) -> Result<Selected, Error> {
+            Ok(Selected::Whole)
+        }
+    }
+
+    impl ser::SerializeStructVariant for Whole {
+        type Ok = Selected;
+        type Error = Error;
+
+        fn serialize_field<T>(
+            &mut self,
+            _key: &'static str,
+            _value: &T,
+        ) -> Result<(), Error>
+        where
+            T: Serialize + ?Sized,
+        {
+            Ok(())
+        }
+
+        fn end(self) -> Result<Selected, Error> {
+            Ok(Selected::Whole)
+        }
+    }
+
+    impl ser::SerializeSeq for SelectFields<'_> {
+        type Ok = Selected;
+        type Error = Error;
+
+        fn serialize_element<T>(&mut self, value: &T) -> Result<(), Error>
+        where
+            T: Serialize + ?Sized,
+        {
+            let idx = self.idx;
+            self.field(value, format_args!("[{idx}]"), JsonKey::Index(idx))
+        }
+
+        fn end(self) -> Result<Selected, Error> {
+            Ok(self.selected)
+        }
+    }
+
+    impl ser::SerializeTuple for SelectFields<'_> {
+        type Ok = Selected;
+        type Error = Error;
+
+        fn serialize_element<T>(&mut self, value: &T) -> Result<(), Error>
+        where
+            T: Serialize + ?Sized,
+        {
+            let idx = self.idx;
+            self.field(value, idx, JsonKey::Index(idx))
+        }
+
+        fn end(self) -> Result<Selected, Error> {
+            Ok(self.selected)
+        }
+    }
+
+    impl ser::SerializeTupleStruct for SelectFields<'_> {
+        type Ok = Selected;
+        type Error = Error;
+
+        fn serialize_field<T>(&mut self, value: &T) -> Result<(), Error>
+        where
+            T: Serialize + ?Sized,
+        {
+            let idx = self.idx;
+            self.field(value, idx, JsonKey::Index(idx))
+        }
+
+        fn end(self) -> Result<Selected, Error> {
+            Ok(self.selected)
+        }
+    }
+
+    impl ser::SerializeStruct for SelectFields<'_> {
+        type Ok = Selected;
+        type Error = Error;
+
+        fn serialize_field<T>(
+            &mut self,
+            key: &'static str,
+            value: &T,
+        ) -> Result<(), Error>
+        where
+            T: Serialize + ?Sized,
+        {
+            self.field(value, key, JsonKey::Field(key))
+        }
+
+        fn skip_field(&mut self, _key: &'static str) -> Result<(), Error> {
+            self.idx += 1;
+            Ok(())
+        }
+
+        fn end(self) -> Result<Selected, Error> {
+            Ok(self.selected)
+        }
+    }
+}
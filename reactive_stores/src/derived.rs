@@ -0,0 +1,119 @@
+use crate::{path::StorePath, ArcField, StoreField};
+use or_poisoned::OrPoisoned;
+use reactive_graph::{computed::ArcMemo, traits::Get};
+use rustc_hash::FxHashMap;
+use std::{
+    any::Any,
+    cell::Cell,
+    fmt::Debug,
+    mem,
+    sync::{Arc, RwLock},
+};
+
+/// The memos for the derived fields of a store, by the path of the field they derive from and
+/// the name of the derived field.
+#[derive(Clone, Default)]
+pub(crate) struct DerivedFields(Arc<RwLock<FxHashMap<DerivedKey, AnyMemo>>>);
+
+type DerivedKey = (StorePath, &'static str);
+type AnyMemo = Box<dyn Any + Send + Sync>;
+
+impl DerivedFields {
+    /// Returns a guard that clears the derived fields once it has been dropped.
+    pub(crate) fn guard(&self) -> Arc<DerivedFieldsGuard> {
+        Arc::new(DerivedFieldsGuard(self.clone()))
+    }
+}
+
+/// Clears the derived fields of a store when it is dropped.
+///
+/// Each memo holds a handle to the store it derives from, and the store holds the memos, so every
+/// handle to the store holds this guard except for the ones held by the memos. Once the last
+/// handle outside the memos has been dropped, the memos are dropped, which releases the store.
+pub(crate) struct DerivedFieldsGuard(DerivedFields);
+
+impl Drop for DerivedFieldsGuard {
+    fn drop(&mut self) {
+        // the memos are dropped after releasing the lock, because dropping them drops the
+        // handles to the store they hold
+        let memos = mem::take(&mut *(self.0).0.write().or_poisoned());
+        drop(memos);
+    }
+}
+
+thread_local! {
+    static CLONING_WEAK: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Whether handles to a store that are cloned now should not hold its [`DerivedFieldsGuard`].
+pub(crate) fn cloning_weak() -> bool {
+    CLONING_WEAK.with(Cell::get)
+}
+
+/// Clones a field, without the clone holding the [`DerivedFieldsGuard`] of its store.
+fn clone_weak<F: Clone>(field: &F) -> F {
+    struct Reset(bool);
+
+    impl Drop for Reset {
+        fn drop(&mut self) {
+            CLONING_WEAK.with(|weak| weak.set(self.0));
+        }
+    }
+
+    let _reset = Reset(CLONING_WEAK.with(|weak| weak.replace(true)));
+    field.clone()
+}
+
+impl Debug for DerivedFields {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("DerivedFields")
+            .field(&self.0.read().or_poisoned().len())
+            .finish()
+    }
+}
+
+/// Reactively reads the value of a derived field, which is calculated by calling `fun` with the
+/// field it derives from.
+///
+/// The value is memoized, so it is only recalculated when one of the fields that `fun` reads
+/// changes, and is shared by every accessor for the same field of the same store.
+///
+/// This is used by the getters generated by `#[store(derive(name: Type = fun))]`.
+#[track_caller]
+pub fn derived_field<F, T, R>(
+    field: F,
+    name: &'static str,
+    fun: fn(ArcField<T>) -> R,
+) -> R
+where
+    F: StoreField<Value = T> + Clone + Into<ArcField<T>>,
+    T: 'static,
+    R: Clone + PartialEq + Send + Sync + 'static,
+{
+    let path = field.path().into_iter().collect::<StorePath>();
+    let derived = field.get_trigger(path.clone()).derived;
+    let key = (path, name);
+
+    let existing = derived
+        .0
+        .read()
+        .or_poisoned()
+        .get(&key)
+        .and_then(|memo| memo.downcast_ref::<ArcMemo<R>>())
+        .cloned();
+    let memo = existing.unwrap_or_else(|| {
+        // the memo is owned by the store, so it only holds a weak handle to it
+        let field = clone_weak(&field).into();
+        let memo = ArcMemo::new(move |_| fun(field.clone()));
+        derived
+            .0
+            .write()
+            .or_poisoned()
+            .entry(key)
+            .or_insert_with(|| Box::new(memo.clone()))
+            .downcast_ref::<ArcMemo<R>>()
+            .cloned()
+            .unwrap_or(memo)
+    });
+    memo.get()
+}
@@ -1,5 +1,6 @@
 use crate::{
     arc_field::{StoreFieldReader, StoreFieldWriter},
+    changes::ChangeGuard,
     path::{StorePath, StorePathSegment},
     ArcField, ArcStore, AtIndex, AtKeyed, DerefedField, KeyMap, KeyedSubfield,
     Store, StoreField, StoreFieldTrigger, Subfield,
@@ -67,6 +68,19 @@ where
     }
 }
 
+impl<T, S> From<Field<T, S>> for ArcField<T>
+where
+    S: Storage<ArcField<T>>,
+{
+    #[track_caller]
+    fn from(value: Field<T, S>) -> Self {
+        value
+            .inner
+            .try_get_value()
+            .unwrap_or_else(unwrap_signal!(value))
+    }
+}
+
 impl<T, S> From<Store<T, S>> for Field<T, S>
 where
     T: 'static,
@@ -232,7 +246,9 @@ impl<T> Write for Field<T> {
     type Value = T;
 
     fn try_write(&self) -> Option<impl UntrackableGuard<Target = Self::Value>> {
-        self.inner.try_get_value().and_then(|inner| (inner.write)())
+        self.inner.try_get_value().and_then(|inner| {
+            (inner.write)().map(|writer| ChangeGuard::new(self, writer))
+        })
     }
 
     fn try_write_untracked(
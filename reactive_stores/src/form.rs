@@ -0,0 +1,372 @@
+use crate::{path::StorePath, Store, StoreField};
+use futures::future::join_all;
+use reactive_graph::{
+    owner::{Owner, StoredValue},
+    signal::RwSignal,
+    traits::{
+        GetValue, ReadValue, Set, Update, With, WithUntracked, WriteValue,
+    },
+};
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::{
+    fmt::{Debug, Display},
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+};
+
+type Validator = Arc<dyn Fn() -> Option<(StorePath, String)> + Send + Sync>;
+
+type AsyncValidator = Arc<
+    dyn Fn()
+            -> Pin<Box<dyn Future<Output = Option<(StorePath, String)>> + Send>>
+        + Send
+        + Sync,
+>;
+
+type ServerErrorHandler = Arc<dyn Fn(&str) + Send + Sync>;
+
+/// A [`Store`](struct@Store) for the value of a form, which also tracks whether each of its fields is dirty
+/// (has been changed), touched (has been interacted with), or has validation errors.
+///
+/// The state of each field is tracked by its path in the store, so every accessor for a field
+/// shares the same state.
+///
+/// ```rust
+/// # use reactive_stores::{FormStore, Store};
+/// # use reactive_graph::traits::Set;
+/// #[derive(Store)]
+/// struct Signup {
+///     email: String,
+///     password: String,
+/// }
+///
+/// let form = FormStore::new(Signup {
+///     email: String::new(),
+///     password: String::new(),
+/// })
+/// .validator(
+///     |signup| signup.email(),
+///     |email| {
+///         if email.contains('@') {
+///             Ok(())
+///         } else {
+///             Err("Please enter an email address.".to_string())
+///         }
+///     },
+/// );
+///
+/// let email = form.store().email();
+/// form.store().email().set("alice".to_string());
+/// assert!(form.is_dirty(email));
+/// assert!(!form.state().validate());
+/// assert_eq!(form.errors(email), ["Please enter an email address."]);
+/// ```
+pub struct FormStore<T> {
+    store: Store<T>,
+    state: FormState,
+}
+
+impl<T> Clone for FormStore<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for FormStore<T> {}
+
+impl<T> Debug for FormStore<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FormStore")
+            .field("state", &self.state)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> From<FormStore<T>> for FormState {
+    fn from(value: FormStore<T>) -> Self {
+        value.state
+    }
+}
+
+impl<T> FormStore<T>
+where
+    T: Send + Sync + 'static,
+{
+    /// Creates a new form with the given initial value, in which no field is dirty or touched.
+    ///
+    /// Fields become dirty when they are written to. This lasts until the current reactive
+    /// [`Owner`] is cleaned up, or until [`FormState::reset`] is called.
+    pub fn new(value: T) -> Self {
+        let store = Store::new(value);
+        let state = FormState::new();
+
+        let changes = store.get_trigger(Default::default()).changes;
+        let id = changes.subscribe(Arc::new(move |paths: &[StorePath]| {
+            state
+                .dirty
+                .update(|dirty| dirty.extend(paths.iter().cloned()));
+        }));
+        Owner::on_cleanup(move || changes.unsubscribe(id));
+
+        Self { store, state }
+    }
+
+    /// The store that holds the value of the form.
+    pub fn store(&self) -> Store<T> {
+        self.store
+    }
+
+    /// The state of the form as a whole, which does not depend on the type of its value.
+    pub fn state(&self) -> FormState {
+        self.state
+    }
+
+    /// Adds a synchronous validator for the field returned by `field`, which will be run by
+    /// [`FormState::validate`] and [`FormState::validate_async`].
+    pub fn validator<F>(
+        self,
+        field: impl Fn(Store<T>) -> F + Send + Sync + 'static,
+        check: impl Fn(&F::Value) -> Result<(), String> + Send + Sync + 'static,
+    ) -> Self
+    where
+        F: StoreField,
+    {
+        let store = self.store;
+        let validator: Validator = Arc::new(move || {
+            let field = field(store);
+            let path = field.path().into_iter().collect::<StorePath>();
+            let value = field.reader()?;
+            check(&value).err().map(|error| (path, error))
+        });
+        self.state.validators.write_value().push(validator);
+        self
+    }
+
+    /// Adds an asynchronous validator for the field returned by `field`, which will be run by
+    /// [`FormState::validate_async`].
+    ///
+    /// `check` is called with a clone of the value of the field at the time validation begins.
+    pub fn async_validator<F, Fut>(
+        self,
+        field: impl Fn(Store<T>) -> F + Send + Sync + 'static,
+        check: impl Fn(F::Value) -> Fut + Send + Sync + 'static,
+    ) -> Self
+    where
+        F: StoreField,
+        F::Value: Clone + Send + 'static,
+        Fut: Future<Output = Result<(), String>> + Send + 'static,
+    {
+        let store = self.store;
+        let check = Arc::new(check);
+        let validator: AsyncValidator = Arc::new(move || {
+            let field = field(store);
+            let path = field.path().into_iter().collect::<StorePath>();
+            let value = field.reader().map(|value| value.clone());
+            let check = Arc::clone(&check);
+            Box::pin(async move {
+                check(value?).await.err().map(|error| (path, error))
+            })
+        });
+        self.state.async_validators.write_value().push(validator);
+        self
+    }
+
+    /// Sets the function used by [`FormState::handle_server_error`] to turn an error returned
+    /// by the server into errors for individual fields, using [`FormStore::set_error`].
+    pub fn on_server_error(
+        self,
+        fun: impl Fn(Self, &str) + Send + Sync + 'static,
+    ) -> Self {
+        let handler: ServerErrorHandler =
+            Arc::new(move |error| fun(self, error));
+        *self.state.on_server_error.write_value() = Some(handler);
+        self
+    }
+
+    /// Reactively checks whether this field, one of its children, or one of its parents has
+    /// been changed.
+    pub fn is_dirty(&self, field: impl StoreField) -> bool {
+        let path = field.path().into_iter().collect::<StorePath>();
+        self.state.dirty.with(|dirty| {
+            dirty.iter().any(|dirty| {
+                dirty.starts_with(&path) || path.starts_with(dirty)
+            })
+        })
+    }
+
+    /// Reactively checks whether this field has been marked as touched by [`FormStore::touch`].
+    pub fn is_touched(&self, field: impl StoreField) -> bool {
+        let path = field.path().into_iter().collect::<StorePath>();
+        self.state.touched.with(|touched| touched.contains(&path))
+    }
+
+    /// Marks this field as touched. This is usually called when an input loses focus.
+    pub fn touch(&self, field: impl StoreField) {
+        let path = field.path().into_iter().collect::<StorePath>();
+        if !self
+            .state
+            .touched
+            .with_untracked(|touched| touched.contains(&path))
+        {
+            self.state.touched.update(|touched| {
+                touched.insert(path);
+            });
+        }
+    }
+
+    /// Reactively returns the validation errors for this field.
+    pub fn errors(&self, field: impl StoreField) -> Vec<String> {
+        let path = field.path().into_iter().collect::<StorePath>();
+        self.state
+            .errors
+            .with(|errors| errors.get(&path).cloned().unwrap_or_default())
+    }
+
+    /// Adds a validation error to this field.
+    pub fn set_error(&self, field: impl StoreField, error: impl ToString) {
+        let path = field.path().into_iter().collect::<StorePath>();
+        self.state.errors.update(|errors| {
+            errors.entry(path).or_default().push(error.to_string());
+        });
+    }
+}
+
+/// The dirty, touched, and validation state of a [`FormStore`], which can be used without
+/// knowing the type of the form's value.
+#[derive(Clone, Copy)]
+pub struct FormState {
+    dirty: RwSignal<FxHashSet<StorePath>>,
+    touched: RwSignal<FxHashSet<StorePath>>,
+    errors: RwSignal<FxHashMap<StorePath, Vec<String>>>,
+    validating: RwSignal<bool>,
+    /// Incremented whenever validation starts or the form is reset, so that the results of
+    /// asynchronous validation that has been superseded can be discarded.
+    version: StoredValue<usize>,
+    validators: StoredValue<Vec<Validator>>,
+    async_validators: StoredValue<Vec<AsyncValidator>>,
+    on_server_error: StoredValue<Option<ServerErrorHandler>>,
+}
+
+impl Debug for FormState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FormState")
+            .field("dirty", &self.dirty)
+            .field("touched", &self.touched)
+            .field("errors", &self.errors)
+            .field("validating", &self.validating)
+            .finish_non_exhaustive()
+    }
+}
+
+impl FormState {
+    fn new() -> Self {
+        Self {
+            dirty: Default::default(),
+            touched: Default::default(),
+            errors: Default::default(),
+            validating: Default::default(),
+            version: StoredValue::new(0),
+            validators: StoredValue::new(Vec::new()),
+            async_validators: StoredValue::new(Vec::new()),
+            on_server_error: StoredValue::new(None),
+        }
+    }
+
+    /// Reactively checks whether any field of the form has been changed.
+    pub fn is_dirty(&self) -> bool {
+        self.dirty.with(|dirty| !dirty.is_empty())
+    }
+
+    /// Reactively checks whether the form has no validation errors.
+    ///
+    /// This does not run the validators; see [`FormState::validate`].
+    pub fn is_valid(&self) -> bool {
+        self.errors.with(|errors| errors.is_empty())
+    }
+
+    /// Reactively checks whether [`FormState::validate_async`] is running.
+    pub fn is_validating(&self) -> bool {
+        self.validating.with(|validating| *validating)
+    }
+
+    /// Discards the results of any asynchronous validation that is running, and returns the
+    /// version of the validation that supersedes it.
+    fn supersede(&self) -> usize {
+        if self.validating.with_untracked(|validating| *validating) {
+            self.validating.set(false);
+        }
+        let mut version = self.version.write_value();
+        *version += 1;
+        *version
+    }
+
+    /// Runs the synchronous validators, replacing any existing errors, and returns `true` if
+    /// there were no errors.
+    ///
+    /// This discards the results of any asynchronous validation that is still running.
+    pub fn validate(&self) -> bool {
+        self.supersede();
+        let validators = self.validators.read_value().clone();
+        let mut errors = FxHashMap::<StorePath, Vec<String>>::default();
+        for (path, error) in
+            validators.iter().filter_map(|validator| validator())
+        {
+            errors.entry(path).or_default().push(error);
+        }
+        let valid = errors.is_empty();
+        self.errors.set(errors);
+        valid
+    }
+
+    /// Runs the synchronous and asynchronous validators, replacing any existing errors, and
+    /// returns `true` if there were no errors.
+    ///
+    /// The synchronous errors are available immediately, and the asynchronous validators run
+    /// concurrently, with their errors added once all of them have finished. If validation is
+    /// started again or the form is reset before then, the results are discarded and this returns
+    /// `false`.
+    pub async fn validate_async(&self) -> bool {
+        let valid = self.validate();
+        let version = self.version.get_value();
+        let validators = self.async_validators.read_value().clone();
+        if validators.is_empty() {
+            return valid;
+        }
+
+        self.validating.set(true);
+        let results =
+            join_all(validators.iter().map(|validator| validator())).await;
+        if self.version.try_get_value() != Some(version) {
+            return false;
+        }
+        let async_errors = results.into_iter().flatten().collect::<Vec<_>>();
+        let valid = valid && async_errors.is_empty();
+        if !async_errors.is_empty() {
+            self.errors.update(|errors| {
+                for (path, error) in async_errors {
+                    errors.entry(path).or_default().push(error);
+                }
+            });
+        }
+        self.validating.set(false);
+        valid
+    }
+
+    /// Passes an error returned by the server to the handler set with
+    /// [`FormStore::on_server_error`], so that it can be shown with the field it applies to.
+    pub fn handle_server_error(&self, error: &impl Display) {
+        let handler = self.on_server_error.read_value().clone();
+        if let Some(handler) = handler {
+            handler(&error.to_string());
+        }
+    }
+
+    /// Marks every field as clean and untouched, and removes all validation errors.
+    pub fn reset(&self) {
+        self.supersede();
+        self.dirty.set(Default::default());
+        self.touched.set(Default::default());
+        self.errors.set(Default::default());
+    }
+}
@@ -0,0 +1,175 @@
+use crate::{
+    changes::{self, JsonKey},
+    path::StorePath,
+    ArcStore, Patch, PatchField, Store,
+};
+use or_poisoned::OrPoisoned;
+use reactive_graph::{
+    history::{Recording, Restorable},
+    owner::Storage,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::{
+    mem,
+    sync::{Arc, Mutex},
+};
+
+/// The values that the fields of a store had on one side of a change, recorded by a
+/// [`History`](reactive_graph::history::History).
+///
+/// Only the fields that were changed are included, serialized as JSON.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoreDiff {
+    fields: Vec<FieldValue>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct FieldValue {
+    path: StorePath,
+    location: Vec<JsonKey>,
+    value: Value,
+}
+
+impl StoreDiff {
+    /// The paths of the fields that were changed.
+    pub fn paths(&self) -> impl Iterator<Item = &StorePath> {
+        self.fields.iter().map(|field| &field.path)
+    }
+}
+
+/// Returns the part of `value` at `location`.
+fn at_location<'a>(
+    value: &'a mut Value,
+    location: &[JsonKey],
+) -> Option<&'a mut Value> {
+    location
+        .iter()
+        .try_fold(value, |value, key| key.child_mut(value))
+}
+
+/// Records the value of each changed field before the change, using the paths from the change
+/// subscribers of the store.
+///
+/// Undoing or redoing a change writes the recorded values back and [patches](Patch) the store, so
+/// only the fields that were part of the change are notified.
+impl<T> Restorable for ArcStore<T>
+where
+    T: Serialize + DeserializeOwned + PatchField + Send + Sync + 'static,
+{
+    type Change = StoreDiff;
+
+    fn record_changes(
+        &self,
+        on_change: Arc<dyn Fn(Self::Change) + Send + Sync>,
+    ) -> Recording {
+        // the only full copy of the value, which is kept up to date so that the previous value of
+        // each changed field can be recorded
+        let present = Mutex::new(
+            serde_json::to_value(&*self.value.read().or_poisoned())
+                .unwrap_or_default(),
+        );
+        let value = Arc::downgrade(&self.value);
+        let keys = self.keys.clone();
+        let id = self.changes.subscribe(Arc::new(move |paths| {
+            let Some(value) = value.upgrade() else {
+                return;
+            };
+            let mut fields = Vec::new();
+            {
+                let value = value.read().or_poisoned();
+                let mut present = present.lock().or_poisoned();
+                for path in paths {
+                    let (mut path, mut location, mut new) =
+                        match changes::locate(&*value, path, &keys) {
+                            Some((location, new)) => {
+                                (path.clone(), location, new)
+                            }
+                            None => (
+                                StorePath::default(),
+                                Vec::new(),
+                                serde_json::to_value(&*value)
+                                    .unwrap_or_default(),
+                            ),
+                        };
+                    if at_location(&mut present, &location).is_none() {
+                        // the field did not exist before, so the whole value is recorded
+                        path = StorePath::default();
+                        location = Vec::new();
+                        new = serde_json::to_value(&*value).unwrap_or_default();
+                    }
+                    let prev = at_location(&mut present, &location)
+                        .expect("location was checked above");
+                    if *prev != new {
+                        fields.push(FieldValue {
+                            path,
+                            location,
+                            value: mem::replace(prev, new),
+                        });
+                    }
+                }
+            }
+            if !fields.is_empty() {
+                on_change(StoreDiff { fields });
+            }
+        }));
+        let changes = self.changes.clone();
+        Recording::new(move || changes.unsubscribe(id))
+    }
+
+    fn merge(earlier: &mut Self::Change, later: Self::Change) {
+        earlier.fields.extend(later.fields);
+    }
+
+    fn revert(&self, change: Self::Change) -> Self::Change {
+        let Ok(mut value) =
+            serde_json::to_value(&*self.value.read().or_poisoned())
+        else {
+            return change;
+        };
+        // later changes are undone first, so the change that redoes this one is built in reverse
+        let mut fields = Vec::with_capacity(change.fields.len());
+        for field in change.fields.into_iter().rev() {
+            if let Some(current) = at_location(&mut value, &field.location) {
+                fields.push(FieldValue {
+                    value: mem::replace(current, field.value),
+                    ..field
+                });
+            }
+        }
+        if let Ok(value) = serde_json::from_value(value) {
+            self.patch(value);
+        }
+        StoreDiff { fields }
+    }
+}
+
+/// See the implementation for [`ArcStore`].
+impl<T, S> Restorable for Store<T, S>
+where
+    T: Serialize + DeserializeOwned + PatchField + Send + Sync + 'static,
+    S: Storage<ArcStore<T>> + Send + Sync + 'static,
+{
+    type Change = StoreDiff;
+
+    fn record_changes(
+        &self,
+        on_change: Arc<dyn Fn(Self::Change) + Send + Sync>,
+    ) -> Recording {
+        match self.inner.try_get_value() {
+            Some(inner) => inner.record_changes(on_change),
+            None => Recording::new(|| {}),
+        }
+    }
+
+    fn merge(earlier: &mut Self::Change, later: Self::Change) {
+        ArcStore::<T>::merge(earlier, later);
+    }
+
+    fn revert(&self, change: Self::Change) -> Self::Change {
+        match self.inner.try_get_value() {
+            Some(inner) => inner.revert(change),
+            None => change,
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use crate::{
+    changes::ChangeGuard,
     path::{StorePath, StorePathSegment},
     store_field::StoreField,
     KeyMap, StoreFieldTrigger,
@@ -332,10 +333,13 @@ where
 
     fn try_write(&self) -> Option<impl UntrackableGuard<Target = Self::Value>> {
         let guard = self.writer()?;
-        Some(KeyedSubfieldWriteGuard {
-            inner: self.clone(),
-            guard: Some(guard),
-        })
+        Some(ChangeGuard::new(
+            self,
+            KeyedSubfieldWriteGuard {
+                inner: self.clone(),
+                guard: Some(guard),
+            },
+        ))
     }
 
     fn try_write_untracked(
@@ -590,7 +594,7 @@ where
     type Value = T::Output;
 
     fn try_write(&self) -> Option<impl UntrackableGuard<Target = Self::Value>> {
-        self.writer()
+        self.writer().map(|writer| ChangeGuard::new(self, writer))
     }
 
     fn try_write_untracked(
@@ -18,9 +18,22 @@
 //! access to a reactive subfield. This value of this field can be accessed via the ordinary signal
 //! traits (`Get`, `Set`, and so on).
 //!
-//! The [`Patch`](macro@Patch) macro allows you to annotate a struct such that stores and fields have a
-//! [`.patch()`](Patch::patch) method, which allows you to provide an entirely new value, but only
-//! notify fields that have changed.
+//! The [`Patch`](macro@Patch) macro allows you to annotate a struct or enum such that stores and
+//! fields have a [`.patch()`](Patch::patch) method, which allows you to provide an entirely new
+//! value, but only notify fields that have changed. If the new value of an enum is a different
+//! variant, the whole enum is notified; otherwise, the fields of the variant are patched. Keyed
+//! fields (marked with `#[store(key: ...)]`) are patched by key rather than by index, so inserting
+//! or removing an item only notifies that item and the collection.
+//!
+//! With the `serde` feature, `subscribe_changes` can be used to observe the changes to a store,
+//! receiving the path of each field that has been written to or patched, and its new value as
+//! JSON. This is useful for syncing a store with a backend or with local storage.
+//! The same changes are used by `reactive_graph::history::History` to undo and redo changes to
+//! a store, recording the previous values of only the fields that were changed.
+//!
+//! With the `hydration` feature, a store created with `Store::new_hydrated` during server
+//! rendering sends its value to the client, where it is used to create the same store during
+//! hydration.
 //!
 //! Updating a field will notify its parents and children, but not its siblings.
 //!
@@ -98,7 +111,7 @@
 //! # fn main() {
 //! # }
 //! ```
-//! 
+//!
 //! ### Additional field types
 //!
 //! Most of the time, your structs will have fields as in the example above: the struct is comprised
@@ -202,6 +215,38 @@
 //!
 //! assert_eq!(tree.child().unwrap().deref_field().value().get(), 2);
 //! ```
+//! ### Derived fields
+//! A `#[store(derive(name: Type = function))]` attribute adds a computed getter to the generated
+//! trait. The function (or non-capturing closure) receives the field as an [ArcField], and its
+//! result is memoized, so it only reruns when one of the fields it reads changes. The getter can be
+//! called on a [struct@Store], a [Field], or any subfield of that type.
+//! ```rust
+//! use reactive_stores::{ArcField, Store};
+//! use reactive_graph::traits::Read;
+//!
+//! #[derive(Store)]
+//! #[store(derive(full_name: String = full_name))]
+//! struct Name {
+//!     first: String,
+//!     last: String,
+//! }
+//!
+//! fn full_name(name: ArcField<Name>) -> String {
+//!     format!("{} {}", name.clone().first().read(), name.last().read())
+//! }
+//!
+//! let store = Store::new(Name {
+//!     first: "Ada".to_string(),
+//!     last: "Lovelace".to_string(),
+//! });
+//! assert_eq!(store.full_name(), "Ada Lovelace");
+//! ```
+//! ### Forms
+//! A [FormStore] wraps a store holding the value of a form, and tracks whether each field is
+//! dirty, touched, or has validation errors. It can run synchronous and asynchronous validators,
+//! and turn errors from the server into errors for individual fields. Passing its [FormState] to
+//! `<ActionForm/>` in `leptos` blocks submission until the synchronous validators pass.
+//!
 //! ### Implementation Notes
 //!
 //! Every struct field can be understood as an index. For example, given the following definition
@@ -249,7 +294,7 @@ use reactive_graph::{
     },
     traits::{
         DefinedAt, Dispose, IsDisposed, Notify, ReadUntracked, Track,
-        UntrackableGuard, Write,
+        UntrackableGuard, WithName, Write,
     },
 };
 pub use reactive_stores_macro::{Patch, Store};
@@ -265,8 +310,13 @@ use std::{
 };
 
 mod arc_field;
+mod changes;
 mod deref;
+mod derived;
 mod field;
+mod form;
+#[cfg(feature = "serde")]
+mod history;
 mod iter;
 mod keyed;
 mod option;
@@ -276,8 +326,17 @@ mod store_field;
 mod subfield;
 
 pub use arc_field::ArcField;
+#[cfg(feature = "serde")]
+pub use changes::StoreChange;
+use changes::{ChangeGuard, StoreChanges};
 pub use deref::*;
+#[doc(hidden)]
+pub use derived::derived_field;
+use derived::{DerivedFields, DerivedFieldsGuard};
 pub use field::Field;
+pub use form::{FormState, FormStore};
+#[cfg(feature = "serde")]
+pub use history::StoreDiff;
 pub use iter::*;
 pub use keyed::*;
 pub use option::*;
@@ -294,6 +353,8 @@ struct TriggerMap(FxHashMap<StorePath, StoreFieldTrigger>);
 pub struct StoreFieldTrigger {
     pub(crate) this: ArcTrigger,
     pub(crate) children: ArcTrigger,
+    pub(crate) changes: StoreChanges,
+    pub(crate) derived: DerivedFields,
 }
 
 impl StoreFieldTrigger {
@@ -304,11 +365,20 @@ impl StoreFieldTrigger {
 }
 
 impl TriggerMap {
-    fn get_or_insert(&mut self, key: StorePath) -> StoreFieldTrigger {
+    fn get_or_insert(
+        &mut self,
+        key: StorePath,
+        changes: &StoreChanges,
+        derived: &DerivedFields,
+    ) -> StoreFieldTrigger {
         if let Some(trigger) = self.0.get(&key) {
             trigger.clone()
         } else {
-            let new = StoreFieldTrigger::new();
+            let new = StoreFieldTrigger {
+                changes: changes.clone(),
+                derived: derived.clone(),
+                ..Default::default()
+            };
             self.0.insert(key, new.clone());
             new
         }
@@ -337,6 +407,7 @@ where
             from_keys.len(),
             Default::default(),
         );
+        let len = from_keys.len();
         for (idx, key) in from_keys.into_iter().enumerate() {
             let segment = idx.into();
             keys.insert(key, (segment, idx));
@@ -344,7 +415,7 @@ where
 
         Self {
             spare_keys: Vec::new(),
-            current_key: 0,
+            current_key: len,
             keys,
         }
     }
@@ -360,8 +431,9 @@ where
 
     fn next_key(&mut self) -> StorePathSegment {
         self.spare_keys.pop().unwrap_or_else(|| {
+            let key = self.current_key;
             self.current_key += 1;
-            self.current_key.into()
+            key.into()
         })
     }
 
@@ -409,7 +481,37 @@ impl<K> Default for FieldKeys<K> {
 
 /// A map of the keys for a keyed subfield.
 #[derive(Default, Clone)]
-pub struct KeyMap(Arc<RwLock<HashMap<StorePath, Box<dyn Any + Send + Sync>>>>);
+pub struct KeyMap(
+    Arc<RwLock<HashMap<StorePath, Box<dyn AnyFieldKeys>>>>,
+    /// Which fields are serialized, for each struct that skips some of its fields.
+    #[cfg(feature = "serde")]
+    Arc<RwLock<HashMap<StorePath, &'static [bool]>>>,
+);
+
+/// A type-erased set of [`FieldKeys`].
+trait AnyFieldKeys: Send + Sync {
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+
+    /// Returns the index of the item with the given path segment.
+    #[cfg_attr(not(feature = "serde"), allow(dead_code))]
+    fn index_of(&self, segment: StorePathSegment) -> Option<usize>;
+}
+
+impl<K> AnyFieldKeys for FieldKeys<K>
+where
+    K: Send + Sync + 'static,
+{
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn index_of(&self, segment: StorePathSegment) -> Option<usize> {
+        self.keys
+            .values()
+            .find(|(item_segment, _)| *item_segment == segment)
+            .map(|(_, idx)| *idx)
+    }
+}
 
 impl KeyMap {
     fn with_field_keys<K, T>(
@@ -431,17 +533,89 @@ impl KeyMap {
         let mut guard = self.0.write().or_poisoned();
         if guard.contains_key(&path) {
             let entry = guard.get_mut(&path)?;
-            let entry = entry.downcast_mut::<FieldKeys<K>>()?;
+            let entry = entry.as_any_mut().downcast_mut::<FieldKeys<K>>()?;
             Some(fun(entry))
         } else {
             drop(guard);
-            let keys = Box::new(FieldKeys::new(initialize()));
+            let keys: Box<dyn AnyFieldKeys> =
+                Box::new(FieldKeys::new(initialize()));
             let mut guard = self.0.write().or_poisoned();
             let entry = guard.entry(path).or_insert(keys);
-            let entry = entry.downcast_mut::<FieldKeys<K>>()?;
+            let entry = entry.as_any_mut().downcast_mut::<FieldKeys<K>>()?;
             Some(fun(entry))
         }
     }
+
+    /// Returns the index of the item with the given path segment in the keyed field at `path`,
+    /// or `None` if the field is not keyed.
+    #[cfg_attr(not(feature = "serde"), allow(dead_code))]
+    fn index_of(
+        &self,
+        path: &StorePath,
+        segment: StorePathSegment,
+    ) -> Option<Option<usize>> {
+        self.0
+            .read()
+            .or_poisoned()
+            .get(path)
+            .map(|keys| keys.index_of(segment))
+    }
+
+    /// Records which fields of the struct at `path` are serialized, for a struct with fields
+    /// that are skipped by `serde`.
+    ///
+    /// This is called by the code generated by `#[derive(Store)]` and `#[derive(Patch)]`.
+    #[doc(hidden)]
+    #[cfg_attr(not(feature = "serde"), allow(unused_variables))]
+    pub fn set_serialized_fields(
+        &self,
+        path: StorePath,
+        serialized: &'static [bool],
+    ) {
+        #[cfg(feature = "serde")]
+        {
+            let current = self.1.read().or_poisoned().get(&path).copied();
+            if current != Some(serialized) {
+                self.1.write().or_poisoned().insert(path, serialized);
+            }
+        }
+    }
+
+    /// Returns the position of the field at `segment` among the serialized fields of the struct
+    /// at `path`, or `None` if that field is not serialized.
+    #[cfg(feature = "serde")]
+    fn serialized_position(
+        &self,
+        path: &StorePath,
+        segment: StorePathSegment,
+    ) -> Option<usize> {
+        let idx = segment.0;
+        match self.1.read().or_poisoned().get(path) {
+            Some(serialized) => {
+                serialized.get(idx).copied().unwrap_or(true).then(|| {
+                    serialized.iter().take(idx).filter(|field| **field).count()
+                })
+            }
+            None => Some(idx),
+        }
+    }
+}
+
+/// Records which fields of the struct in `field` are serialized, for a struct with fields that
+/// are skipped by `serde`.
+///
+/// This is called by the code generated by `#[derive(Store)]`.
+#[doc(hidden)]
+pub fn set_serialized_fields(
+    field: &impl StoreField,
+    serialized: &'static [bool],
+) {
+    if let Some(keys) = field.keys() {
+        keys.set_serialized_fields(
+            field.path().into_iter().collect(),
+            serialized,
+        );
+    }
 }
 
 /// A reference-counted container for a reactive store.
@@ -456,21 +630,104 @@ pub struct ArcStore<T> {
     pub(crate) value: Arc<RwLock<T>>,
     signals: Arc<RwLock<TriggerMap>>,
     keys: KeyMap,
+    changes: StoreChanges,
+    derived: DerivedFields,
+    /// `None` for the handles held by the derived fields themselves.
+    derived_guard: Option<Arc<DerivedFieldsGuard>>,
 }
 
 impl<T> ArcStore<T> {
     /// Creates a new store from the initial value.
     pub fn new(value: T) -> Self {
+        let derived = DerivedFields::default();
         Self {
             #[cfg(any(debug_assertions, leptos_debuginfo))]
             defined_at: Location::caller(),
             value: Arc::new(RwLock::new(value)),
             signals: Default::default(),
             keys: Default::default(),
+            changes: Default::default(),
+            derived_guard: Some(derived.guard()),
+            derived,
         }
     }
 }
 
+#[cfg(feature = "hydration")]
+impl<T> ArcStore<T>
+where
+    T: serde::Serialize + serde::de::DeserializeOwned + Send + Sync + 'static,
+{
+    /// Creates a new store whose value is sent from the server to the client during hydration.
+    ///
+    /// On the server, the store is created with the value returned by `initial`, and its value is
+    /// serialized once the initial HTML for the page has been rendered, so that any changes made
+    /// while rendering are included. On the client, the store is created with that value, and
+    /// `initial` is only called if there is no serialized value.
+    ///
+    /// Like a resource, the store is matched to its serialized value by the order in which it was
+    /// created, so hydrated stores should be created in the same order on the server and client.
+    #[track_caller]
+    pub fn new_hydrated(initial: impl FnOnce() -> T) -> Self {
+        let shared_context =
+            reactive_graph::owner::Owner::current_shared_context();
+        let Some(shared_context) = shared_context else {
+            return Self::new(initial());
+        };
+        let id = shared_context.next_id();
+
+        if shared_context.is_browser() {
+            let value = shared_context
+                .read_data(&id)
+                .and_then(|value| serde_json::from_str(&value).ok());
+            Self::new(value.unwrap_or_else(initial))
+        } else {
+            let store = Self::new(initial());
+            if shared_context.get_is_hydrating() {
+                let value = store.clone();
+                shared_context.write_async(
+                    id,
+                    Box::pin(async move {
+                        let value = value.value.read().or_poisoned();
+                        serde_json::to_string(&*value)
+                            .expect("couldn't serialize the value of a store")
+                    }),
+                );
+            }
+            store
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T> ArcStore<T>
+where
+    T: serde::Serialize + Send + Sync + 'static,
+{
+    /// Calls `fun` with each change to the fields of this store, including the path of the field
+    /// and its new value, serialized as JSON.
+    ///
+    /// Changes are delivered when a field is written to, and when the store or one of its fields
+    /// is [patched](Patch), in which case only the fields that were actually changed are
+    /// included. If both a field and one of its children have changed, only the parent field is
+    /// included. Untracked writes are not included.
+    ///
+    /// The subscription lasts until the current reactive [`Owner`](reactive_graph::owner::Owner)
+    /// is cleaned up, or for as long as the store exists if there is no owner.
+    pub fn subscribe_changes(
+        &self,
+        fun: impl Fn(&StoreChange) + Send + Sync + 'static,
+    ) {
+        let subscriber =
+            changes::subscriber(&self.value, self.keys.clone(), fun);
+        let id = self.changes.subscribe(subscriber);
+        let changes = self.changes.clone();
+        reactive_graph::owner::Owner::on_cleanup(move || {
+            changes.unsubscribe(id)
+        });
+    }
+}
+
 impl<T: Default> Default for ArcStore<T> {
     fn default() -> Self {
         Self::new(T::default())
@@ -496,6 +753,13 @@ impl<T> Clone for ArcStore<T> {
             value: Arc::clone(&self.value),
             signals: Arc::clone(&self.signals),
             keys: self.keys.clone(),
+            changes: self.changes.clone(),
+            derived: self.derived.clone(),
+            derived_guard: if derived::cloning_weak() {
+                None
+            } else {
+                self.derived_guard.clone()
+            },
         }
     }
 }
@@ -511,6 +775,25 @@ impl<T> DefinedAt for ArcStore<T> {
             None
         }
     }
+
+    fn debug_name(&self) -> Option<Arc<str>> {
+        self.signals
+            .write()
+            .or_poisoned()
+            .get_or_insert(Default::default(), &self.changes, &self.derived)
+            .this
+            .debug_name()
+    }
+}
+
+impl<T: 'static> WithName for ArcStore<T> {
+    fn with_name(self, name: impl Into<Arc<str>>) -> Self {
+        let name = name.into();
+        let trigger = self.get_trigger(Default::default());
+        trigger.this.with_name(Arc::clone(&name));
+        trigger.children.with_name(name);
+        self
+    }
 }
 
 impl<T> IsDisposed for ArcStore<T> {
@@ -538,8 +821,9 @@ where
     type Value = T;
 
     fn try_write(&self) -> Option<impl UntrackableGuard<Target = Self::Value>> {
-        self.writer()
-            .map(|writer| WriteGuard::new(self.clone(), writer))
+        self.writer().map(|writer| {
+            ChangeGuard::new(self, WriteGuard::new(self.clone(), writer))
+        })
     }
 
     fn try_write_untracked(
@@ -594,6 +878,24 @@ where
     }
 }
 
+#[cfg(feature = "hydration")]
+impl<T> Store<T>
+where
+    T: serde::Serialize + serde::de::DeserializeOwned + Send + Sync + 'static,
+{
+    /// Creates a new store whose value is sent from the server to the client during hydration.
+    ///
+    /// See [`ArcStore::new_hydrated`].
+    #[track_caller]
+    pub fn new_hydrated(initial: impl FnOnce() -> T) -> Self {
+        Self {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: Location::caller(),
+            inner: ArenaItem::new_with_storage(ArcStore::new_hydrated(initial)),
+        }
+    }
+}
+
 impl<T> Store<T, LocalStorage>
 where
     T: 'static,
@@ -610,6 +912,26 @@ where
     }
 }
 
+#[cfg(feature = "serde")]
+impl<T, S> Store<T, S>
+where
+    T: serde::Serialize + Send + Sync + 'static,
+    S: Storage<ArcStore<T>>,
+{
+    /// Calls `fun` with each change to the fields of this store, including the path of the field
+    /// and its new value, serialized as JSON.
+    ///
+    /// See [`ArcStore::subscribe_changes`].
+    pub fn subscribe_changes(
+        &self,
+        fun: impl Fn(&StoreChange) + Send + Sync + 'static,
+    ) {
+        if let Some(inner) = self.inner.try_get_value() {
+            inner.subscribe_changes(fun);
+        }
+    }
+}
+
 impl<T> Default for Store<T>
 where
     T: Default + Send + Sync + 'static,
@@ -659,6 +981,25 @@ impl<T, S> DefinedAt for Store<T, S> {
             None
         }
     }
+
+    fn debug_name(&self) -> Option<Arc<str>> {
+        self.inner.debug_name()
+    }
+}
+
+impl<T, S> WithName for Store<T, S>
+where
+    T: 'static,
+    S: Storage<ArcStore<T>>,
+{
+    fn with_name(self, name: impl Into<Arc<str>>) -> Self {
+        let name = name.into();
+        if let Some(inner) = self.inner.try_get_value() {
+            inner.with_name(Arc::clone(&name));
+        }
+        self.inner.with_name(name);
+        self
+    }
 }
 
 impl<T, S> IsDisposed for Store<T, S>
@@ -702,7 +1043,9 @@ where
     type Value = T;
 
     fn try_write(&self) -> Option<impl UntrackableGuard<Target = Self::Value>> {
-        self.writer().map(|writer| WriteGuard::new(*self, writer))
+        self.writer().map(|writer| {
+            ChangeGuard::new(self, WriteGuard::new(*self, writer))
+        })
     }
 
     fn try_write_untracked(
@@ -1021,6 +1364,66 @@ mod tests {
         assert_eq!(combined_count.load(Ordering::Relaxed), 2);
     }
 
+    #[tokio::test]
+    async fn patching_uses_manual_patch_field_impls() {
+        use crate::{path::StorePath, PatchField};
+
+        // implements only the required method, as implementations written before keyed
+        // patching did
+        #[derive(Debug, Default, PartialEq)]
+        struct Celsius(i32);
+
+        impl PatchField for Celsius {
+            fn patch_field(
+                &mut self,
+                new: Self,
+                path: &StorePath,
+                notify: &mut dyn FnMut(&StorePath),
+            ) {
+                if *self != new {
+                    *self = new;
+                    notify(path);
+                }
+            }
+        }
+
+        #[derive(Debug, Store, Patch, Default)]
+        struct Weather {
+            temperature: Celsius,
+            city: String,
+        }
+
+        _ = any_spawner::Executor::init_tokio();
+
+        let count = Arc::new(AtomicUsize::new(0));
+        let store = Store::new(Weather {
+            temperature: Celsius(20),
+            city: "Oslo".into(),
+        });
+
+        Effect::new_sync({
+            let count = Arc::clone(&count);
+            move |_| {
+                println!("{:?}", store.temperature().read());
+                count.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+        tick().await;
+        store.patch(Weather {
+            temperature: Celsius(20),
+            city: "Bergen".into(),
+        });
+        tick().await;
+        assert_eq!(count.load(Ordering::Relaxed), 1);
+
+        store.patch(Weather {
+            temperature: Celsius(25),
+            city: "Bergen".into(),
+        });
+        tick().await;
+        assert_eq!(count.load(Ordering::Relaxed), 2);
+    }
+
     #[tokio::test]
     async fn patching_only_notifies_changed_field_with_custom_patch() {
         #[derive(Debug, Store, Patch, Default)]
@@ -1083,6 +1486,752 @@ mod tests {
         assert_eq!(combined_count.load(Ordering::Relaxed), 3);
     }
 
+    #[tokio::test]
+    async fn patching_enum_only_notifies_changed_variant_or_fields() {
+        #[derive(Debug, Clone, PartialEq, Store, Patch)]
+        enum Status {
+            Idle,
+            Loading(u32),
+            Loaded {
+                #[patch(|this, new| *this = new)]
+                name: String,
+                count: usize,
+            },
+        }
+
+        _ = any_spawner::Executor::init_tokio();
+
+        let variant_count = Arc::new(AtomicUsize::new(0));
+        let field_count = Arc::new(AtomicUsize::new(0));
+
+        let store = Store::new(Status::Loading(10));
+
+        Effect::new_sync({
+            let variant_count = Arc::clone(&variant_count);
+            move |_| {
+                println!("{:?}", store.idle());
+                variant_count.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+        Effect::new_sync({
+            let field_count = Arc::clone(&field_count);
+            move |_| {
+                println!("{:?}", store.loading_0().map(|n| *n.read()));
+                println!("{:?}", store.loaded_count().map(|n| *n.read()));
+                field_count.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+        tick().await;
+        assert_eq!(variant_count.load(Ordering::Relaxed), 1);
+        assert_eq!(field_count.load(Ordering::Relaxed), 1);
+
+        // same variant, same fields
+        store.patch(Status::Loading(10));
+        tick().await;
+        assert_eq!(variant_count.load(Ordering::Relaxed), 1);
+        assert_eq!(field_count.load(Ordering::Relaxed), 1);
+
+        // same tuple variant, changed field
+        store.patch(Status::Loading(20));
+        tick().await;
+        assert_eq!(variant_count.load(Ordering::Relaxed), 1);
+        assert_eq!(field_count.load(Ordering::Relaxed), 2);
+
+        // changed variant
+        store.patch(Status::Loaded {
+            name: "todos".into(),
+            count: 1,
+        });
+        tick().await;
+        assert_eq!(variant_count.load(Ordering::Relaxed), 2);
+        assert_eq!(field_count.load(Ordering::Relaxed), 3);
+
+        // same struct variant, changed field
+        store.patch(Status::Loaded {
+            name: "todos".into(),
+            count: 2,
+        });
+        tick().await;
+        assert_eq!(variant_count.load(Ordering::Relaxed), 2);
+        assert_eq!(field_count.load(Ordering::Relaxed), 4);
+        assert_eq!(store.loaded_count().map(|n| *n.read_untracked()), Some(2));
+
+        // unit variants
+        store.patch(Status::Idle);
+        tick().await;
+        assert_eq!(variant_count.load(Ordering::Relaxed), 3);
+        assert_eq!(field_count.load(Ordering::Relaxed), 5);
+        store.patch(Status::Idle);
+        tick().await;
+        assert_eq!(variant_count.load(Ordering::Relaxed), 3);
+        assert_eq!(field_count.load(Ordering::Relaxed), 5);
+        assert_eq!(*store.read_untracked(), Status::Idle);
+    }
+
+    #[tokio::test]
+    async fn keyed_patching_only_notifies_changed_items() {
+        use crate::AtKeyed;
+
+        #[derive(Debug, Store, Patch, Default)]
+        struct Rows {
+            #[store(key: usize = |row| row.id)]
+            rows: Vec<Row>,
+        }
+
+        #[derive(Debug, Store, Patch, Default)]
+        struct Row {
+            id: usize,
+            label: String,
+        }
+
+        fn rows(rows: &[(usize, &str)]) -> Rows {
+            Rows {
+                rows: rows
+                    .iter()
+                    .map(|(id, label)| Row {
+                        id: *id,
+                        label: label.to_string(),
+                    })
+                    .collect(),
+            }
+        }
+
+        _ = any_spawner::Executor::init_tokio();
+
+        let list_count = Arc::new(AtomicUsize::new(0));
+        let a_count = Arc::new(AtomicUsize::new(0));
+        let b_count = Arc::new(AtomicUsize::new(0));
+
+        let store = Store::new(rows(&[(1, "a"), (2, "b")]));
+
+        Effect::new_sync({
+            let list_count = Arc::clone(&list_count);
+            move |_| {
+                println!("{}", store.rows().into_iter().count());
+                list_count.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+        for (id, count) in [(1, &a_count), (2, &b_count)] {
+            Effect::new_sync({
+                let count = Arc::clone(count);
+                move |_| {
+                    println!(
+                        "{:?}",
+                        *AtKeyed::new(store.rows(), id).label().read()
+                    );
+                    count.fetch_add(1, Ordering::Relaxed);
+                }
+            });
+        }
+        tick().await;
+        assert_eq!(list_count.load(Ordering::Relaxed), 1);
+        assert_eq!(a_count.load(Ordering::Relaxed), 1);
+        assert_eq!(b_count.load(Ordering::Relaxed), 1);
+
+        // inserting a row at the top only notifies the list
+        store.patch(rows(&[(0, "new"), (1, "a"), (2, "b")]));
+        tick().await;
+        assert_eq!(list_count.load(Ordering::Relaxed), 2);
+        assert_eq!(a_count.load(Ordering::Relaxed), 1);
+        assert_eq!(b_count.load(Ordering::Relaxed), 1);
+        assert_eq!(
+            *AtKeyed::new(store.rows(), 0).label().read_untracked(),
+            "new"
+        );
+        assert_eq!(
+            *AtKeyed::new(store.rows(), 2).label().read_untracked(),
+            "b"
+        );
+
+        // changing a row only notifies that row
+        store.patch(rows(&[(0, "new"), (1, "a"), (2, "B")]));
+        tick().await;
+        assert_eq!(list_count.load(Ordering::Relaxed), 2);
+        assert_eq!(a_count.load(Ordering::Relaxed), 1);
+        assert_eq!(b_count.load(Ordering::Relaxed), 2);
+
+        // removing and reordering rows keeps the keys in sync
+        store.patch(rows(&[(2, "B"), (3, "c"), (1, "a")]));
+        tick().await;
+        assert_eq!(list_count.load(Ordering::Relaxed), 3);
+        assert_eq!(a_count.load(Ordering::Relaxed), 1);
+        assert_eq!(b_count.load(Ordering::Relaxed), 2);
+        assert_eq!(
+            *AtKeyed::new(store.rows(), 1).label().read_untracked(),
+            "a"
+        );
+        assert_eq!(
+            *AtKeyed::new(store.rows(), 2).label().read_untracked(),
+            "B"
+        );
+        assert_eq!(
+            *AtKeyed::new(store.rows(), 3).label().read_untracked(),
+            "c"
+        );
+    }
+
+    #[tokio::test]
+    async fn patching_map_only_notifies_changed_entries() {
+        use crate::{path::StorePath, StoreField};
+        use std::{collections::HashMap, sync::Mutex};
+
+        #[derive(Debug, Store, Patch, Default)]
+        struct Settings {
+            values: HashMap<String, u32>,
+        }
+
+        _ = any_spawner::Executor::init_tokio();
+
+        let count = Arc::new(AtomicUsize::new(0));
+        let store = Store::new(Settings {
+            values: HashMap::from([("a".to_string(), 1)]),
+        });
+        let values_path =
+            store.values().path().into_iter().collect::<StorePath>();
+        let changed = Arc::new(Mutex::new(Vec::<StorePath>::new()));
+        store
+            .get_trigger(Default::default())
+            .changes
+            .subscribe(Arc::new({
+                let changed = Arc::clone(&changed);
+                move |paths: &[StorePath]| {
+                    changed.lock().unwrap().extend(paths.iter().cloned())
+                }
+            }));
+
+        Effect::new_sync({
+            let count = Arc::clone(&count);
+            move |_| {
+                println!("{:?}", store.values().read());
+                count.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+        tick().await;
+        assert_eq!(count.load(Ordering::Relaxed), 1);
+
+        store.patch(Settings {
+            values: HashMap::from([("a".to_string(), 1)]),
+        });
+        tick().await;
+        assert_eq!(count.load(Ordering::Relaxed), 1);
+        assert!(changed.lock().unwrap().is_empty());
+
+        // changing the value of an entry only notifies that entry
+        store.patch(Settings {
+            values: HashMap::from([("a".to_string(), 2)]),
+        });
+        tick().await;
+        assert_eq!(count.load(Ordering::Relaxed), 1);
+        let entry_paths = std::mem::take(&mut *changed.lock().unwrap());
+        assert_eq!(entry_paths.len(), 1);
+        assert!(entry_paths[0].starts_with(&values_path));
+        assert_ne!(entry_paths[0], values_path);
+
+        // replacing an entry changes the keys of the map, so the map is notified too
+        store.patch(Settings {
+            values: HashMap::from([("b".to_string(), 2)]),
+        });
+        tick().await;
+        assert_eq!(count.load(Ordering::Relaxed), 2);
+        assert_eq!(*changed.lock().unwrap(), [values_path]);
+        assert_eq!(store.values().read_untracked().get("b"), Some(&2));
+    }
+
+    #[tokio::test]
+    async fn derived_fields_only_rerun_when_their_fields_change() {
+        use crate::{ArcField, Field};
+
+        static RUNS: AtomicUsize = AtomicUsize::new(0);
+
+        #[derive(Debug, Store)]
+        #[store(derive(total: u32 = total, item_count: usize = |cart| {
+            cart.items().read().len()
+        }))]
+        struct Cart {
+            note: String,
+            items: Vec<Item>,
+        }
+
+        #[derive(Debug, Store)]
+        struct Item {
+            price: u32,
+            quantity: u32,
+        }
+
+        fn total(cart: ArcField<Cart>) -> u32 {
+            RUNS.fetch_add(1, Ordering::Relaxed);
+            cart.items()
+                .iter_unkeyed()
+                .map(|item| {
+                    *item.clone().price().read() * *item.quantity().read()
+                })
+                .sum()
+        }
+
+        _ = any_spawner::Executor::init_tokio();
+
+        let count = Arc::new(AtomicUsize::new(0));
+        let store = Store::new(Cart {
+            note: String::new(),
+            items: vec![
+                Item {
+                    price: 2,
+                    quantity: 1,
+                },
+                Item {
+                    price: 5,
+                    quantity: 2,
+                },
+            ],
+        });
+
+        Effect::new_sync({
+            let count = Arc::clone(&count);
+            move |_| {
+                println!("total = {}", store.total());
+                count.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+        tick().await;
+        assert_eq!(count.load(Ordering::Relaxed), 1);
+        assert_eq!(RUNS.load(Ordering::Relaxed), 1);
+
+        // the memo is shared by every accessor for the same store
+        let field: Field<Cart> = store.into();
+        assert_eq!(field.total(), 12);
+        assert_eq!(store.item_count(), 2);
+        assert_eq!(RUNS.load(Ordering::Relaxed), 1);
+
+        store.note().set("leave at the door".to_string());
+        tick().await;
+        assert_eq!(count.load(Ordering::Relaxed), 1);
+        assert_eq!(RUNS.load(Ordering::Relaxed), 1);
+
+        store.items().at_unkeyed(0).quantity().set(3);
+        tick().await;
+        assert_eq!(count.load(Ordering::Relaxed), 2);
+        assert_eq!(RUNS.load(Ordering::Relaxed), 2);
+        assert_eq!(store.total(), 16);
+    }
+
+    #[test]
+    fn derived_fields_are_released_with_their_store() {
+        use crate::ArcStore;
+
+        #[derive(Debug, Store)]
+        #[store(derive(double: u32 = |counter| *counter.count().read() * 2))]
+        struct Counter {
+            count: u32,
+        }
+
+        let store = ArcStore::new(Counter { count: 1 });
+        assert_eq!(store.clone().double(), 2);
+        let value = Arc::downgrade(&store.value);
+
+        let clone = store.clone();
+        drop(store);
+        assert_eq!(clone.clone().double(), 2);
+        assert!(value.upgrade().is_some());
+
+        // the memo holds a handle to the store, which does not keep the store alive
+        drop(clone);
+        assert!(value.upgrade().is_none());
+    }
+
+    #[tokio::test]
+    async fn form_store_tracks_field_state() {
+        use crate::FormStore;
+
+        _ = any_spawner::Executor::init_tokio();
+
+        let form = FormStore::new(data())
+            .validator(
+                |todos| todos.user(),
+                |user| {
+                    if user.is_empty() {
+                        Err("required".to_string())
+                    } else {
+                        Ok(())
+                    }
+                },
+            )
+            .async_validator(
+                |todos| todos.user(),
+                |user| async move {
+                    tick().await;
+                    if user == "Bob" {
+                        Err("taken".to_string())
+                    } else {
+                        Ok(())
+                    }
+                },
+            )
+            .on_server_error(|form, error| {
+                form.set_error(form.store().todos(), error);
+            });
+        let state = form.state();
+        let store = form.store();
+
+        assert!(!state.is_dirty());
+        store.todos().at_unkeyed(1).label().set("Sell".to_string());
+        assert!(state.is_dirty());
+        assert!(form.is_dirty(store.todos()));
+        assert!(form.is_dirty(store.todos().at_unkeyed(1).label()));
+        assert!(!form.is_dirty(store.todos().at_unkeyed(0)));
+        assert!(!form.is_dirty(store.user()));
+
+        form.touch(store.user());
+        assert!(form.is_touched(store.user()));
+        assert!(!form.is_touched(store.todos()));
+
+        assert!(state.validate());
+        assert!(!state.validate_async().await);
+        assert_eq!(form.errors(store.user()), ["taken"]);
+
+        store.user().set(String::new());
+        assert!(!state.validate());
+        assert_eq!(form.errors(store.user()), ["required"]);
+
+        store.user().set("Alice".to_string());
+        assert!(state.validate_async().await);
+        assert!(state.is_valid());
+
+        state.handle_server_error(&"too many todos");
+        assert!(!state.is_valid());
+        assert_eq!(form.errors(store.todos()), ["too many todos"]);
+
+        state.reset();
+        assert!(!state.is_dirty());
+        assert!(!form.is_touched(store.user()));
+        assert!(state.is_valid());
+    }
+
+    #[tokio::test]
+    async fn superseded_async_validation_is_discarded() {
+        use crate::FormStore;
+
+        _ = any_spawner::Executor::init_tokio();
+
+        let form = FormStore::new(data())
+            .async_validator(
+                |todos| todos.user(),
+                |user| async move {
+                    tick().await;
+                    if user == "Bob" {
+                        Err("taken".to_string())
+                    } else {
+                        Ok(())
+                    }
+                },
+            )
+            .async_validator(
+                |todos| todos.user(),
+                |user| async move {
+                    tick().await;
+                    if user.len() < 4 {
+                        Err("too short".to_string())
+                    } else {
+                        Ok(())
+                    }
+                },
+            );
+        let state = form.state();
+        let store = form.store();
+
+        // every validator reports its error
+        assert!(!state.validate_async().await);
+        assert_eq!(form.errors(store.user()), ["taken", "too short"]);
+
+        // validation that has started with the old value is superseded by a later run
+        let mut first = Box::pin(state.validate_async());
+        assert!(futures::poll!(&mut first).is_pending());
+        assert!(state.is_validating());
+        store.user().set("Alice".to_string());
+        assert!(state.validate_async().await);
+        assert!(!first.await);
+        assert!(state.is_valid());
+        assert!(!state.is_validating());
+
+        // and by resetting the form
+        store.user().set("Bob".to_string());
+        let mut pending = Box::pin(state.validate_async());
+        assert!(futures::poll!(&mut pending).is_pending());
+        state.reset();
+        assert!(!pending.await);
+        assert!(state.is_valid());
+        assert!(!state.is_validating());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn subscribing_to_changes() {
+        use crate::AtKeyed;
+        use reactive_graph::traits::Set;
+        use serde::Serialize;
+        use serde_json::{json, Value};
+        use std::sync::Mutex;
+
+        #[derive(Debug, Store, Patch, Serialize)]
+        struct Board {
+            title: String,
+            #[store(key: usize = |card| card.id)]
+            cards: Vec<Card>,
+        }
+
+        #[derive(Debug, Store, Patch, Serialize)]
+        struct Card {
+            id: usize,
+            label: String,
+        }
+
+        fn card(id: usize, label: &str) -> Card {
+            Card {
+                id,
+                label: label.to_string(),
+            }
+        }
+
+        let store = Store::new(Board {
+            title: "Todo".into(),
+            cards: vec![card(1, "a"), card(2, "b")],
+        });
+        let changes = Arc::new(Mutex::new(Vec::new()));
+        store.subscribe_changes({
+            let changes = Arc::clone(&changes);
+            move |change| {
+                changes
+                    .lock()
+                    .unwrap()
+                    .push((change.field.clone(), change.value.clone()))
+            }
+        });
+        let take = || std::mem::take(&mut *changes.lock().unwrap());
+
+        store.title().set("Done".into());
+        assert_eq!(take(), [("title".to_string(), json!("Done"))]);
+
+        AtKeyed::new(store.cards(), 2).label().set("B".into());
+        assert_eq!(take(), [("cards[1].label".to_string(), json!("B"))]);
+
+        // untracked writes are not included
+        store.title().write_untracked().push('!');
+        assert_eq!(take(), []);
+
+        // patching only includes the fields that changed
+        store.patch(Board {
+            title: "Done!".into(),
+            cards: vec![card(1, "a"), card(2, "c")],
+        });
+        assert_eq!(take(), [("cards[1].label".to_string(), json!("c"))]);
+
+        // a parent field includes its children
+        store.patch(Board {
+            title: "Done!".into(),
+            cards: vec![card(2, "d"), card(1, "a")],
+        });
+        assert_eq!(
+            take(),
+            [(
+                "cards".to_string(),
+                json!([{ "id": 2, "label": "d" }, { "id": 1, "label": "a" }])
+            )]
+        );
+
+        store.write().title = "Archived".into();
+        let whole = take();
+        assert_eq!(whole.len(), 1);
+        assert_eq!(whole[0].0, "");
+        assert_eq!(whole[0].1["title"], Value::from("Archived"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn subscribing_to_changes_with_skipped_fields() {
+        use reactive_graph::traits::Set;
+        use serde::Serialize;
+        use serde_json::json;
+        use std::sync::Mutex;
+
+        #[derive(Debug, Clone, Store, Patch, Serialize)]
+        struct Profile {
+            #[serde(skip)]
+            session: u32,
+            name: String,
+            #[serde(skip_serializing, rename = "cached")]
+            cache: Vec<String>,
+            age: u32,
+        }
+
+        let profile = Profile {
+            session: 0,
+            name: "Ada".into(),
+            cache: Vec::new(),
+            age: 36,
+        };
+        let subscribe = |store: Store<Profile>| {
+            let changes = Arc::new(Mutex::new(Vec::new()));
+            store.subscribe_changes({
+                let changes = Arc::clone(&changes);
+                move |change| {
+                    changes
+                        .lock()
+                        .unwrap()
+                        .push((change.field.clone(), change.value.clone()))
+                }
+            });
+            move || std::mem::take(&mut *changes.lock().unwrap())
+        };
+
+        let store = Store::new(profile.clone());
+        let take = subscribe(store);
+        store.name().set("Grace".into());
+        assert_eq!(take(), [("name".to_string(), json!("Grace"))]);
+        store.age().set(37);
+        assert_eq!(take(), [("age".to_string(), json!(37))]);
+
+        // skipped fields are not serialized, so their changes are not described
+        store.session().set(1);
+        store.cache().set(vec!["a".into()]);
+        assert_eq!(take(), []);
+
+        // patching a store whose fields have not been accessed yet
+        let store = Store::new(profile.clone());
+        let take = subscribe(store);
+        store.patch(Profile { age: 40, ..profile });
+        assert_eq!(take(), [("age".to_string(), json!(40))]);
+    }
+
+    #[cfg(feature = "hydration")]
+    #[tokio::test]
+    async fn hydrated_store_serializes_its_final_value() {
+        use futures::StreamExt;
+        use hydration_context::{SharedContext, SsrSharedContext};
+        use reactive_graph::owner::Owner;
+        use serde::{Deserialize, Serialize};
+
+        #[derive(Debug, Store, Serialize, Deserialize)]
+        struct Settings {
+            theme: String,
+        }
+
+        // without a shared context, this is an ordinary store
+        let store = Store::new_hydrated(|| Settings {
+            theme: "light".into(),
+        });
+        assert_eq!(store.theme().read_untracked().as_str(), "light");
+
+        let shared_context = Arc::new(SsrSharedContext::new());
+        let owner = Owner::new_root(Some(shared_context.clone()));
+        owner.set();
+
+        let store = Store::new_hydrated(|| Settings {
+            theme: "light".into(),
+        });
+        *store.theme().write() = "dark".into();
+
+        let data = shared_context
+            .pending_data()
+            .unwrap()
+            .collect::<Vec<_>>()
+            .await
+            .concat();
+        assert!(data.contains("__PENDING_RESOURCES=[0,]"));
+        assert!(data.contains(&format!(
+            "{:?}",
+            serde_json::to_string(&Settings {
+                theme: "dark".into()
+            })
+            .unwrap()
+        )));
+    }
+
+    #[cfg(feature = "serde")]
+    #[tokio::test]
+    async fn undo_only_notifies_changed_fields() {
+        use crate::{StoreDiff, StoreField, StorePath};
+        use reactive_graph::history::{History, Restorable};
+        use serde::{Deserialize, Serialize};
+        use std::sync::Mutex;
+
+        #[derive(
+            Debug, Clone, PartialEq, Store, Patch, Serialize, Deserialize,
+        )]
+        struct Profile {
+            name: String,
+            age: u32,
+        }
+
+        _ = any_spawner::Executor::init_tokio();
+
+        let name_count = Arc::new(AtomicUsize::new(0));
+        let age_count = Arc::new(AtomicUsize::new(0));
+
+        let store = Store::new(Profile {
+            name: "Alice".into(),
+            age: 30,
+        });
+        let history = History::new(store);
+        let recorded = Arc::new(Mutex::new(Vec::new()));
+        let _recording = store.record_changes({
+            let recorded = Arc::clone(&recorded);
+            Arc::new(move |diff: StoreDiff| {
+                recorded
+                    .lock()
+                    .unwrap()
+                    .push(diff.paths().cloned().collect::<Vec<_>>());
+            })
+        });
+
+        Effect::new_sync({
+            let name_count = Arc::clone(&name_count);
+            move |_| {
+                println!("{:?}", *store.name().read());
+                name_count.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+        Effect::new_sync({
+            let age_count = Arc::clone(&age_count);
+            move |_| {
+                println!("{:?}", *store.age().read());
+                age_count.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+        tick().await;
+        store.age().set(31);
+        tick().await;
+        store.name().set("Bob".into());
+        tick().await;
+        assert_eq!(name_count.load(Ordering::Relaxed), 2);
+        assert_eq!(age_count.load(Ordering::Relaxed), 2);
+        // each change only records the field that was changed
+        assert_eq!(
+            *recorded.lock().unwrap(),
+            vec![
+                vec![store.age().path().into_iter().collect::<StorePath>()],
+                vec![store.name().path().into_iter().collect::<StorePath>()],
+            ]
+        );
+
+        assert!(history.undo());
+        tick().await;
+        assert_eq!(store.name().read_untracked().as_str(), "Alice");
+        assert_eq!(name_count.load(Ordering::Relaxed), 3);
+        assert_eq!(age_count.load(Ordering::Relaxed), 2);
+
+        assert!(history.undo());
+        tick().await;
+        assert_eq!(*store.age().read_untracked(), 30);
+        assert_eq!(name_count.load(Ordering::Relaxed), 3);
+        assert_eq!(age_count.load(Ordering::Relaxed), 3);
+        assert!(!history.undo());
+
+        assert!(history.redo());
+        tick().await;
+        assert_eq!(*store.age().read_untracked(), 31);
+        assert_eq!(name_count.load(Ordering::Relaxed), 3);
+        assert_eq!(age_count.load(Ordering::Relaxed), 4);
+    }
+
     #[derive(Debug, Store)]
     pub struct StructWithOption {
         opt_field: Option<Todo>,
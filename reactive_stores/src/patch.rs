@@ -1,8 +1,16 @@
-use crate::{path::StorePath, StoreField};
+use crate::{
+    path::{StorePath, StorePathSegment},
+    KeyMap, StoreField,
+};
 use itertools::{EitherOrBoth, Itertools};
 use reactive_graph::traits::{Notify, UntrackableGuard};
+use rustc_hash::FxHashMap;
 use std::{
     borrow::Cow,
+    collections::HashMap,
+    fmt::Debug,
+    hash::{BuildHasher, Hash},
+    mem,
     net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
     num::{
         NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8,
@@ -31,15 +39,20 @@ where
 
     fn patch(&self, new: Self::Value) {
         let path = self.path().into_iter().collect::<StorePath>();
+        let keys = self.keys();
+        let mut changed = Vec::new();
         if let Some(mut writer) = self.writer() {
             // don't track the writer for the whole store
             writer.untrack();
             let mut notify = |path: &StorePath| {
                 self.get_trigger(path.to_owned()).this.notify();
                 self.get_trigger(path.to_owned()).children.notify();
+                changed.push(path.to_owned());
             };
-            writer.patch_field(new, &path, &mut notify);
+            writer.patch_field_keyed(new, &path, &mut notify, keys.as_ref());
         }
+        // the writer has been dropped, so subscribers can read the new value
+        self.get_trigger(path).changes.notify(&changed);
     }
 }
 
@@ -52,6 +65,24 @@ pub trait PatchField {
         path: &StorePath,
         notify: &mut dyn FnMut(&StorePath),
     );
+
+    /// Patches the field with some new value, using the keys of the store to find the paths of
+    /// the items in keyed fields, and only notifying if the value has changed.
+    ///
+    /// Types that contain keyed fields should implement this and pass `keys` on to their own
+    /// fields. By default, this ignores the keys and calls [`PatchField::patch_field`].
+    fn patch_field_keyed(
+        &mut self,
+        new: Self,
+        path: &StorePath,
+        notify: &mut dyn FnMut(&StorePath),
+        keys: Option<&KeyMap>,
+    ) where
+        Self: Sized,
+    {
+        _ = keys;
+        self.patch_field(new, path, notify);
+    }
 }
 
 macro_rules! patch_primitives {
@@ -123,6 +154,16 @@ where
         new: Self,
         path: &StorePath,
         notify: &mut dyn FnMut(&StorePath),
+    ) {
+        self.patch_field_keyed(new, path, notify, None);
+    }
+
+    fn patch_field_keyed(
+        &mut self,
+        new: Self,
+        path: &StorePath,
+        notify: &mut dyn FnMut(&StorePath),
+        keys: Option<&KeyMap>,
     ) {
         if self.is_empty() && new.is_empty() {
             return;
@@ -144,7 +185,7 @@ where
             {
                 match item {
                     EitherOrBoth::Both(new, old) => {
-                        old.patch_field(new, &new_path, notify);
+                        old.patch_field_keyed(new, &new_path, notify, keys);
                     }
                     EitherOrBoth::Left(new) => {
                         adds.push(new);
@@ -167,6 +208,207 @@ where
     }
 }
 
+/// Patches a keyed collection, like a field marked with `#[store(key: K = ...)]`, by matching
+/// its old and new items by key rather than by index.
+///
+/// Items whose key is in both the old and new collection are patched in place. Only the items that
+/// have changed, been inserted, or been removed are notified, along with the collection itself if
+/// its keys have changed. The indices of the keys in the store's [`KeyMap`] are updated to match
+/// the new order of the items.
+///
+/// If the store has no keys, this falls back to patching the collection by index.
+pub fn patch_keyed<T, K>(
+    this: &mut Vec<T>,
+    new: Vec<T>,
+    key_fn: impl Fn(&T) -> K,
+    path: &StorePath,
+    notify: &mut dyn FnMut(&StorePath),
+    keys: Option<&KeyMap>,
+) where
+    T: PatchField,
+    K: Debug + Hash + PartialEq + Eq + Send + Sync + 'static,
+{
+    let Some(key_map) = keys else {
+        this.patch_field_keyed(new, path, notify, keys);
+        return;
+    };
+
+    let old_keys = this.iter().map(&key_fn).collect::<Vec<_>>();
+    let new_keys = new.iter().map(&key_fn).collect::<Vec<_>>();
+    let keys_changed = old_keys != new_keys;
+
+    // update the keys first, so that the lock on the key map is not held while patching the
+    // items, which may have keyed fields of their own
+    let (removed, segments) = key_map
+        .with_field_keys(
+            path.clone(),
+            |field_keys| {
+                let removed = old_keys
+                    .iter()
+                    .filter(|key| !new_keys.contains(key))
+                    .filter_map(|key| field_keys.get(key))
+                    .map(|(segment, _)| segment)
+                    .collect::<Vec<_>>();
+                if keys_changed {
+                    field_keys.update(new.iter().map(&key_fn));
+                }
+                let segments = new_keys
+                    .iter()
+                    .map(|key| field_keys.get(key).map(|(segment, _)| segment))
+                    .collect::<Vec<_>>();
+                (removed, segments)
+            },
+            || this.iter().map(&key_fn).collect(),
+        )
+        .unwrap_or_default();
+    let item_path = |segment: Option<StorePathSegment>| {
+        let mut item_path = path.clone();
+        if let Some(segment) = segment {
+            item_path.push(segment);
+        }
+        item_path
+    };
+
+    if !keys_changed {
+        // the same items in the same order, so only the items themselves can have changed
+        for ((old, new), segment) in this.iter_mut().zip(new).zip(segments) {
+            old.patch_field_keyed(new, &item_path(segment), notify, keys);
+        }
+        return;
+    }
+
+    let mut old_items = mem::take(this)
+        .into_iter()
+        .zip(old_keys)
+        .map(|(item, key)| (key, item))
+        .collect::<FxHashMap<_, _>>();
+    this.reserve(new.len());
+    for ((new, key), segment) in new.into_iter().zip(&new_keys).zip(segments) {
+        match old_items.remove(key) {
+            Some(mut old) => {
+                old.patch_field_keyed(new, &item_path(segment), notify, keys);
+                this.push(old);
+            }
+            None => {
+                this.push(new);
+                notify(&item_path(segment));
+            }
+        }
+    }
+    for segment in removed {
+        notify(&item_path(Some(segment)));
+    }
+    notify(path);
+}
+
+impl<K, V, S> PatchField for HashMap<K, V, S>
+where
+    K: Debug + Clone + Hash + Eq + Send + Sync + 'static,
+    V: PatchField,
+    S: BuildHasher,
+{
+    fn patch_field(
+        &mut self,
+        new: Self,
+        path: &StorePath,
+        notify: &mut dyn FnMut(&StorePath),
+    ) {
+        self.patch_field_keyed(new, path, notify, None);
+    }
+
+    /// Patches the entries of the map by their keys.
+    ///
+    /// Each key is given its own path in the store, in the same way as the items of a keyed
+    /// field, so only the entries that have been inserted, removed, or changed are notified. The
+    /// map itself is also notified if its set of keys has changed. If the store has no keys, the
+    /// map is notified if any of its entries has changed.
+    fn patch_field_keyed(
+        &mut self,
+        mut new: Self,
+        path: &StorePath,
+        notify: &mut dyn FnMut(&StorePath),
+        keys: Option<&KeyMap>,
+    ) {
+        let keys_changed = self.len() != new.len()
+            || self.keys().any(|key| !new.contains_key(key));
+
+        let Some(key_map) = keys else {
+            let mut changed = keys_changed;
+            self.retain(|key, _| new.contains_key(key));
+            for (key, new) in new.drain() {
+                match self.get_mut(&key) {
+                    Some(old) => {
+                        old.patch_field(new, path, &mut |_| changed = true)
+                    }
+                    None => {
+                        self.insert(key, new);
+                    }
+                }
+            }
+            if changed {
+                notify(path);
+            }
+            return;
+        };
+
+        // update the keys first, so that the lock on the key map is not held while patching the
+        // entries, which may have keyed fields of their own
+        let (removed, segments) = key_map
+            .with_field_keys(
+                path.clone(),
+                |field_keys| {
+                    let removed = self
+                        .keys()
+                        .filter(|key| !new.contains_key(key))
+                        .filter_map(|key| field_keys.get(key))
+                        .map(|(segment, _)| segment)
+                        .collect::<Vec<_>>();
+                    if keys_changed {
+                        field_keys.update(new.keys().cloned());
+                    }
+                    let segments = new
+                        .keys()
+                        .filter_map(|key| {
+                            field_keys
+                                .get(key)
+                                .map(|(segment, _)| (key.clone(), segment))
+                        })
+                        .collect::<FxHashMap<_, _>>();
+                    (removed, segments)
+                },
+                || self.keys().cloned().collect(),
+            )
+            .unwrap_or_default();
+        let entry_path = |segment: Option<StorePathSegment>| {
+            let mut entry_path = path.clone();
+            if let Some(segment) = segment {
+                entry_path.push(segment);
+            }
+            entry_path
+        };
+
+        self.retain(|key, _| new.contains_key(key));
+        for (key, new) in new.drain() {
+            let entry_path = entry_path(segments.get(&key).copied());
+            match self.get_mut(&key) {
+                Some(old) => {
+                    old.patch_field_keyed(new, &entry_path, notify, keys)
+                }
+                None => {
+                    self.insert(key, new);
+                    notify(&entry_path);
+                }
+            }
+        }
+        if keys_changed {
+            for segment in removed {
+                notify(&entry_path(Some(segment)));
+            }
+            notify(path);
+        }
+    }
+}
+
 macro_rules! patch_tuple {
 	($($ty:ident),*) => {
 		impl<$($ty),*> PatchField for ($($ty,)*)
@@ -178,6 +420,16 @@ macro_rules! patch_tuple {
                 new: Self,
                 path: &StorePath,
                 notify: &mut dyn FnMut(&StorePath),
+            ) {
+                self.patch_field_keyed(new, path, notify, None);
+            }
+
+            fn patch_field_keyed(
+                &mut self,
+                new: Self,
+                path: &StorePath,
+                notify: &mut dyn FnMut(&StorePath),
+                keys: Option<&KeyMap>,
             ) {
                 let mut idx = 0;
                 let mut new_path = path.to_owned();
@@ -188,7 +440,7 @@ macro_rules! patch_tuple {
                     let ($($ty,)*) = self;
                     let ($([<new_ $ty:lower>],)*) = new;
                     $(
-                        $ty.patch_field([<new_ $ty:lower>], &new_path, notify);
+                        $ty.patch_field_keyed([<new_ $ty:lower>], &new_path, notify, keys);
                         idx += 1;
                         new_path.replace_last(idx);
                     )*
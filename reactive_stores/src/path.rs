@@ -28,6 +28,11 @@ impl StorePath {
         self.0.pop()
     }
 
+    /// Returns `true` if this path is the same as `other`, or is the path of one of its children.
+    pub fn starts_with(&self, other: &StorePath) -> bool {
+        self.0.starts_with(&other.0)
+    }
+
     /// Updates the last segment in the place in place.
     pub fn replace_last(&mut self, segment: impl Into<StorePathSegment>) {
         if let Some(last) = self.0.last_mut() {
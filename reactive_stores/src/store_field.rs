@@ -64,7 +64,11 @@ where
     #[track_caller]
     fn get_trigger(&self, path: StorePath) -> StoreFieldTrigger {
         let triggers = &self.signals;
-        let trigger = triggers.write().or_poisoned().get_or_insert(path);
+        let trigger = triggers.write().or_poisoned().get_or_insert(
+            path,
+            &self.changes,
+            &self.derived,
+        );
         trigger
     }
 
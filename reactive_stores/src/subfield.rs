@@ -1,4 +1,5 @@
 use crate::{
+    changes::ChangeGuard,
     path::{StorePath, StorePathSegment},
     store_field::StoreField,
     KeyMap, StoreFieldTrigger,
@@ -186,7 +187,7 @@ where
     type Value = T;
 
     fn try_write(&self) -> Option<impl UntrackableGuard<Target = Self::Value>> {
-        self.writer()
+        self.writer().map(|writer| ChangeGuard::new(self, writer))
     }
 
     fn try_write_untracked(
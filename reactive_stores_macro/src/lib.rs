@@ -6,8 +6,8 @@ use syn::{
     parse::{Parse, ParseStream, Parser},
     punctuated::Punctuated,
     token::Comma,
-    ExprClosure, Field, Fields, Generics, Ident, Index, Meta, Result, Token,
-    Type, Variant, Visibility, WhereClause,
+    Attribute, Expr, ExprClosure, Field, Fields, Generics, Ident, Index, Meta,
+    Result, Token, Type, Variant, Visibility, WhereClause,
 };
 
 #[proc_macro_error]
@@ -31,6 +31,7 @@ struct Model {
     name: Ident,
     generics: Generics,
     ty: ModelTy,
+    derived: Vec<DerivedField>,
 }
 
 enum ModelTy {
@@ -68,15 +69,80 @@ impl Parse for Model {
             }
         };
 
+        let derived = store_attrs(&input.attrs)
+            .into_iter()
+            .flat_map(|attr| match attr {
+                StoreAttr::Derive(fields) => fields,
+            })
+            .collect();
+
         Ok(Self {
             vis: input.vis,
             generics: input.generics,
             name: input.ident,
             ty,
+            derived,
         })
     }
 }
 
+enum StoreAttr {
+    Derive(Vec<DerivedField>),
+}
+
+impl Parse for StoreAttr {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mode: Ident = input.parse()?;
+        if mode == "derive" {
+            let content;
+            syn::parenthesized!(content in input);
+            let fields =
+                Punctuated::<DerivedField, Comma>::parse_terminated(&content)?;
+            Ok(StoreAttr::Derive(fields.into_iter().collect()))
+        } else {
+            Err(syn::Error::new(
+                mode.span(),
+                "expected `derive(<name>: <Type> = <fn>)`",
+            ))
+        }
+    }
+}
+
+struct DerivedField {
+    ident: Ident,
+    ty: Type,
+    fun: Expr,
+}
+
+impl Parse for DerivedField {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let ident: Ident = input.parse()?;
+        let _col: Token!(:) = input.parse()?;
+        let ty: Type = input.parse()?;
+        let _eq: Token!(=) = input.parse()?;
+        let fun: Expr = input.parse()?;
+        Ok(DerivedField { ident, ty, fun })
+    }
+}
+
+fn store_attrs(attrs: &[Attribute]) -> Vec<StoreAttr> {
+    attrs
+        .iter()
+        .filter(|attr| attr.meta.path().is_ident("store"))
+        .flat_map(|attr| match &attr.meta {
+            Meta::List(list) => {
+                match Punctuated::<StoreAttr, Comma>::parse_terminated
+                    .parse2(list.tokens.clone())
+                {
+                    Ok(attrs) => attrs.into_iter().collect::<Vec<_>>(),
+                    Err(e) => abort!(list, e),
+                }
+            }
+            _ => abort!(attr, "expected `#[store(derive(...))]`"),
+        })
+        .collect()
+}
+
 #[derive(Clone)]
 enum SubfieldMode {
     Keyed(ExprClosure, Type),
@@ -108,6 +174,7 @@ impl ToTokens for Model {
             name,
             generics,
             ty,
+            derived,
         } = &self;
         let any_store_field = Ident::new("AnyStoreField", Span::call_site());
         let trait_name = Ident::new(&format!("{name}StoreFields"), name.span());
@@ -138,18 +205,42 @@ impl ToTokens for Model {
         let (trait_fields, read_fields): (Vec<_>, Vec<_>) =
             ty.to_field_data(&library_path, generics, &any_store_field, name);
 
+        // derived fields are memoized getters, available on any field that can become an ArcField
+        let (trait_derived, read_derived): (Vec<_>, Vec<_>) = derived
+            .iter()
+            .map(|DerivedField { ident, ty, fun }| {
+                let signature = quote! {
+                    fn #ident(self) -> #ty
+                    where
+                        Self: Clone + Into<#library_path::ArcField<#name #generics>>,
+                        #name #generics: 'static
+                };
+                (
+                    quote! { #signature; },
+                    quote! {
+                        #signature {
+                            let fun: fn(#library_path::ArcField<#name #generics>) -> #ty = #fun;
+                            #library_path::derived_field(self, stringify!(#ident), fun)
+                        }
+                    },
+                )
+            })
+            .unzip();
+
         // read access
         tokens.extend(quote! {
             #vis trait #trait_name <AnyStoreField>
             #where_with_orig
             {
                 #(#trait_fields)*
+                #(#trait_derived)*
             }
 
             impl #generics_with_orig #trait_name <AnyStoreField> for AnyStoreField
             #where_with_orig
             {
                #(#read_fields)*
+               #(#read_derived)*
             }
         });
     }
@@ -164,66 +255,54 @@ impl ModelTy {
         name: &Ident,
     ) -> (Vec<TokenStream>, Vec<TokenStream>) {
         match self {
-            ModelTy::Struct { fields } => fields
-                .iter()
-                .enumerate()
-                .map(|(idx, field)| {
-                    let Field {
-                        ident, ty, attrs, ..
-                    } = &field;
-                    let modes = attrs
-                        .iter()
-                        .find_map(|attr| {
-                            attr.meta.path().is_ident("store").then(|| {
-                                match &attr.meta {
-                                    Meta::List(list) => {
-                                        match Punctuated::<
-                                                SubfieldMode,
-                                                Comma,
-                                            >::parse_terminated
-                                                .parse2(list.tokens.clone())
-                                            {
-                                                Ok(modes) => Some(
-                                                    modes
-                                                        .iter()
-                                                        .cloned()
-                                                        .collect::<Vec<_>>(),
-                                                ),
-                                                Err(e) => abort!(list, e),
-                                            }
-                                    }
-                                    _ => None,
-                                }
-                            })
-                        })
-                        .flatten();
-
-                    (
-                        field_to_tokens(
-                            idx,
-                            false,
-                            modes.as_deref(),
-                            library_path,
-                            ident.as_ref(),
-                            generics,
-                            any_store_field,
-                            name,
-                            ty,
-                        ),
-                        field_to_tokens(
-                            idx,
-                            true,
-                            modes.as_deref(),
-                            library_path,
-                            ident.as_ref(),
-                            generics,
-                            any_store_field,
-                            name,
-                            ty,
-                        ),
-                    )
-                })
-                .unzip(),
+            ModelTy::Struct { fields } => {
+                // the store needs to know which fields are serialized to describe changes to
+                // them, if any are skipped by serde
+                let register = serialized_fields(fields)
+                    .map(|serialized| {
+                        quote! {
+                            #library_path::set_serialized_fields(&self, &[#(#serialized),*]);
+                        }
+                    })
+                    .unwrap_or_default();
+                fields
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, field)| {
+                        let Field {
+                            ident, ty, attrs, ..
+                        } = &field;
+                        let modes = store_modes(attrs);
+
+                        (
+                            field_to_tokens(
+                                idx,
+                                false,
+                                modes.as_deref(),
+                                &register,
+                                library_path,
+                                ident.as_ref(),
+                                generics,
+                                any_store_field,
+                                name,
+                                ty,
+                            ),
+                            field_to_tokens(
+                                idx,
+                                true,
+                                modes.as_deref(),
+                                &register,
+                                library_path,
+                                ident.as_ref(),
+                                generics,
+                                any_store_field,
+                                name,
+                                ty,
+                            ),
+                        )
+                    })
+                    .unzip()
+            }
             ModelTy::Enum { variants } => variants
                 .iter()
                 .map(|variant| {
@@ -255,11 +334,59 @@ impl ModelTy {
     }
 }
 
+/// Parses the `#[store(...)]` attribute of a field, if it has one.
+fn store_modes(attrs: &[Attribute]) -> Option<Vec<SubfieldMode>> {
+    attrs
+        .iter()
+        .find_map(|attr| {
+            attr.meta.path().is_ident("store").then(|| match &attr.meta {
+                Meta::List(list) => {
+                    match Punctuated::<SubfieldMode, Comma>::parse_terminated
+                        .parse2(list.tokens.clone())
+                    {
+                        Ok(modes) => {
+                            Some(modes.iter().cloned().collect::<Vec<_>>())
+                        }
+                        Err(e) => abort!(list, e),
+                    }
+                }
+                _ => None,
+            })
+        })
+        .flatten()
+}
+
+/// Returns whether each field is serialized, or `None` if none of the fields are skipped with
+/// `#[serde(skip)]` or `#[serde(skip_serializing)]`.
+fn serialized_fields(fields: &[Field]) -> Option<Vec<bool>> {
+    let serialized = fields
+        .iter()
+        .map(|field| {
+            !field.attrs.iter().any(|attr| match &attr.meta {
+                Meta::List(list) if list.path.is_ident("serde") => {
+                    Punctuated::<Meta, Comma>::parse_terminated
+                        .parse2(list.tokens.clone())
+                        .map(|metas| {
+                            metas.iter().any(|meta| {
+                                meta.path().is_ident("skip")
+                                    || meta.path().is_ident("skip_serializing")
+                            })
+                        })
+                        .unwrap_or(false)
+                }
+                _ => false,
+            })
+        })
+        .collect::<Vec<_>>();
+    serialized.contains(&false).then_some(serialized)
+}
+
 #[allow(clippy::too_many_arguments)]
 fn field_to_tokens(
     idx: usize,
     include_body: bool,
     modes: Option<&[SubfieldMode]>,
+    register: &TokenStream,
     library_path: &proc_macro2::TokenStream,
     orig_ident: Option<&Ident>,
     generics: &Generics,
@@ -291,6 +418,7 @@ fn field_to_tokens(
                     return if include_body {
                         quote! {
                             #signature {
+                                #register
                                 #library_path::KeyedSubfield::new(
                                     self,
                                     #idx.into(),
@@ -320,6 +448,7 @@ fn field_to_tokens(
     if include_body {
         quote! {
             fn #ident(self) ->  #library_path::Subfield<#any_store_field, #name #generics, #ty> {
+                #register
                 #library_path::Subfield::new(
                     self,
                     #idx.into(),
@@ -541,13 +670,8 @@ struct PatchModel {
 }
 
 enum PatchModelTy {
-    Struct {
-        fields: Vec<Field>,
-    },
-    #[allow(dead_code)]
-    Enum {
-        variants: Vec<Variant>,
-    },
+    Struct { fields: Vec<Field> },
+    Enum { variants: Vec<Variant> },
 }
 
 impl Parse for PatchModel {
@@ -570,17 +694,12 @@ impl Parse for PatchModel {
 
                 PatchModelTy::Struct { fields }
             }
-            syn::Data::Enum(_e) => {
-                abort_call_site!("only structs can be used with `Patch`");
-
-                // TODO: support enums later on
-                // PatchModelTy::Enum {
-                //     variants: e.variants.into_iter().collect(),
-                // }
-            }
+            syn::Data::Enum(e) => PatchModelTy::Enum {
+                variants: e.variants.into_iter().collect(),
+            },
             _ => {
                 abort_call_site!(
-                    "only structs and enums can be used with `Store`"
+                    "only structs and enums can be used with `Patch`"
                 );
             }
         };
@@ -598,71 +717,122 @@ impl ToTokens for PatchModel {
         let library_path = quote! { reactive_stores };
         let PatchModel { name, generics, ty } = &self;
 
-        let fields = match ty {
+        let body = match ty {
             PatchModelTy::Struct { fields } => {
-                fields.iter().enumerate().map(|(idx, field)| {
-                    let Field {
-                        attrs, ident, ..
-                    } = &field;
+                let register = serialized_fields(fields).map(|serialized| {
+                    quote! {
+                        if let Some(keys) = keys {
+                            keys.set_serialized_fields(path.clone(), &[#(#serialized),*]);
+                        }
+                    }
+                });
+                let fields = fields.iter().enumerate().map(|(idx, field)| {
+                    let Field { attrs, ident, .. } = &field;
                     let field_name = match &ident {
                         Some(ident) => quote! { #ident },
-                        None => quote! { #idx },
+                        None => {
+                            let idx = Index::from(idx);
+                            quote! { #idx }
+                        }
                     };
-                    let closure = attrs
+                    let patch = patch_field_tokens(
+                        &library_path,
+                        attrs,
+                        quote! { self.#field_name },
+                        quote! { new.#field_name },
+                    );
+                    quote! {
+                        #patch
+                        new_path.replace_last(#idx + 1);
+                    }
+                });
+                quote! {
+                    #register
+                    let mut new_path = path.clone();
+                    new_path.push(0);
+                    #(#fields)*
+                }
+            }
+            PatchModelTy::Enum { variants } => {
+                // the fields of every variant share the same path segment, so if the
+                // variant is unchanged, each field is patched at that path; if the
+                // variant has changed, the whole enum is replaced and notified
+                let arms = variants.iter().map(|variant| {
+                    let Variant { ident, fields, .. } = variant;
+                    let (this_idents, new_idents): (Vec<_>, Vec<_>) = fields
                         .iter()
-                        .find_map(|attr| {
-                            attr.meta.path().is_ident("patch").then(
-                                || match &attr.meta {
-                                    Meta::List(list) => {
-                                        match Punctuated::<
-                                                ExprClosure,
-                                                Comma,
-                                            >::parse_terminated
-                                                .parse2(list.tokens.clone())
-                                            {
-                                                Ok(closures) => {
-                                                    let closure = closures.iter().next().cloned().expect_or_abort("should have ONE closure");
-                                                    if closure.inputs.len() != 2 {
-                                                        abort!(closure.inputs, "patch closure should have TWO params as in #[patch(|this, new| ...)]");
-                                                    }
-                                                    closure
-                                                },
-                                                Err(e) => abort!(list, e),
-                                            }
-                                    }
-                                    _ => abort!(attr.meta, "needs to be as `#[patch(|this, new| ...)]`"),
-                                },
+                        .enumerate()
+                        .map(|(idx, _)| {
+                            (
+                                Ident::new(
+                                    &format!("this_{idx}"),
+                                    Span::call_site(),
+                                ),
+                                Ident::new(
+                                    &format!("new_{idx}"),
+                                    Span::call_site(),
+                                ),
+                            )
+                        })
+                        .unzip();
+                    let patches =
+                        fields.iter().zip(&this_idents).zip(&new_idents).map(
+                            |((field, this), new)| {
+                                patch_field_tokens(
+                                    &library_path,
+                                    &field.attrs,
+                                    quote! { (*#this) },
+                                    quote! { #new },
+                                )
+                            },
+                        );
+                    let (this_pat, new_pat) = match fields {
+                        Fields::Unit => (quote! {}, quote! {}),
+                        Fields::Named(fields) => {
+                            let names = fields
+                                .named
+                                .iter()
+                                .map(|field| field.ident.as_ref().unwrap())
+                                .collect::<Vec<_>>();
+                            (
+                                quote! { { #(#names: #this_idents),* } },
+                                quote! { { #(#names: #new_idents),* } },
                             )
-                        });
-
-                    if let Some(closure) = closure {
-                        let params = closure.inputs;
-                        let body = closure.body;
-                        quote! {
-                            if new.#field_name != self.#field_name {
-                                _ = {
-                                    let (#params) = (&mut self.#field_name, new.#field_name);
-                                    #body
-                                };
-                                notify(&new_path);
-                            }
-                            new_path.replace_last(#idx + 1);
                         }
-                    } else {
-                        quote! {
-                            #library_path::PatchField::patch_field(
-                                &mut self.#field_name,
-                                new.#field_name,
-                                &new_path,
-                                notify
-                            );
-                            new_path.replace_last(#idx + 1);
+                        Fields::Unnamed(_) => (
+                            quote! { (#(#this_idents),*) },
+                            quote! { (#(#new_idents),*) },
+                        ),
+                    };
+                    quote! {
+                        (#name::#ident #this_pat, #name::#ident #new_pat) => {
+                            #(#patches)*
                         }
                     }
-                }).collect::<Vec<_>>()
-            }
-            PatchModelTy::Enum { variants: _ } => {
-                unreachable!("not implemented currently")
+                });
+                let new_path = if variants
+                    .iter()
+                    .any(|variant| !variant.fields.is_empty())
+                {
+                    quote! {
+                        let mut new_path = path.clone();
+                        new_path.push(0);
+                    }
+                } else {
+                    // only unit variants, so there are no fields to patch
+                    quote! { _ = keys; }
+                };
+                quote! {
+                    #new_path
+                    match (self, new) {
+                        #(#arms)*
+                        #[allow(unreachable_patterns)]
+                        (this, new) => {
+                            *this = new;
+                            notify(path);
+                        }
+                    }
+                }
             }
         };
 
@@ -676,11 +846,98 @@ impl ToTokens for PatchModel {
                     path: &#library_path::StorePath,
                     notify: &mut dyn FnMut(&#library_path::StorePath),
                 ) {
-                    let mut new_path = path.clone();
-                    new_path.push(0);
-                    #(#fields)*
+                    #library_path::PatchField::patch_field_keyed(
+                        self,
+                        new,
+                        path,
+                        notify,
+                        None
+                    );
+                }
+
+                fn patch_field_keyed(
+                    &mut self,
+                    new: Self,
+                    path: &#library_path::StorePath,
+                    notify: &mut dyn FnMut(&#library_path::StorePath),
+                    keys: Option<&#library_path::KeyMap>,
+                ) {
+                    #body
                 }
             }
         });
     }
 }
+
+/// Generates the code to patch a single field, where `this` and `new` are the place expressions
+/// for the current field and its new value.
+fn patch_field_tokens(
+    library_path: &TokenStream,
+    attrs: &[Attribute],
+    this: TokenStream,
+    new: TokenStream,
+) -> TokenStream {
+    let closure = attrs.iter().find_map(|attr| {
+        attr.meta.path().is_ident("patch").then(|| match &attr.meta {
+            Meta::List(list) => {
+                match Punctuated::<ExprClosure, Comma>::parse_terminated
+                    .parse2(list.tokens.clone())
+                {
+                    Ok(closures) => {
+                        let closure = closures
+                            .iter()
+                            .next()
+                            .cloned()
+                            .expect_or_abort("should have ONE closure");
+                        if closure.inputs.len() != 2 {
+                            abort!(closure.inputs, "patch closure should have TWO params as in #[patch(|this, new| ...)]");
+                        }
+                        closure
+                    }
+                    Err(e) => abort!(list, e),
+                }
+            }
+            _ => abort!(
+                attr.meta,
+                "needs to be as `#[patch(|this, new| ...)]`"
+            ),
+        })
+    });
+
+    if let Some(closure) = closure {
+        let params = closure.inputs;
+        let body = closure.body;
+        quote! {
+            if #new != #this {
+                _ = {
+                    let (#params) = (&mut #this, #new);
+                    #body
+                };
+                notify(&new_path);
+            }
+        }
+    } else if let Some(SubfieldMode::Keyed(keyed_by, key_ty)) =
+        store_modes(attrs).and_then(|modes| modes.into_iter().next())
+    {
+        quote! {
+            #library_path::patch_keyed::<_, #key_ty>(
+                &mut #this,
+                #new,
+                #keyed_by,
+                &new_path,
+                notify,
+                keys
+            );
+        }
+    } else {
+        quote! {
+            #library_path::PatchField::patch_field_keyed(
+                &mut #this,
+                #new,
+                &new_path,
+                notify,
+                keys
+            );
+        }
+    }
+}
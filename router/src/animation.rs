@@ -0,0 +1,320 @@
+use crate::{
+    location::{BrowserUrl, LocationProvider},
+    nested_router::RouteContext,
+    view_transition::start_view_transition,
+};
+use leptos::{ev, prelude::*};
+use or_poisoned::OrPoisoned;
+use reactive_graph::{
+    effect::RenderEffect,
+    signal::{ArcRwSignal, ArcTrigger},
+    traits::{GetUntracked, Notify, Set, Track},
+};
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tachys::view::any_view::IntoAny;
+
+/// Describes how the views inside an [`Outlet`](crate::components::Outlet) should be animated
+/// when navigating between routes.
+///
+/// When a route animation is set on [`Routes`](crate::components::Routes) or
+/// [`ParentRoute`](crate::components::ParentRoute), the outgoing view is kept mounted while
+/// the incoming view is rendered next to it. Each view is wrapped in a `<div>` with the base
+/// `class`; the entering view additionally receives the `enter` class and the outgoing view the
+/// `exit` class. The outgoing view is unmounted as soon as its wrapper fires `animationend` or
+/// `transitionend`, or once `timeout` has elapsed, whichever comes first.
+///
+/// Because both views are mounted at the same time, you will usually want to position them on
+/// top of one another, for example with `display: grid` on the parent and `grid-area: 1 / 1` on
+/// the wrappers.
+///
+/// ```rust
+/// # use leptos::prelude::*;
+/// # use leptos_router::{animation::RouteAnimation, components::*, path};
+/// # use std::time::Duration;
+/// # #[component]
+/// # fn App() -> impl IntoView {
+/// view! {
+///     <Router>
+///         <Routes
+///             fallback=|| "Not found."
+///             animation=RouteAnimation::new()
+///                 .class("page")
+///                 .enter("slide-in")
+///                 .exit("slide-out")
+///                 .enter_back("slide-in-back")
+///                 .exit_back("slide-out-back")
+///                 .timeout(Duration::from_millis(300))
+///         >
+///             <Route path=path!("") view=|| "Home"/>
+///             <Route path=path!("about") view=|| "About"/>
+///         </Routes>
+///     </Router>
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RouteAnimation {
+    class: &'static str,
+    enter: &'static str,
+    exit: &'static str,
+    enter_back: Option<&'static str>,
+    exit_back: Option<&'static str>,
+    timeout: Duration,
+    view_transition: bool,
+}
+
+impl Default for RouteAnimation {
+    fn default() -> Self {
+        Self {
+            class: "",
+            enter: "",
+            exit: "",
+            enter_back: None,
+            exit_back: None,
+            timeout: Duration::from_millis(300),
+            view_transition: false,
+        }
+    }
+}
+
+impl RouteAnimation {
+    /// Creates a new route animation with no classes and a timeout of 300ms.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets a class that is always applied to the wrapper around each route view.
+    pub fn class(mut self, class: &'static str) -> Self {
+        self.class = class;
+        self
+    }
+
+    /// Sets the class applied to the incoming view.
+    pub fn enter(mut self, class: &'static str) -> Self {
+        self.enter = class;
+        self
+    }
+
+    /// Sets the class applied to the outgoing view.
+    pub fn exit(mut self, class: &'static str) -> Self {
+        self.exit = class;
+        self
+    }
+
+    /// Sets the class applied to the incoming view during a back navigation.
+    /// Defaults to the [`enter`](Self::enter) class.
+    pub fn enter_back(mut self, class: &'static str) -> Self {
+        self.enter_back = Some(class);
+        self
+    }
+
+    /// Sets the class applied to the outgoing view during a back navigation.
+    /// Defaults to the [`exit`](Self::exit) class.
+    pub fn exit_back(mut self, class: &'static str) -> Self {
+        self.exit_back = Some(class);
+        self
+    }
+
+    /// Sets the maximum amount of time the outgoing view stays mounted if no
+    /// `animationend` or `transitionend` event is fired.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Whether to swap the views inside a call to the View Transition API, if it is supported.
+    pub fn view_transition(mut self, view_transition: bool) -> Self {
+        self.view_transition = view_transition;
+        self
+    }
+
+    fn classes(&self, is_back: bool) -> (&'static str, &'static str) {
+        if is_back {
+            (
+                self.enter_back.unwrap_or(self.enter),
+                self.exit_back.unwrap_or(self.exit),
+            )
+        } else {
+            (self.enter, self.exit)
+        }
+    }
+
+    fn wrapper_class(&self, state: &str) -> String {
+        match (self.class.is_empty(), state.is_empty()) {
+            (true, _) => state.to_string(),
+            (false, true) => self.class.to_string(),
+            (false, false) => format!("{} {state}", self.class),
+        }
+    }
+}
+
+/// One of the two places an animated outlet can render a route view into.
+///
+/// While navigating, the incoming view is rendered into the free slot and the outgoing view stays
+/// in its slot until its exit animation has finished.
+#[derive(Clone)]
+struct AnimatedSlot {
+    trigger: ArcTrigger,
+    filled: Arc<AtomicBool>,
+    leaving: Arc<AtomicBool>,
+    generation: Arc<AtomicUsize>,
+    class: ArcRwSignal<String>,
+}
+
+impl AnimatedSlot {
+    fn new(filled: bool, class: String) -> Self {
+        Self {
+            trigger: ArcTrigger::new(),
+            filled: Arc::new(AtomicBool::new(filled)),
+            leaving: Arc::new(AtomicBool::new(false)),
+            generation: Arc::new(AtomicUsize::new(0)),
+            class: ArcRwSignal::new(class),
+        }
+    }
+
+    fn enter(&self, class: String) {
+        self.generation.fetch_add(1, Ordering::Relaxed);
+        self.leaving.store(false, Ordering::Relaxed);
+        self.filled.store(true, Ordering::Relaxed);
+        self.class.set(class);
+        self.trigger.notify();
+    }
+
+    fn leave(&self, class: String) -> usize {
+        self.leaving.store(true, Ordering::Relaxed);
+        self.class.set(class);
+        self.generation.load(Ordering::Relaxed)
+    }
+
+    /// Unmounts the view in this slot, if it is still leaving and has not been reused since.
+    fn finish(&self, generation: Option<usize>) {
+        let is_current = generation.map_or(true, |generation| {
+            self.generation.load(Ordering::Relaxed) == generation
+        });
+        if is_current && self.leaving.swap(false, Ordering::Relaxed) {
+            self.filled.store(false, Ordering::Relaxed);
+            self.trigger.notify();
+        }
+    }
+
+    fn view(&self, ctx: &RouteContext) -> impl IntoView {
+        let slot = self.clone();
+        let view_fn = Arc::clone(&ctx.view_fn);
+        move || {
+            slot.trigger.track();
+            slot.filled.load(Ordering::Relaxed).then(|| {
+                let view = (view_fn.lock().or_poisoned())();
+                let class = slot.class.clone();
+                let on_end = {
+                    let slot = slot.clone();
+                    move |target: Option<web_sys::EventTarget>,
+                          current: Option<web_sys::EventTarget>| {
+                        // ignore animations that bubble up from inside the route view
+                        if target == current {
+                            slot.finish(None);
+                        }
+                    }
+                };
+                let on_transition_end = on_end.clone();
+                view! {
+                    <div
+                        class=move || class.get()
+                        on:animationend=move |ev: ev::AnimationEvent| {
+                            on_end(ev.target(), ev.current_target())
+                        }
+                        on:transitionend=move |ev: ev::TransitionEvent| {
+                            on_transition_end(ev.target(), ev.current_target())
+                        }
+                    >
+                        {view}
+                    </div>
+                }
+            })
+        }
+    }
+}
+
+/// Renders the view of the route in `ctx`, keeping the outgoing view mounted while the
+/// animation runs whenever the route changes.
+pub(crate) fn animated_outlet(
+    ctx: RouteContext,
+    animation: RouteAnimation,
+) -> impl IntoView {
+    let slots = [
+        AnimatedSlot::new(true, animation.wrapper_class("")),
+        AnimatedSlot::new(false, animation.wrapper_class("")),
+    ];
+    let active = Arc::new(AtomicUsize::new(0));
+    let is_back = use_context::<BrowserUrl>().map(|loc| loc.is_back());
+    let level = ctx.level;
+
+    let effect = RenderEffect::new({
+        let slots = slots.clone();
+        let trigger = ctx.trigger.clone();
+        move |prev: Option<()>| {
+            trigger.track();
+            if prev.is_none() {
+                return;
+            }
+
+            let is_back = is_back
+                .map(|is_back| is_back.get_untracked())
+                .unwrap_or(false);
+            let (enter, exit) = animation.classes(is_back);
+            let prev_idx = active.fetch_xor(1, Ordering::Relaxed);
+            let entering = slots[prev_idx ^ 1].clone();
+            let leaving = slots[prev_idx].clone();
+
+            let swap = move || {
+                entering.enter(animation.wrapper_class(enter));
+                let generation = leaving.leave(animation.wrapper_class(exit));
+                set_timeout(
+                    move || leaving.finish(Some(generation)),
+                    animation.timeout,
+                );
+            };
+            if animation.view_transition {
+                start_view_transition(level, is_back, swap);
+            } else {
+                swap();
+            }
+        }
+    });
+    on_cleanup(move || drop(effect));
+
+    let [first, second] = &slots;
+    (first.view(&ctx), second.view(&ctx)).into_any()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RouteAnimation;
+
+    #[test]
+    fn back_classes_fall_back_to_forward_classes() {
+        let animation = RouteAnimation::new().enter("in").exit("out");
+        assert_eq!(animation.classes(false), ("in", "out"));
+        assert_eq!(animation.classes(true), ("in", "out"));
+
+        let animation = animation.enter_back("in-back").exit_back("out-back");
+        assert_eq!(animation.classes(false), ("in", "out"));
+        assert_eq!(animation.classes(true), ("in-back", "out-back"));
+    }
+
+    #[test]
+    fn wrapper_class_includes_base_class() {
+        let animation = RouteAnimation::new();
+        assert_eq!(animation.wrapper_class(""), "");
+        assert_eq!(animation.wrapper_class("in"), "in");
+
+        let animation = animation.class("page");
+        assert_eq!(animation.wrapper_class(""), "page");
+        assert_eq!(animation.wrapper_class("in"), "page in");
+    }
+}
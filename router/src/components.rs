@@ -3,15 +3,20 @@ pub use super::{form::*, link::*};
 use crate::location::RequestUrl;
 pub use crate::nested_router::Outlet;
 use crate::{
+    animation::RouteAnimation,
     flat_router::FlatRoutesView,
     hooks::use_navigate,
+    i18n::{negotiate_locale, Locales},
     location::{
-        BrowserUrl, Location, LocationChange, LocationProvider, State, Url,
+        BrowserUrl, Location, LocationChange, LocationProvider, SlotLocation,
+        State, Url,
     },
     navigate::NavigateOptions,
     nested_router::NestedRoutesView,
+    prefetch::{prefetcher, Prefetcher},
     resolve_path::resolve_path,
-    ChooseView, MatchNestedRoutes, NestedRoute, RouteDefs, SsrMode,
+    strip_base, ChooseView, MatchNestedRoutes, NestedRoute, PrefetchFn,
+    RouteDefs, SsrMode,
 };
 use either_of::EitherOf3;
 use leptos::{children, prelude::*};
@@ -23,9 +28,13 @@ use reactive_graph::{
 };
 use std::{
     borrow::Cow,
+    collections::HashSet,
     fmt::{Debug, Display},
     mem,
-    sync::Arc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
     time::Duration,
 };
 use tachys::view::any_view::AnyView;
@@ -59,6 +68,10 @@ pub fn Router<Chil>(
     /// A signal that will be set while the navigation process is underway.
     #[prop(optional, into)]
     set_is_routing: Option<SignalSetter<bool>>,
+    /// The locales supported by the app. When set, every route can also be reached behind a
+    /// locale prefix, like `/fr/about`. See [`Locales`].
+    #[prop(optional)]
+    locales: Option<Locales>,
     // TODO trailing slashes
     ///// How trailing slashes should be handled in [`Route`] paths.
     //#[prop(optional)]
@@ -104,6 +117,28 @@ where
     // set server function redirect hook
     _ = server_fn::redirect::set_redirect_hook(redirect_hook);
 
+    // the locale is taken from the path, falling back to the negotiated locale
+    let locale = locales.clone().map(|locales| {
+        let negotiated = negotiate_locale(&locales);
+        let base = base.clone().unwrap_or_default();
+        let current_url = current_url.clone();
+        ArcMemo::new(move |_| {
+            current_url.with(|url| {
+                let path = strip_base(&base, url.path()).unwrap_or(url.path());
+                locales
+                    .split_path(path)
+                    .0
+                    .unwrap_or(&negotiated)
+                    .to_string()
+            })
+        })
+    });
+
+    let slot = location_provider
+        .as_ref()
+        .map(|location: &BrowserUrl| location.slot.clone())
+        .unwrap_or_default();
+
     provide_context(RouterContext {
         base,
         current_url,
@@ -112,6 +147,11 @@ where
         set_is_routing,
         query_mutations: Default::default(),
         location_provider,
+        prefetcher: Default::default(),
+        prefetched: Default::default(),
+        locales,
+        locale,
+        slot,
     });
 
     let children = children.into_inner();
@@ -128,10 +168,25 @@ pub(crate) struct RouterContext {
     pub query_mutations:
         ArcStoredValue<Vec<(Oco<'static, str>, Option<String>)>>,
     pub location_provider: Option<BrowserUrl>,
+    pub prefetcher: ArcStoredValue<Option<Prefetcher>>,
+    pub prefetched: ArcStoredValue<HashSet<String>>,
+    pub locales: Option<Locales>,
+    pub locale: Option<ArcMemo<String>>,
+    pub slot: ArcRwSignal<Option<SlotLocation>>,
 }
 
 impl RouterContext {
     pub fn navigate(&self, path: &str, options: NavigateOptions) {
+        self.navigate_in_slot(path, options, None);
+    }
+
+    /// Navigates to `path`, rendering it in the [`RouteSlot`] named `slot`, if any.
+    pub fn navigate_in_slot(
+        &self,
+        path: &str,
+        options: NavigateOptions,
+        slot: Option<Cow<'static, str>>,
+    ) {
         let current = self.current_url.read_untracked();
         let resolved_to = if options.resolve {
             resolve_path(
@@ -182,6 +237,17 @@ impl RouterContext {
             self.state.set(options.state.clone());
         }
 
+        // update the slot before the URL, so that the routes never render the new URL in the
+        // wrong place
+        let slot = SlotLocation::next(
+            self.slot.read_untracked().as_ref(),
+            slot,
+            &current,
+        );
+        if slot != *self.slot.read_untracked() {
+            self.slot.set(slot);
+        }
+
         // update URL signal, if necessary
         let value = url.to_full_path();
         if current != url {
@@ -226,6 +292,10 @@ pub fn Routes<Defs, FallbackFn, Fallback>(
     /// Whether to use the View Transition API during navigation.
     #[prop(optional)]
     transition: bool,
+    /// Animates navigations between top-level routes, keeping the outgoing view mounted
+    /// until its exit animation has finished.
+    #[prop(optional)]
+    animation: Option<RouteAnimation>,
     /// The route definitions. This should consist of one or more [`ParentRoute`] or [`Route`]
     /// components.
     children: RouteChildren<Defs>,
@@ -240,6 +310,9 @@ where
         current_url,
         base,
         set_is_routing,
+        prefetcher: router_prefetcher,
+        locales,
+        slot,
         ..
     } = use_context()
         .expect("<Routes> should be used inside a <Router> component");
@@ -251,11 +324,15 @@ where
     let routes = RouteDefs::new_with_base(
         children.into_inner(),
         base.clone().unwrap_or_default(),
-    );
+    )
+    .with_locales(locales);
     let outer_owner =
         Owner::current().expect("creating Routes, but no Owner was found");
+    *router_prefetcher.write_value() =
+        Some(prefetcher(routes.clone(), outer_owner.clone()));
+    let main_url = main_url(&current_url, &slot);
     move || {
-        current_url.track();
+        let current_url = url_signal(&current_url, main_url.get());
         outer_owner.with(|| {
             current_url.read_untracked().provide_server_action_error()
         });
@@ -268,6 +345,7 @@ where
             fallback: fallback.clone(),
             set_is_routing,
             transition,
+            animation,
         }
     }
 }
@@ -293,6 +371,9 @@ where
         current_url,
         base,
         set_is_routing,
+        prefetcher: router_prefetcher,
+        locales,
+        slot,
         ..
     } = use_context()
         .expect("<FlatRoutes> should be used inside a <Router> component");
@@ -307,13 +388,17 @@ where
     let routes = RouteDefs::new_with_base(
         children.into_inner(),
         base.clone().unwrap_or_default(),
-    );
+    )
+    .with_locales(locales);
 
     let outer_owner =
         Owner::current().expect("creating Router, but no Owner was found");
+    *router_prefetcher.write_value() =
+        Some(prefetcher(routes.clone(), outer_owner.clone()));
+    let main_url = main_url(&current_url, &slot);
 
     move || {
-        current_url.track();
+        let current_url = url_signal(&current_url, main_url.get());
         outer_owner.with(|| {
             current_url.read_untracked().provide_server_action_error()
         });
@@ -329,6 +414,145 @@ where
     }
 }
 
+/// The URL rendered by the main routes: while a [`RouteSlot`] is open, this is the location
+/// that was open before navigating into the slot.
+fn main_url(
+    current_url: &ArcRwSignal<Url>,
+    slot: &ArcRwSignal<Option<SlotLocation>>,
+) -> ArcMemo<Url> {
+    let current_url = current_url.clone();
+    let slot = slot.clone();
+    ArcMemo::new(move |_| match &*slot.read() {
+        Some(slot) => slot.background.clone(),
+        None => current_url.get(),
+    })
+}
+
+/// Reuses the router's URL signal if `url` is the current URL.
+fn url_signal(current_url: &ArcRwSignal<Url>, url: Url) -> ArcRwSignal<Url> {
+    if *current_url.read_untracked() == url {
+        current_url.clone()
+    } else {
+        ArcRwSignal::new(url)
+    }
+}
+
+/// Renders routes into a named slot, on top of the main [`Routes`].
+///
+/// A navigation can target a slot by setting `route_slot` on an [`A`], or by navigating with the
+/// function returned by [`use_navigate_in_slot`](crate::hooks::use_navigate_in_slot). The current location is then matched against
+/// the routes in the slot with the same name, while the main routes keep rendering the location
+/// that was open before the navigation, so their state is preserved. This makes it possible to,
+/// for example, open `/photos/:id` as a modal over `/feed`, while keeping a shareable URL.
+///
+/// The slot is stored in the history state, so it is restored during back and forward
+/// navigations. When a page is loaded directly, or the navigation did not target a slot, the
+/// slot renders nothing and the main routes render the full page, so they should also include
+/// any routes that can be opened in a slot.
+///
+/// For a navigation into a slot, the value stored in `window.history.state` is an object of
+/// the form `{ leptosRouteSlot, leptosBackground, state }`, where `state` is the
+/// [`state`](crate::NavigateOptions::state) passed to the navigation. This does not affect
+/// [`use_location`](crate::hooks::use_location), but code that reads `window.history.state`
+/// directly needs to read the `state` key of that object.
+///
+/// ```rust
+/// # use leptos::prelude::*;
+/// # use leptos_router::{components::*, path};
+/// # #[component]
+/// # fn App() -> impl IntoView {
+/// view! {
+///     <Router>
+///         <Routes fallback=|| "Not found.">
+///             <Route path=path!("feed") view=|| view! {
+///                 <A href="/photos/1" route_slot="modal">"Photo"</A>
+///             }/>
+///             <Route path=path!("photos/:id") view=|| "Photo page"/>
+///         </Routes>
+///         <RouteSlot name="modal">
+///             <Route path=path!("photos/:id") view=|| "Photo modal"/>
+///         </RouteSlot>
+///     </Router>
+/// }
+/// # }
+/// ```
+#[component(transparent)]
+pub fn RouteSlot<Defs>(
+    /// The name of the slot, which navigations use to target it.
+    #[prop(into)]
+    name: Cow<'static, str>,
+    /// Whether to use the View Transition API during navigation.
+    #[prop(optional)]
+    transition: bool,
+    /// Animates navigations between routes in the slot.
+    #[prop(optional)]
+    animation: Option<RouteAnimation>,
+    /// The route definitions. This should consist of one or more [`ParentRoute`] or [`Route`]
+    /// components.
+    children: RouteChildren<Defs>,
+) -> impl IntoView
+where
+    Defs: MatchNestedRoutes + Clone + Send + 'static,
+{
+    let location = use_context::<BrowserUrl>();
+    let RouterContext {
+        current_url,
+        base,
+        set_is_routing,
+        locales,
+        slot,
+        ..
+    } = use_context()
+        .expect("<RouteSlot> should be used inside a <Router> component");
+    let base = base.map(|base| {
+        let mut base = Oco::from(base);
+        base.upgrade_inplace();
+        base
+    });
+    let routes = RouteDefs::new_with_base(
+        children.into_inner(),
+        base.clone().unwrap_or_default(),
+    )
+    .with_locales(locales);
+    let outer_owner =
+        Owner::current().expect("creating RouteSlot, but no Owner was found");
+
+    let slot_url = ArcMemo::new({
+        let current_url = current_url.clone();
+        move |_| {
+            slot.read()
+                .as_ref()
+                .filter(|slot| slot.slot == name)
+                .map(|_| current_url.get())
+        }
+    });
+    let is_open = Arc::new(AtomicBool::new(false));
+    move || {
+        let Some(url) = slot_url.get() else {
+            is_open.store(false, Ordering::Relaxed);
+            return None;
+        };
+        // the nested router only completes navigations when it is rebuilt, so the first
+        // navigation into the slot has to be completed here
+        if !is_open.swap(true, Ordering::Relaxed) {
+            if let Some(location) = &location {
+                location.ready_to_complete();
+            }
+        }
+        Some(NestedRoutesView {
+            location: location.clone(),
+            routes: routes.clone(),
+            outer_owner: outer_owner.clone(),
+            current_url: url_signal(&current_url, url),
+            base: base.clone(),
+            fallback: || (),
+            set_is_routing,
+            transition,
+            animation,
+        })
+    }
+}
+
 /// Describes a portion of the nested layout of the app, specifying the route it should match
 /// and the element it should display.
 #[component(transparent)]
@@ -344,11 +568,16 @@ pub fn Route<Segments, View>(
     /// Defaults to out-of-order streaming.
     #[prop(optional)]
     ssr: SsrMode,
+    /// Warms the data for this route when a link to it is prefetched.
+    #[prop(optional, into)]
+    prefetch: Option<PrefetchFn>,
 ) -> NestedRoute<Segments, (), (), View>
 where
     View: ChooseView,
 {
-    NestedRoute::new(path, view).ssr_mode(ssr)
+    NestedRoute::new(path, view)
+        .ssr_mode(ssr)
+        .prefetch(prefetch)
 }
 
 /// Describes a portion of the nested layout of the app, specifying the route it should match
@@ -368,12 +597,22 @@ pub fn ParentRoute<Segments, View, Children>(
     /// Defaults to out-of-order streaming.
     #[prop(optional)]
     ssr: SsrMode,
+    /// Animates navigations between the child routes rendered in this route's [`Outlet`].
+    #[prop(optional)]
+    animation: Option<RouteAnimation>,
+    /// Warms the data for this route when a link to it is prefetched.
+    #[prop(optional, into)]
+    prefetch: Option<PrefetchFn>,
 ) -> NestedRoute<Segments, Children, (), View>
 where
     View: ChooseView,
 {
     let children = children.into_inner();
-    NestedRoute::new(path, view).ssr_mode(ssr).child(children)
+    NestedRoute::new(path, view)
+        .ssr_mode(ssr)
+        .child(children)
+        .animation(animation)
+        .prefetch(prefetch)
 }
 
 /// Describes a route that is guarded by a certain condition. This works the same way as
@@ -463,6 +702,9 @@ pub fn ProtectedParentRoute<Segments, ViewFn, View, C, PathFn, P, Children>(
     /// Defaults to out-of-order streaming.
     #[prop(optional)]
     ssr: SsrMode,
+    /// Animates navigations between the child routes rendered in this route's [`Outlet`].
+    #[prop(optional)]
+    animation: Option<RouteAnimation>,
 ) -> NestedRoute<Segments, Children, (), impl Fn() -> AnyView + Send + Clone>
 where
     ViewFn: Fn() -> View + Send + Clone + 'static,
@@ -505,7 +747,10 @@ where
         };
         (view! { <Transition fallback>{view}</Transition> }).into_any()
     };
-    NestedRoute::new(path, view).ssr_mode(ssr).child(children)
+    NestedRoute::new(path, view)
+        .ssr_mode(ssr)
+        .child(children)
+        .animation(animation)
 }
 
 /// Redirects the user to a new URL, whether on the client side or on the server
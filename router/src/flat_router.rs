@@ -4,8 +4,7 @@ use crate::{
     matching::{MatchParams, RouteDefs},
     params::ParamsMap,
     view_transition::start_view_transition,
-    ChooseView, MatchInterface, MatchNestedRoutes, PathSegment, RouteList,
-    RouteListing, RouteMatchId,
+    ChooseView, MatchInterface, MatchNestedRoutes, RouteList, RouteMatchId,
 };
 use any_spawner::Executor;
 use either_of::Either;
@@ -18,7 +17,7 @@ use reactive_graph::{
     transition::AsyncTransition,
     wrappers::write::SignalSetter,
 };
-use std::{cell::RefCell, iter, mem, rc::Rc};
+use std::{cell::RefCell, mem, rc::Rc};
 use tachys::{
     hydration::Cursor,
     reactive_graph::OwnedView,
@@ -439,27 +438,7 @@ where
         // if this is being run on the server for the first time, generating all possible routes
         if RouteList::is_generating() {
             // add routes
-            let (base, routes) = self.routes.generate_routes();
-            let routes = routes
-                .into_iter()
-                .map(|data| {
-                    let path = base
-                        .into_iter()
-                        .flat_map(|base| {
-                            iter::once(PathSegment::Static(
-                                base.to_string().into(),
-                            ))
-                        })
-                        .chain(data.segments)
-                        .collect::<Vec<_>>();
-                    RouteListing::new(
-                        path,
-                        data.ssr_mode,
-                        data.methods,
-                        data.regenerate,
-                    )
-                })
-                .collect::<Vec<_>>();
+            let routes = self.routes.generate_route_listings();
 
             // add fallback
             // TODO fix: causes overlapping route issues on Axum
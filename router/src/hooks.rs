@@ -13,6 +13,7 @@ use reactive_graph::{
     wrappers::write::SignalSetter,
 };
 use std::{
+    borrow::Cow,
     str::FromStr,
     sync::atomic::{AtomicBool, Ordering},
 };
@@ -278,6 +279,30 @@ pub fn use_navigate() -> impl Fn(&str, NavigateOptions) + Clone {
     move |path: &str, options: NavigateOptions| cx.navigate(path, options)
 }
 
+/// Returns a function that navigates to a new route, and renders it in the
+/// [`RouteSlot`](crate::components::RouteSlot) named `slot`, while the main routes keep rendering
+/// the current location.
+///
+/// This is the equivalent of setting `route_slot` on an [`A`](crate::components::A).
+///
+/// ```rust
+/// # if false { // can't actually navigate, no <Router/>
+/// let open_in_modal = leptos_router::hooks::use_navigate_in_slot("modal");
+/// open_in_modal("/photos/1", Default::default());
+/// # }
+/// ```
+#[track_caller]
+pub fn use_navigate_in_slot(
+    slot: impl Into<Cow<'static, str>>,
+) -> impl Fn(&str, NavigateOptions) + Clone {
+    let cx = use_context::<RouterContext>()
+        .expect("You cannot call `use_navigate_in_slot` outside a <Router>.");
+    let slot = slot.into();
+    move |path: &str, options: NavigateOptions| {
+        cx.navigate_in_slot(path, options, Some(slot.clone()))
+    }
+}
+
 /// Returns a reactive string that contains the route that was matched for
 /// this [`Route`](crate::components::Route).
 #[track_caller]
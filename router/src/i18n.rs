@@ -0,0 +1,377 @@
+use crate::{
+    components::RouterContext, hooks::use_location, matching::strip_base,
+};
+use leptos::prelude::*;
+use leptos_meta::Link;
+use reactive_graph::{computed::Memo, owner::Owner};
+use std::{borrow::Cow, sync::Arc};
+
+/// The locales supported by a [`Router`](crate::components::Router).
+///
+/// When locales are set on the router, every route can also be reached with a locale prefix
+/// (`/fr/about`, `/de/about`, …) without having to repeat the route tree. The prefix is stripped
+/// before matching, and the current locale is available from [`use_locale`].
+///
+/// Paths without a locale prefix (`/about`) are rendered in the locale negotiated for the user:
+/// on the server, from the locale cookie and the `Accept-Language` header (see
+/// [`LocalePreferences`]); in the browser, from the same cookie and `navigator.languages`. If
+/// none of the user's preferred locales are supported, the first supported locale is used.
+///
+/// ```rust
+/// # use leptos::prelude::*;
+/// # use leptos_router::{components::*, i18n::Locales, path};
+/// # #[component]
+/// # fn App() -> impl IntoView {
+/// view! {
+///     <Router locales=Locales::new(["en", "fr", "de"])>
+///         <Routes fallback=|| "Not found.">
+///             <Route path=path!("") view=|| "Home"/>
+///             <Route path=path!("about") view=|| "About"/>
+///         </Routes>
+///     </Router>
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Locales {
+    supported: Arc<[Cow<'static, str>]>,
+    cookie: Cow<'static, str>,
+}
+
+impl Locales {
+    /// Creates a set of supported locales. The first locale is used when the user's preferred
+    /// locale cannot be negotiated.
+    ///
+    /// # Panics
+    /// Panics if `supported` is empty.
+    pub fn new<T>(supported: impl IntoIterator<Item = T>) -> Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        let supported =
+            supported.into_iter().map(Into::into).collect::<Arc<[_]>>();
+        assert!(!supported.is_empty(), "at least one locale is required");
+        Self {
+            supported,
+            cookie: "locale".into(),
+        }
+    }
+
+    /// Sets the name of the cookie that stores the user's preferred locale. Defaults to `"locale"`.
+    pub fn cookie(mut self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.cookie = name.into();
+        self
+    }
+
+    /// The name of the cookie that stores the user's preferred locale.
+    pub fn cookie_name(&self) -> &str {
+        &self.cookie
+    }
+
+    /// The locale used when none of the user's preferred locales are supported.
+    pub fn default_locale(&self) -> &str {
+        &self.supported[0]
+    }
+
+    /// All supported locales, in the order they were declared.
+    pub fn supported(&self) -> impl Iterator<Item = &str> {
+        self.supported.iter().map(AsRef::as_ref)
+    }
+
+    /// Returns the supported locale equal to `locale`, ignoring ASCII case.
+    pub fn find(&self, locale: &str) -> Option<&str> {
+        self.supported().find(|l| l.eq_ignore_ascii_case(locale))
+    }
+
+    /// Splits a path into its locale prefix, if any, and the rest of the path.
+    ///
+    /// The remaining path always starts with a `/`.
+    pub fn split_path<'a>(&self, path: &'a str) -> (Option<&str>, &'a str) {
+        let trimmed = path.trim_start_matches('/');
+        let (first, rest) = match trimmed.find('/') {
+            Some(idx) => trimmed.split_at(idx),
+            None => (trimmed, ""),
+        };
+        match self.find(first) {
+            Some(locale) => {
+                (Some(locale), if rest.is_empty() { "/" } else { rest })
+            }
+            None => (None, path),
+        }
+    }
+
+    /// Returns `path` with its locale prefix replaced by `locale`.
+    pub fn localize_path(&self, locale: &str, path: &str) -> String {
+        let (_, rest) = self.split_path(path);
+        let rest = rest.trim_start_matches('/');
+        if rest.is_empty() {
+            format!("/{locale}")
+        } else {
+            format!("/{locale}/{rest}")
+        }
+    }
+
+    /// Chooses the best supported locale for a user with the given preferences.
+    ///
+    /// A supported locale in the cookie always wins. Otherwise, the languages in
+    /// `Accept-Language` are tried in order of their quality values, first as an exact match
+    /// (`fr-CA`) and then by their primary language (`fr`).
+    pub fn negotiate(&self, preferences: &LocalePreferences) -> &str {
+        let from_cookie = preferences
+            .cookie
+            .as_deref()
+            .and_then(|cookies| cookie_value(cookies, &self.cookie))
+            .and_then(|locale| self.find(locale));
+        if let Some(locale) = from_cookie {
+            return locale;
+        }
+
+        let mut languages = preferences
+            .accept_language
+            .as_deref()
+            .map(parse_accept_language)
+            .unwrap_or_default();
+        // stable sort, so that languages with equal quality keep their order
+        languages.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+        languages
+            .into_iter()
+            .filter(|(_, q)| *q > 0.0)
+            .find_map(|(lang, _)| {
+                self.find(lang).or_else(|| {
+                    let primary = lang.split('-').next().unwrap_or(lang);
+                    self.supported().find(|supported| {
+                        let supported =
+                            supported.split('-').next().unwrap_or(supported);
+                        supported.eq_ignore_ascii_case(primary)
+                    })
+                })
+            })
+            .unwrap_or_else(|| self.default_locale())
+    }
+}
+
+fn cookie_value<'a>(cookies: &'a str, name: &str) -> Option<&'a str> {
+    cookies.split(';').find_map(|cookie| {
+        let (key, value) = cookie.trim().split_once('=')?;
+        (key == name).then(|| value.trim_matches('"'))
+    })
+}
+
+fn parse_accept_language(header: &str) -> Vec<(&str, f32)> {
+    header
+        .split(',')
+        .filter_map(|lang| {
+            let mut parts = lang.split(';');
+            let tag = parts.next()?.trim();
+            if tag.is_empty() || tag == "*" {
+                return None;
+            }
+            let q = parts
+                .find_map(|param| param.trim().strip_prefix("q="))
+                .and_then(|q| q.parse().ok())
+                .unwrap_or(1.0);
+            Some((tag, q))
+        })
+        .collect()
+}
+
+/// The locale preferences sent with the current request.
+///
+/// This is provided as context by the server integrations, and used by the
+/// [`Router`](crate::components::Router) to negotiate the locale for paths without a locale
+/// prefix.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LocalePreferences {
+    /// The value of the `Accept-Language` header.
+    pub accept_language: Option<String>,
+    /// The value of the `Cookie` header.
+    pub cookie: Option<String>,
+}
+
+impl LocalePreferences {
+    #[cfg(not(feature = "ssr"))]
+    fn from_browser() -> Self {
+        use js_sys::Reflect;
+        use wasm_bindgen::JsValue;
+
+        let cookie = Reflect::get(&document(), &JsValue::from_str("cookie"))
+            .ok()
+            .and_then(|cookie| cookie.as_string());
+        let accept_language = window()
+            .navigator()
+            .languages()
+            .iter()
+            .filter_map(|lang| lang.as_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        Self {
+            accept_language: Some(accept_language),
+            cookie,
+        }
+    }
+}
+
+/// Negotiates the locale used for paths without a locale prefix.
+///
+/// The locale negotiated on the server is serialized into the page, so that the browser hydrates
+/// with the same locale even if its own preferences differ.
+pub(crate) fn negotiate_locale(locales: &Locales) -> String {
+    let shared_context = Owner::current_shared_context();
+    let id = shared_context.as_ref().map(|sc| sc.next_id());
+
+    #[cfg(feature = "ssr")]
+    {
+        let preferences =
+            use_context::<LocalePreferences>().unwrap_or_default();
+        let locale = locales.negotiate(&preferences).to_string();
+        if let (Some(sc), Some(id)) = (shared_context, id) {
+            if sc.get_is_hydrating() {
+                let locale = locale.clone();
+                sc.write_async(id, Box::pin(async move { locale }));
+            }
+        }
+        locale
+    }
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        let hydrated = shared_context
+            .zip(id)
+            .and_then(|(sc, id)| sc.read_data(&id))
+            .and_then(|locale| locales.find(&locale).map(str::to_string));
+        hydrated.unwrap_or_else(|| {
+            locales
+                .negotiate(&LocalePreferences::from_browser())
+                .to_string()
+        })
+    }
+}
+
+/// Returns the current locale: the locale prefix of the current path if there is one, and the
+/// negotiated locale otherwise.
+///
+/// # Panics
+/// Panics if the [`Router`](crate::components::Router) was not given any
+/// [`Locales`].
+#[track_caller]
+pub fn use_locale() -> Memo<String> {
+    let RouterContext { locale, .. } =
+        use_context().expect("called use_locale outside a <Router>");
+    locale
+        .expect("called use_locale, but no locales were set on the <Router>")
+        .into()
+}
+
+/// Renders a `<link rel="alternate" hreflang="…">` tag in the `<head>` for each supported locale,
+/// pointing at the current page in that locale, and an `x-default` link to the page without a
+/// locale prefix.
+///
+/// This requires [`leptos_meta`] to be set up with `provide_meta_context()`.
+#[component]
+pub fn LocaleAlternates(
+    /// The origin used to make the links absolute, like `https://example.com`. Search engines
+    /// expect alternate links to be absolute URLs.
+    #[prop(optional, into)]
+    origin: Option<Oco<'static, str>>,
+) -> impl IntoView {
+    let RouterContext { base, locales, .. } =
+        use_context().expect("used <LocaleAlternates/> outside a <Router>");
+    let locales = locales.expect(
+        "used <LocaleAlternates/>, but no locales were set on the <Router>",
+    );
+    let location = use_location();
+    let origin = origin.unwrap_or_default();
+    let base = base.unwrap_or_default();
+
+    move || {
+        let pathname = location.pathname.get();
+        let path = strip_base(&base, &pathname).unwrap_or(&pathname);
+        let (_, path) = locales.split_path(path);
+        let base = base.trim_end_matches('/');
+        let href = |path: &str| {
+            let path = path.trim_end_matches('/');
+            let path = if path.is_empty() && base.is_empty() {
+                "/"
+            } else {
+                path
+            };
+            Oco::from(format!("{origin}{base}{path}"))
+        };
+        locales
+            .supported()
+            .map(|locale| (locale, href(&locales.localize_path(locale, path))))
+            .chain([("x-default", href(path))])
+            .map(|(locale, href)| {
+                view! {
+                    <Link
+                        rel="alternate"
+                        hreflang=Oco::from(locale.to_string())
+                        href=href
+                    />
+                }
+            })
+            .collect_view()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LocalePreferences, Locales};
+
+    fn prefs(
+        accept_language: Option<&str>,
+        cookie: Option<&str>,
+    ) -> LocalePreferences {
+        LocalePreferences {
+            accept_language: accept_language.map(Into::into),
+            cookie: cookie.map(Into::into),
+        }
+    }
+
+    #[test]
+    fn splits_locale_prefix() {
+        let locales = Locales::new(["en", "fr", "pt-BR"]);
+        assert_eq!(locales.split_path("/fr/about"), (Some("fr"), "/about"));
+        assert_eq!(locales.split_path("/fr"), (Some("fr"), "/"));
+        assert_eq!(locales.split_path("/pt-br/a/b"), (Some("pt-BR"), "/a/b"));
+        assert_eq!(locales.split_path("/about"), (None, "/about"));
+        assert_eq!(locales.split_path("/"), (None, "/"));
+        assert_eq!(locales.split_path("/french"), (None, "/french"));
+    }
+
+    #[test]
+    fn localizes_paths() {
+        let locales = Locales::new(["en", "fr"]);
+        assert_eq!(locales.localize_path("fr", "/about"), "/fr/about");
+        assert_eq!(locales.localize_path("fr", "/en/about"), "/fr/about");
+        assert_eq!(locales.localize_path("en", "/"), "/en");
+        assert_eq!(locales.localize_path("en", "/fr"), "/en");
+    }
+
+    #[test]
+    fn negotiates_from_cookie_first() {
+        let locales = Locales::new(["en", "fr", "de"]);
+        let prefs = prefs(Some("de"), Some("theme=dark; locale=fr"));
+        assert_eq!(locales.negotiate(&prefs), "fr");
+
+        let locales = locales.cookie("lang");
+        assert_eq!(locales.negotiate(&prefs), "de");
+    }
+
+    #[test]
+    fn negotiates_from_accept_language() {
+        let locales = Locales::new(["en", "fr", "de-AT"]);
+        assert_eq!(
+            locales
+                .negotiate(&prefs(Some("es;q=0.9, fr;q=0.8, en;q=0.5"), None)),
+            "fr"
+        );
+        assert_eq!(
+            locales.negotiate(&prefs(Some("fr-CA, en;q=0.9"), None)),
+            "fr"
+        );
+        assert_eq!(locales.negotiate(&prefs(Some("de-DE"), None)), "de-AT");
+        assert_eq!(locales.negotiate(&prefs(Some("fr;q=0, es"), None)), "en");
+        assert_eq!(locales.negotiate(&prefs(None, None)), "en");
+    }
+}
@@ -124,6 +124,8 @@
 #![cfg_attr(feature = "nightly", feature(auto_traits))]
 #![cfg_attr(feature = "nightly", feature(negative_impls))]
 
+/// Animated transitions between routes.
+pub mod animation;
 /// Components for route definition and for enhanced links and forms.
 pub mod components;
 /// An optimized "flat" router without nested routes.
@@ -132,6 +134,8 @@ mod form;
 mod generate_route_list;
 /// Hooks that can be used to access router state inside your components.
 pub mod hooks;
+/// Locale-prefixed routes and locale negotiation.
+pub mod i18n;
 mod link;
 /// Utilities for accessing the current location.
 pub mod location;
@@ -142,6 +146,9 @@ mod navigate;
 pub mod nested_router;
 /// Support for maps of parameters in the path or in the query.
 pub mod params;
+mod prefetch;
+/// Typed state stored in the URL query string.
+pub mod query_state;
 mod ssr_mode;
 /// Support for static routing.
 pub mod static_routes;
@@ -152,6 +159,7 @@ pub use leptos_router_macro::path;
 pub use matching::*;
 pub use method::*;
 pub use navigate::*;
+pub use prefetch::{Prefetch, PrefetchFn};
 pub use ssr_mode::*;
 
 pub(crate) mod view_transition {
@@ -1,5 +1,10 @@
-use crate::{components::RouterContext, hooks::use_resolved_path};
-use leptos::{children::Children, oco::Oco, prelude::*};
+use crate::{
+    components::RouterContext,
+    hooks::use_resolved_path,
+    prefetch::{use_prefetch, Prefetch},
+    strip_base,
+};
+use leptos::{children::Children, html, oco::Oco, prelude::*};
 use reactive_graph::{computed::ArcMemo, owner::use_context};
 use std::{borrow::Cow, rc::Rc};
 
@@ -76,6 +81,18 @@ where
 /// Previously, this component took these as component props. Now, they can be added using the
 /// `prop:` syntax, and will be added directly to the DOM. They can work with either `<a>` elements
 /// or the `<A/>` component.
+///
+/// ### Prefetching
+///
+/// Setting `prefetch` loads the code for the route this link points to (for lazy routes), and
+/// runs the `prefetch` function declared on that route, before the user clicks on the link.
+/// See [`Prefetch`] for the available strategies.
+///
+/// ### Locales
+///
+/// If the [`Router`](crate::components::Router) has [`Locales`](crate::i18n::Locales), links to
+/// paths without a locale prefix keep the current locale, so that `<A href="/about">` links to
+/// `/fr/about` while browsing in French. Set `locale` to link to the same path in another locale.
 #[component]
 pub fn A<H>(
     /// Used to calculate the link's `href` attribute. Will be resolved relative
@@ -95,6 +112,16 @@ pub fn A<H>(
     /// If `true`, the router will scroll to the top of the window at the end of navigation. Defaults to `true`.
     #[prop(default = true)]
     scroll: bool,
+    /// When to prefetch the code and data for the linked route. Defaults to never prefetching.
+    #[prop(optional)]
+    prefetch: Option<Prefetch>,
+    /// The name of a [`RouteSlot`](crate::components::RouteSlot) to open the linked route in,
+    /// while the main routes keep rendering the current page.
+    #[prop(optional, into)]
+    route_slot: Option<Oco<'static, str>>,
+    /// The locale to link to, if the router has locales. Defaults to the current locale.
+    #[prop(optional, into)]
+    locale: Option<Oco<'static, str>>,
     /// The nodes or elements to be shown inside the link.
     children: Children,
 ) -> impl IntoView
@@ -108,9 +135,15 @@ where
         children: Children,
         strict_trailing_slash: bool,
         scroll: bool,
+        prefetch: Option<Prefetch>,
+        route_slot: Option<Oco<'static, str>>,
     ) -> impl IntoView {
         let RouterContext { current_url, .. } =
             use_context().expect("tried to use <A/> outside a <Router/>.");
+        let node_ref = NodeRef::<html::A>::new();
+        if let Some(strategy) = prefetch {
+            use_prefetch(strategy, node_ref, href.clone());
+        }
         let is_active = {
             let href = href.clone();
             move || {
@@ -130,10 +163,12 @@ where
 
         view! {
             <a
+                node_ref=node_ref
                 href=move || href.get().unwrap_or_default()
                 target=target
                 aria-current=move || if is_active() { Some("page") } else { None }
                 data-noscroll=!scroll
+                data-route-slot=route_slot
             >
 
                 {children()}
@@ -142,7 +177,56 @@ where
     }
 
     let href = use_resolved_path(move || href.to_href()());
-    inner(href, target, exact, children, strict_trailing_slash, scroll)
+    let href = localize_href(href, locale);
+    inner(
+        href,
+        target,
+        exact,
+        children,
+        strict_trailing_slash,
+        scroll,
+        prefetch,
+        route_slot,
+    )
+}
+
+// Adds the current locale prefix (or `locale`) to an absolute `href`, if the router has locales.
+fn localize_href(
+    href: ArcMemo<Option<String>>,
+    locale: Option<Oco<'static, str>>,
+) -> ArcMemo<Option<String>> {
+    let RouterContext {
+        base,
+        locales,
+        locale: current_locale,
+        ..
+    } = use_context().expect("tried to use <A/> outside a <Router/>.");
+    let (Some(locales), Some(current_locale)) = (locales, current_locale)
+    else {
+        return href;
+    };
+    let base = base.unwrap_or_default();
+    ArcMemo::new(move |_| {
+        let href = href.get()?;
+        if !href.starts_with('/') || href.starts_with("//") {
+            return Some(href);
+        }
+        let Some(path) = strip_base(&base, &href) else {
+            return Some(href);
+        };
+        let path_end = path.find(['?', '#']).unwrap_or(path.len());
+        let (path, rest) = path.split_at(path_end);
+        let (prefix, _) = locales.split_path(path);
+        let locale = match (&locale, prefix) {
+            (Some(locale), _) => locale.to_string(),
+            // explicitly-prefixed links are left alone
+            (None, Some(_)) => return Some(href),
+            (None, None) => current_locale.get(),
+        };
+        let localized = locales.localize_path(&locale, path);
+        let base = base.trim_end_matches('/');
+        Some(format!("{base}{localized}{rest}"))
+    })
 }
 
 // Test if `href` is active for `location`.  Assumes _both_ `href` and `location` begin with a `'/'`.
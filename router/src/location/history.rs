@@ -1,4 +1,6 @@
-use super::{handle_anchor_click, LocationChange, LocationProvider, Url};
+use super::{
+    handle_anchor_click, LocationChange, LocationProvider, SlotLocation, Url,
+};
 use crate::{hooks::use_navigate, params::ParamsMap};
 use core::fmt;
 use futures::channel::oneshot;
@@ -25,6 +27,7 @@ pub struct BrowserUrl {
     pub(crate) pending_navigation: Arc<Mutex<Option<oneshot::Sender<()>>>>,
     pub(crate) path_stack: ArcStoredValue<Vec<Url>>,
     pub(crate) is_back: ArcRwSignal<bool>,
+    pub(crate) slot: ArcRwSignal<Option<SlotLocation>>,
 }
 
 impl fmt::Debug for BrowserUrl {
@@ -69,6 +72,9 @@ impl LocationProvider for BrowserUrl {
             pending_navigation: Default::default(),
             path_stack,
             is_back: Default::default(),
+            // a direct load always renders the full page, even if the history
+            // entry was created by a navigation into a slot
+            slot: Default::default(),
         })
     }
 
@@ -121,13 +127,20 @@ impl LocationProvider for BrowserUrl {
             let url = self.url.clone();
             let pending = Arc::clone(&self.pending_navigation);
             let this = self.clone();
-            move |new_url: Url, loc| {
+            move |new_url: Url,
+                  loc: LocationChange,
+                  slot: Option<Cow<'static, str>>| {
                 let same_path = {
                     let curr = url.read_untracked();
                     curr.origin() == new_url.origin()
                         && curr.path() == new_url.path()
                 };
 
+                this.slot.set(SlotLocation::next(
+                    this.slot.read_untracked().as_ref(),
+                    slot,
+                    &url.read_untracked(),
+                ));
                 url.set(new_url.clone());
                 if same_path {
                     this.complete_navigation(&loc);
@@ -183,6 +196,7 @@ impl LocationProvider for BrowserUrl {
             let url = self.url.clone();
             let path_stack = self.path_stack.clone();
             let is_back = self.is_back.clone();
+            let slot = self.slot.clone();
             move || match Self::current() {
                 Ok(new_url) => {
                     let stack = path_stack.read_value();
@@ -192,6 +206,14 @@ impl LocationProvider for BrowserUrl {
 
                     is_back.set(is_navigating_back);
 
+                    let state =
+                        tachys::dom::window().history().and_then(|h| h.state());
+                    slot.set(state.ok().and_then(|state| {
+                        SlotLocation::from_state(&state, |url| {
+                            Self::parse(url).ok()
+                        })
+                    }));
+
                     url.set(new_url);
                 }
                 Err(e) => {
@@ -220,20 +242,21 @@ impl LocationProvider for BrowserUrl {
 
     fn complete_navigation(&self, loc: &LocationChange) {
         let history = window().history().unwrap();
+        // the slot has already been updated for this navigation, and is only set if it targeted
+        // a slot
+        let state = match &*self.slot.read_untracked() {
+            Some(slot) => slot.wrap_state(&loc.state),
+            None => loc.state.to_js_value(),
+        };
 
         if loc.replace {
             history
-                .replace_state_with_url(
-                    &loc.state.to_js_value(),
-                    "",
-                    Some(&loc.value),
-                )
+                .replace_state_with_url(&state, "", Some(&loc.value))
                 .unwrap();
         } else {
             // push the "forward direction" marker
-            let state = &loc.state.to_js_value();
             history
-                .push_state_with_url(state, "", Some(&loc.value))
+                .push_state_with_url(&state, "", Some(&loc.value))
                 .unwrap();
         }
 
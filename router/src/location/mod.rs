@@ -178,6 +178,98 @@ impl Location {
     }
 }
 
+/// A navigation that renders into a named [`RouteSlot`](crate::components::RouteSlot), while
+/// the main routes keep rendering the `background` location.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlotLocation {
+    /// The name of the slot the current location is rendered in.
+    pub slot: Cow<'static, str>,
+    /// The location rendered by the main routes underneath the slot.
+    pub background: Url,
+}
+
+impl SlotLocation {
+    const SLOT_KEY: &'static str = "leptosRouteSlot";
+    const BACKGROUND_KEY: &'static str = "leptosBackground";
+    const STATE_KEY: &'static str = "state";
+
+    /// Returns the slot location after navigating away from `current` into `slot`.
+    ///
+    /// Navigating between two locations in the same slot keeps the original background, so that
+    /// the main routes stay mounted.
+    pub(crate) fn next(
+        prev: Option<&SlotLocation>,
+        slot: Option<Cow<'static, str>>,
+        current: &Url,
+    ) -> Option<SlotLocation> {
+        let slot = slot?;
+        let background = match prev {
+            Some(prev) if prev.slot == slot => prev.background.clone(),
+            _ => current.clone(),
+        };
+        Some(SlotLocation { slot, background })
+    }
+
+    /// Wraps the user's history state with this slot location, so that it can be restored
+    /// during back and forward navigations.
+    ///
+    /// The result is `{ leptosRouteSlot, leptosBackground, state }`, which is documented on
+    /// [`RouteSlot`](crate::components::RouteSlot) because it is visible to any code that reads
+    /// `window.history.state`.
+    pub(crate) fn wrap_state(&self, state: &State) -> JsValue {
+        let background = &self.background;
+        let background = format!(
+            "{}{}{}{}",
+            background.path(),
+            if background.search().is_empty() {
+                ""
+            } else {
+                "?"
+            },
+            background.search(),
+            background.hash()
+        );
+        let wrapped = js_sys::Object::new();
+        _ = Reflect::set(
+            &wrapped,
+            &JsValue::from_str(Self::SLOT_KEY),
+            &JsValue::from_str(&self.slot),
+        );
+        _ = Reflect::set(
+            &wrapped,
+            &JsValue::from_str(Self::BACKGROUND_KEY),
+            &JsValue::from_str(&background),
+        );
+        _ = Reflect::set(
+            &wrapped,
+            &JsValue::from_str(Self::STATE_KEY),
+            &state.to_js_value(),
+        );
+        wrapped.into()
+    }
+
+    /// Reads the slot location from history state created by [`SlotLocation::wrap_state`].
+    pub(crate) fn from_state(
+        state: &JsValue,
+        parse: impl Fn(&str) -> Option<Url>,
+    ) -> Option<SlotLocation> {
+        if !state.is_object() {
+            return None;
+        }
+        let get = |key| {
+            Reflect::get(state, &JsValue::from_str(key))
+                .ok()
+                .and_then(|value| value.as_string())
+        };
+        let slot = get(Self::SLOT_KEY)?;
+        let background = parse(&get(Self::BACKGROUND_KEY)?)?;
+        Some(SlotLocation {
+            slot: slot.into(),
+            background,
+        })
+    }
+}
+
 /// A description of a navigation.
 #[derive(Debug, Clone, PartialEq)]
 pub struct LocationChange {
@@ -272,7 +364,8 @@ pub(crate) fn handle_anchor_click<NavFn, NavFut>(
     navigate: NavFn,
 ) -> Box<dyn Fn(Event) -> Result<(), JsValue>>
 where
-    NavFn: Fn(Url, LocationChange) -> NavFut + 'static,
+    NavFn:
+        Fn(Url, LocationChange, Option<Cow<'static, str>>) -> NavFut + 'static,
     NavFut: Future<Output = ()> + 'static,
 {
     let router_base = router_base.unwrap_or_default();
@@ -356,17 +449,62 @@ where
                 .and_then(|value| value.as_bool())
                 .unwrap_or(false);
 
+            let slot = a.get_attribute("data-route-slot");
+
             let change = LocationChange {
                 value: to,
                 replace,
+                // opening a slot shouldn't scroll away from the background route
                 scroll: !a.has_attribute("noscroll")
-                    && !a.has_attribute("data-noscroll"),
+                    && !a.has_attribute("data-noscroll")
+                    && slot.is_none(),
                 state: State::new(state),
             };
 
-            Executor::spawn_local(navigate(url, change));
+            Executor::spawn_local(navigate(url, change, slot.map(Into::into)));
         }
 
         Ok(())
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{SlotLocation, Url};
+
+    fn url(path: &str) -> Url {
+        Url {
+            path: path.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn slot_navigations_keep_the_original_background() {
+        let feed = url("/feed");
+        let opened =
+            SlotLocation::next(None, Some("modal".into()), &feed).unwrap();
+        assert_eq!(opened.background, feed);
+
+        // moving between locations in the same slot keeps the background
+        let next = SlotLocation::next(
+            Some(&opened),
+            Some("modal".into()),
+            &url("/photos/1"),
+        )
+        .unwrap();
+        assert_eq!(next.background, feed);
+
+        // opening another slot uses the current location as its background
+        let other = SlotLocation::next(
+            Some(&opened),
+            Some("drawer".into()),
+            &url("/photos/1"),
+        )
+        .unwrap();
+        assert_eq!(other.background, url("/photos/1"));
+
+        // a regular navigation closes the slot
+        assert!(SlotLocation::next(Some(&opened), None, &feed).is_none());
+    }
+}
@@ -8,15 +8,19 @@ pub use path_segment::*;
 mod horizontal;
 mod nested;
 mod vertical;
-use crate::{static_routes::RegenerationFn, Method, SsrMode};
+use crate::{
+    animation::RouteAnimation, i18n::Locales, static_routes::RegenerationFn,
+    Method, PrefetchFn, RouteListing, SsrMode,
+};
 pub use horizontal::*;
 pub use nested::*;
-use std::{borrow::Cow, collections::HashSet, sync::atomic::Ordering};
+use std::{borrow::Cow, collections::HashSet, iter, sync::atomic::Ordering};
 pub use vertical::*;
 
 #[derive(Debug)]
 pub struct RouteDefs<Children> {
     base: Option<Cow<'static, str>>,
+    locales: Option<Locales>,
     children: Children,
 }
 
@@ -27,6 +31,7 @@ where
     fn clone(&self) -> Self {
         Self {
             base: self.base.clone(),
+            locales: self.locales.clone(),
             children: self.children.clone(),
         }
     }
@@ -36,6 +41,7 @@ impl<Children> RouteDefs<Children> {
     pub fn new(children: Children) -> Self {
         Self {
             base: None,
+            locales: None,
             children,
         }
     }
@@ -46,9 +52,26 @@ impl<Children> RouteDefs<Children> {
     ) -> Self {
         Self {
             base: Some(base.into()),
+            locales: None,
             children,
         }
     }
+
+    /// Allows each route to also be matched behind a locale prefix, like `/fr/about`.
+    pub fn with_locales(mut self, locales: Option<Locales>) -> Self {
+        self.locales = locales;
+        self
+    }
+}
+
+/// Strips `base` from the start of `path`, returning `None` if `path` is not under `base`.
+pub(crate) fn strip_base<'a>(base: &str, path: &'a str) -> Option<&'a str> {
+    let (base, path) = if base.starts_with('/') {
+        (base.trim_start_matches('/'), path.trim_start_matches('/'))
+    } else {
+        (base, path)
+    };
+    path.strip_prefix(base)
 }
 
 impl<Children> RouteDefs<Children>
@@ -58,14 +81,11 @@ where
     pub fn match_route(&self, path: &str) -> Option<Children::Match> {
         let path = match &self.base {
             None => path,
-            Some(base) => {
-                let (base, path) = if base.starts_with('/') {
-                    (base.trim_start_matches('/'), path.trim_start_matches('/'))
-                } else {
-                    (base.as_ref(), path)
-                };
-                path.strip_prefix(base)?
-            }
+            Some(base) => strip_base(base, path)?,
+        };
+        let path = match &self.locales {
+            None => path,
+            Some(locales) => locales.split_path(path).1,
         };
 
         let (matched, remaining) = self.children.match_nested(path);
@@ -86,6 +106,41 @@ where
     ) {
         (self.base.as_deref(), self.children.generate_routes())
     }
+
+    /// Generates the route listings for every route, including a copy of each route behind
+    /// every locale prefix.
+    pub(crate) fn generate_route_listings(&self) -> Vec<RouteListing> {
+        let (base, routes) = self.generate_routes();
+        let prefixes = iter::once(None)
+            .chain(self.locales.iter().flat_map(Locales::supported).map(Some))
+            .collect::<Vec<_>>();
+        routes
+            .into_iter()
+            .flat_map(|data| {
+                prefixes
+                    .iter()
+                    .map(|locale| {
+                        let path = base
+                            .map(|base| {
+                                PathSegment::Static(base.to_string().into())
+                            })
+                            .into_iter()
+                            .chain(locale.map(|locale| {
+                                PathSegment::Static(locale.to_string().into())
+                            }))
+                            .chain(data.segments.iter().cloned())
+                            .collect::<Vec<_>>();
+                        RouteListing::new(
+                            path,
+                            data.ssr_mode.clone(),
+                            data.methods.clone(),
+                            data.regenerate.clone(),
+                        )
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
@@ -109,6 +164,16 @@ pub trait MatchInterface {
     fn as_matched(&self) -> &str;
 
     fn into_view_and_child(self) -> (impl ChooseView, Option<Self::Child>);
+
+    /// The animation used when navigating between the children of this route.
+    fn animation(&self) -> Option<RouteAnimation> {
+        None
+    }
+
+    /// The function used to warm the data for this route when a link to it is prefetched.
+    fn prefetch(&self) -> Option<PrefetchFn> {
+        None
+    }
 }
 
 pub trait MatchParams {
@@ -141,8 +206,8 @@ pub struct GeneratedRouteData {
 mod tests {
     use super::{NestedRoute, ParamSegment, RouteDefs};
     use crate::{
-        matching::MatchParams, MatchInterface, PathSegment, StaticSegment,
-        WildcardSegment,
+        i18n::Locales, matching::MatchParams, MatchInterface, PathSegment,
+        StaticSegment, WildcardSegment,
     };
     use either_of::Either;
 
@@ -321,6 +386,49 @@ mod tests {
         let params = matched.to_params();
         assert_eq!(params, vec![("any".into(), "foobar".into())]);
     }
+
+    #[test]
+    pub fn matches_and_generates_locale_prefixed_routes() {
+        let routes: RouteDefs<_> = RouteDefs::new_with_base(
+            (
+                NestedRoute::new(StaticSegment("/"), || ()),
+                NestedRoute::new(ParamSegment("id"), || ()),
+            ),
+            "/app",
+        )
+        .with_locales(Some(Locales::new(["en", "fr"])));
+
+        assert!(routes.match_route("/app/").is_some());
+        assert!(routes.match_route("/app/fr").is_some());
+        assert!(routes.match_route("/app/fr/").is_some());
+        let matched = routes.match_route("/app/fr/42").unwrap();
+        assert_eq!(matched.to_params(), vec![("id".into(), "42".into())]);
+        // a locale is only stripped as a whole segment
+        let matched = routes.match_route("/app/french").unwrap();
+        assert_eq!(matched.to_params(), vec![("id".into(), "french".into())]);
+
+        let paths = routes
+            .generate_route_listings()
+            .into_iter()
+            .map(|listing| listing.path().to_vec())
+            .collect::<Vec<_>>();
+        let base = PathSegment::Static("/app".into());
+        let en = PathSegment::Static("en".into());
+        let fr = PathSegment::Static("fr".into());
+        let root = PathSegment::Static("/".into());
+        let id = PathSegment::Param("id".into());
+        assert_eq!(
+            paths,
+            vec![
+                vec![base.clone(), root.clone()],
+                vec![base.clone(), en.clone(), root.clone()],
+                vec![base.clone(), fr.clone(), root],
+                vec![base.clone(), id.clone()],
+                vec![base.clone(), en, id.clone()],
+                vec![base, fr, id],
+            ]
+        );
+    }
 }
 
 #[derive(Debug)]
@@ -2,7 +2,10 @@ use super::{
     MatchInterface, MatchNestedRoutes, PartialPathMatch, PathSegment,
     PossibleRouteMatch, RouteMatchId,
 };
-use crate::{ChooseView, GeneratedRouteData, MatchParams, Method, SsrMode};
+use crate::{
+    animation::RouteAnimation, ChooseView, GeneratedRouteData, MatchParams,
+    Method, PrefetchFn, SsrMode,
+};
 use core::{fmt, iter};
 use either_of::Either;
 use std::{
@@ -24,6 +27,8 @@ pub struct NestedRoute<Segments, Children, Data, View> {
     view: View,
     methods: HashSet<Method>,
     ssr_mode: SsrMode,
+    animation: Option<RouteAnimation>,
+    prefetch: Option<PrefetchFn>,
 }
 
 impl<Segments, Children, Data, View> Clone
@@ -43,6 +48,8 @@ where
             view: self.view.clone(),
             methods: self.methods.clone(),
             ssr_mode: self.ssr_mode.clone(),
+            animation: self.animation,
+            prefetch: self.prefetch.clone(),
         }
     }
 }
@@ -60,6 +67,8 @@ impl<Segments, View> NestedRoute<Segments, (), (), View> {
             view,
             methods: [Method::Get].into(),
             ssr_mode: Default::default(),
+            animation: None,
+            prefetch: None,
         }
     }
 }
@@ -76,6 +85,8 @@ impl<Segments, Data, View> NestedRoute<Segments, (), Data, View> {
             view,
             ssr_mode,
             methods,
+            animation,
+            prefetch,
             ..
         } = self;
         NestedRoute {
@@ -86,6 +97,8 @@ impl<Segments, Data, View> NestedRoute<Segments, (), Data, View> {
             view,
             ssr_mode,
             methods,
+            animation,
+            prefetch,
         }
     }
 
@@ -95,6 +108,22 @@ impl<Segments, Data, View> NestedRoute<Segments, (), Data, View> {
     }
 }
 
+impl<Segments, Children, Data, View>
+    NestedRoute<Segments, Children, Data, View>
+{
+    /// Animates navigations between the children of this route.
+    pub fn animation(mut self, animation: Option<RouteAnimation>) -> Self {
+        self.animation = animation;
+        self
+    }
+
+    /// Warms the data for this route when a link to it is prefetched.
+    pub fn prefetch(mut self, prefetch: Option<PrefetchFn>) -> Self {
+        self.prefetch = prefetch;
+        self
+    }
+}
+
 #[derive(PartialEq, Eq)]
 pub struct NestedMatch<Child, View> {
     id: RouteMatchId,
@@ -105,6 +134,8 @@ pub struct NestedMatch<Child, View> {
     /// The nested route.
     child: Option<Child>,
     view_fn: View,
+    animation: Option<RouteAnimation>,
+    prefetch: Option<PrefetchFn>,
 }
 
 impl<Child, View> fmt::Debug for NestedMatch<Child, View>
@@ -145,6 +176,14 @@ where
     fn into_view_and_child(self) -> (impl ChooseView, Option<Self::Child>) {
         (self.view_fn, self.child)
     }
+
+    fn animation(&self) -> Option<RouteAnimation> {
+        self.animation
+    }
+
+    fn prefetch(&self) -> Option<PrefetchFn> {
+        self.prefetch.clone()
+    }
 }
 
 impl<Segments, Children, Data, View> MatchNestedRoutes
@@ -199,6 +238,8 @@ where
                                     params,
                                     child: inner,
                                     view_fn: self.view.clone(),
+                                    animation: self.animation,
+                                    prefetch: self.prefetch.clone(),
                                 },
                             )),
                             remaining,
@@ -1,5 +1,8 @@
 use super::{MatchInterface, MatchNestedRoutes, PathSegment, RouteMatchId};
-use crate::{ChooseView, GeneratedRouteData, MatchParams};
+use crate::{
+    animation::RouteAnimation, ChooseView, GeneratedRouteData, MatchParams,
+    PrefetchFn,
+};
 use core::iter;
 use either_of::*;
 use std::borrow::Cow;
@@ -73,6 +76,14 @@ where
     fn into_view_and_child(self) -> (impl ChooseView, Option<Self::Child>) {
         self.0.into_view_and_child()
     }
+
+    fn animation(&self) -> Option<RouteAnimation> {
+        self.0.animation()
+    }
+
+    fn prefetch(&self) -> Option<PrefetchFn> {
+        self.0.prefetch()
+    }
 }
 
 impl<A> MatchNestedRoutes for (A,)
@@ -142,6 +153,20 @@ where
             }
         }
     }
+
+    fn animation(&self) -> Option<RouteAnimation> {
+        match self {
+            Either::Left(i) => i.animation(),
+            Either::Right(i) => i.animation(),
+        }
+    }
+
+    fn prefetch(&self) -> Option<PrefetchFn> {
+        match self {
+            Either::Left(i) => i.prefetch(),
+            Either::Right(i) => i.prefetch(),
+        }
+    }
 }
 
 impl<A, B> MatchNestedRoutes for (A, B)
@@ -237,6 +262,18 @@ macro_rules! tuples {
                     })*
                 }
             }
+
+            fn animation(&self) -> Option<RouteAnimation> {
+                match self {
+                    $($either::$ty(i) => i.animation(),)*
+                }
+            }
+
+            fn prefetch(&self) -> Option<PrefetchFn> {
+                match self {
+                    $($either::$ty(i) => i.prefetch(),)*
+                }
+            }
         }
 
         impl<$($ty),*> MatchNestedRoutes for ($($ty,)*)
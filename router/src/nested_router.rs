@@ -1,11 +1,12 @@
 use crate::{
+    animation::{animated_outlet, RouteAnimation},
     hooks::Matched,
     location::{LocationProvider, Url},
     matching::RouteDefs,
     params::ParamsMap,
     view_transition::start_view_transition,
-    ChooseView, MatchInterface, MatchNestedRoutes, MatchParams, PathSegment,
-    RouteList, RouteListing, RouteMatchId,
+    ChooseView, MatchInterface, MatchNestedRoutes, MatchParams, RouteList,
+    RouteMatchId,
 };
 use any_spawner::Executor;
 use either_of::{Either, EitherOf3};
@@ -51,6 +52,7 @@ pub(crate) struct NestedRoutesView<Loc, Defs, FalFn> {
     pub fallback: FalFn,
     pub set_is_routing: Option<SignalSetter<bool>>,
     pub transition: bool,
+    pub animation: Option<RouteAnimation>,
 }
 
 /// Retained view state for the nested router.
@@ -83,6 +85,7 @@ where
             current_url,
             fallback,
             base,
+            animation,
             ..
         } = self;
 
@@ -106,6 +109,7 @@ where
                     &mut loaders,
                     &mut outlets,
                     &outer_owner,
+                    animation,
                 );
                 drop(url);
                 outer_owner.with(|| EitherOf3::C(Outlet().into_any()))
@@ -175,6 +179,7 @@ where
                     &mut full_loaders,
                     &mut state.outlets,
                     &self.outer_owner,
+                    self.animation,
                     self.set_is_routing.is_some(),
                     0,
                 );
@@ -273,27 +278,7 @@ where
         // if this is being run on the server for the first time, generating all possible routes
         if RouteList::is_generating() {
             // add routes
-            let (base, routes) = self.routes.generate_routes();
-            let routes = routes
-                .into_iter()
-                .map(|data| {
-                    let path = base
-                        .into_iter()
-                        .flat_map(|base| {
-                            iter::once(PathSegment::Static(
-                                base.to_string().into(),
-                            ))
-                        })
-                        .chain(data.segments)
-                        .collect::<Vec<_>>();
-                    RouteListing::new(
-                        path,
-                        data.ssr_mode,
-                        data.methods,
-                        data.regenerate,
-                    )
-                })
-                .collect::<Vec<_>>();
+            let routes = self.routes.generate_route_listings();
 
             // add fallback
             // TODO fix: causes overlapping route issues on Axum
@@ -320,6 +305,7 @@ where
                 current_url,
                 fallback,
                 base,
+                animation,
                 ..
             } = self;
             let current_url = current_url.read_untracked();
@@ -336,6 +322,7 @@ where
                         &mut loaders,
                         &mut outlets,
                         &outer_owner,
+                        animation,
                     );
 
                     // outlets will not send their views if the loaders are never polled
@@ -367,6 +354,7 @@ where
             current_url,
             fallback,
             base,
+            animation,
             ..
         } = self;
         let current_url = current_url.read_untracked();
@@ -383,6 +371,7 @@ where
                     &mut loaders,
                     &mut outlets,
                     &outer_owner,
+                    animation,
                 );
 
                 // outlets will not send their views if the loaders are never polled
@@ -414,6 +403,7 @@ where
             current_url,
             fallback,
             base,
+            animation,
             ..
         } = self;
 
@@ -436,6 +426,7 @@ where
                         &mut loaders,
                         &mut outlets,
                         &outer_owner,
+                        animation,
                     );
                     drop(url);
 
@@ -462,13 +453,15 @@ type OutletViewFn = Box<dyn Fn() -> Suspend<AnyView> + Send>;
 
 pub(crate) struct RouteContext {
     id: RouteMatchId,
-    trigger: ArcTrigger,
     url: ArcRwSignal<Url>,
     params: ArcRwSignal<ParamsMap>,
     owner: Owner,
     pub matched: ArcRwSignal<String>,
     base: Option<Oco<'static, str>>,
-    view_fn: Arc<Mutex<OutletViewFn>>,
+    pub(crate) trigger: ArcTrigger,
+    pub(crate) view_fn: Arc<Mutex<OutletViewFn>>,
+    pub(crate) animation: Option<RouteAnimation>,
+    pub(crate) level: u8,
 }
 
 impl Debug for RouteContext {
@@ -481,6 +474,8 @@ impl Debug for RouteContext {
             .field("owner", &self.owner.debug_id())
             .field("matched", &self.matched)
             .field("base", &self.base)
+            .field("animation", &self.animation)
+            .field("level", &self.level)
             .finish_non_exhaustive()
     }
 }
@@ -502,6 +497,8 @@ impl Clone for RouteContext {
             matched: self.matched.clone(),
             base: self.base.clone(),
             view_fn: Arc::clone(&self.view_fn),
+            animation: self.animation,
+            level: self.level,
         }
     }
 }
@@ -514,6 +511,7 @@ trait AddNestedRoute {
         loaders: &mut Vec<Pin<Box<dyn Future<Output = ArcTrigger>>>>,
         outlets: &mut Vec<RouteContext>,
         parent: &Owner,
+        animation: Option<RouteAnimation>,
     );
 
     #[allow(clippy::too_many_arguments)]
@@ -526,6 +524,7 @@ trait AddNestedRoute {
         full_loaders: &mut Vec<oneshot::Receiver<()>>,
         outlets: &mut Vec<RouteContext>,
         parent: &Owner,
+        animation: Option<RouteAnimation>,
         set_is_routing: bool,
         level: u8,
     ) -> u8;
@@ -542,6 +541,7 @@ where
         loaders: &mut Vec<Pin<Box<dyn Future<Output = ArcTrigger>>>>,
         outlets: &mut Vec<RouteContext>,
         parent: &Owner,
+        animation: Option<RouteAnimation>,
     ) {
         let orig_url = url;
 
@@ -613,9 +613,15 @@ where
                 Suspend::new(Box::pin(async { ().into_any() }))
             }))),
             base: base.clone(),
+            animation,
+            level: outlets.len() as u8,
         };
         outlets.push(outlet.clone());
 
+        // the animation declared on this route applies to its own <Outlet/>,
+        // i.e., to transitions between its children
+        let child_animation = self.animation();
+
         // send the initial view through the channel, and recurse through the children
         let (view, child) = self.into_view_and_child();
 
@@ -659,7 +665,14 @@ where
         // this is important because to build the view, we need access to the outlet
         // and the outlet will be returned from building this child
         if let Some(child) = child {
-            child.build_nested_route(orig_url, base, loaders, outlets, &owner);
+            child.build_nested_route(
+                orig_url,
+                base,
+                loaders,
+                outlets,
+                &owner,
+                child_animation,
+            );
         }
     }
 
@@ -673,6 +686,7 @@ where
         full_loaders: &mut Vec<oneshot::Receiver<()>>,
         outlets: &mut Vec<RouteContext>,
         parent: &Owner,
+        animation: Option<RouteAnimation>,
         set_is_routing: bool,
         level: u8,
     ) -> u8 {
@@ -685,7 +699,9 @@ where
         match current {
             // if there's nothing currently in the routes at this point, build from here
             None => {
-                self.build_nested_route(url, base, preloaders, outlets, parent);
+                self.build_nested_route(
+                    url, base, preloaders, outlets, parent, animation,
+                );
                 level
             }
             Some(current) => {
@@ -698,6 +714,7 @@ where
                 let new_params =
                     self.to_params().into_iter().collect::<ParamsMap>();
                 let new_match = self.as_matched().to_owned();
+                let child_animation = self.animation();
 
                 let (view, child) = self.into_view_and_child();
 
@@ -711,6 +728,7 @@ where
                     // update the ID of the match at this depth, so that futures rebuilds diff
                     // against the new ID, not the original one
                     current.id = id;
+                    current.animation = animation;
 
                     // create new URL and params signals
                     let old_url = mem::replace(
@@ -819,7 +837,12 @@ where
                     // if this children has matches, then rebuild the lower section of the tree
                     if let Some(child) = child {
                         child.build_nested_route(
-                            url, base, preloaders, outlets, &owner,
+                            url,
+                            base,
+                            preloaders,
+                            outlets,
+                            &owner,
+                            child_animation,
                         );
                     }
 
@@ -842,6 +865,7 @@ where
                         full_loaders,
                         outlets,
                         &owner,
+                        child_animation,
                         set_is_routing,
                         level + 1,
                     )
@@ -876,18 +900,24 @@ where
 
 /// Displays the child route nested in a parent route, allowing you to control exactly where
 /// that child route is displayed. Renders nothing if there is no nested child.
+///
+/// If the parent route declares a [`RouteAnimation`], the outgoing view is kept mounted
+/// while the incoming view animates in.
 #[component]
 pub fn Outlet() -> impl RenderHtml
 where
 {
-    move || {
-        let ctx = use_context::<RouteContext>()
-            .expect("<Outlet/> used without RouteContext being provided.");
-        let RouteContext {
-            trigger, view_fn, ..
-        } = ctx;
-        trigger.track();
-        let view_fn = view_fn.lock().or_poisoned();
-        view_fn()
+    let ctx = use_context::<RouteContext>()
+        .expect("<Outlet/> used without RouteContext being provided.");
+    match ctx.animation {
+        Some(animation) => Either::Left(animated_outlet(ctx, animation)),
+        None => Either::Right(move || {
+            let RouteContext {
+                trigger, view_fn, ..
+            } = &ctx;
+            trigger.track();
+            let view_fn = view_fn.lock().or_poisoned();
+            view_fn()
+        }),
     }
 }
@@ -0,0 +1,273 @@
+use crate::{
+    components::RouterContext, params::ParamsMap, ChooseView, MatchInterface,
+    MatchNestedRoutes, MatchParams, RouteDefs,
+};
+use any_spawner::Executor;
+use futures::future::join_all;
+use js_sys::Reflect;
+use leptos::{html, prelude::*};
+use or_poisoned::OrPoisoned;
+use reactive_graph::{
+    computed::{ArcMemo, ScopedFuture},
+    owner::Owner,
+};
+use send_wrapper::SendWrapper;
+use std::{
+    fmt::{self, Debug},
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+};
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+
+/// When an [`A`](crate::components::A) should load the code and data for the route it links to,
+/// so that navigating to it feels instant.
+///
+/// Prefetching calls [`ChooseView::preload`] for every route matched by the link (which loads
+/// the code for lazy routes) and runs any [`PrefetchFn`] declared on those routes. Each URL is
+/// only prefetched once, and nothing is prefetched if the user has enabled `Save-Data`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Prefetch {
+    /// Prefetch when the user hovers over, focuses, or touches the link.
+    Hover,
+    /// Prefetch when the link scrolls into the viewport.
+    Viewport,
+    /// Prefetch as soon as the link has been rendered.
+    Render,
+}
+
+type PrefetchFuture = Pin<Box<dyn Future<Output = ()>>>;
+
+/// Warms the data used by a route, given the params it will be matched with.
+///
+/// This is declared with the `prefetch` prop on [`Route`](crate::components::Route) or
+/// [`ParentRoute`](crate::components::ParentRoute), and is run when a link to that route is
+/// prefetched. For example, it can call a server function that uses `GET` so that the response
+/// is cached by the browser, or fill an application-level cache.
+#[derive(Clone)]
+pub struct PrefetchFn(
+    Arc<dyn Fn(ParamsMap) -> PrefetchFuture + Send + Sync + 'static>,
+);
+
+impl PrefetchFn {
+    /// Creates a new prefetch function.
+    pub fn new<F, Fut>(fun: F) -> Self
+    where
+        F: Fn(ParamsMap) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + 'static,
+    {
+        Self(Arc::new(move |params| Box::pin(fun(params))))
+    }
+
+    fn run(&self, params: ParamsMap) -> PrefetchFuture {
+        (self.0)(params)
+    }
+}
+
+impl<F, Fut> From<F> for PrefetchFn
+where
+    F: Fn(ParamsMap) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = ()> + 'static,
+{
+    fn from(value: F) -> Self {
+        Self::new(value)
+    }
+}
+
+impl Debug for PrefetchFn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("PrefetchFn").finish()
+    }
+}
+
+impl PartialEq for PrefetchFn {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for PrefetchFn {}
+
+/// Matches a path against the route definitions registered by `<Routes/>` or `<FlatRoutes/>`,
+/// and returns a future that loads everything the matched routes need.
+pub(crate) type Prefetcher =
+    Arc<dyn Fn(&str) -> Option<PrefetchFuture> + Send + Sync>;
+
+pub(crate) fn prefetcher<Defs>(
+    routes: RouteDefs<Defs>,
+    owner: Owner,
+) -> Prefetcher
+where
+    Defs: MatchNestedRoutes + Send + 'static,
+{
+    let routes = Mutex::new(routes);
+    Arc::new(move |path| {
+        let matched = routes.lock().or_poisoned().match_route(path)?;
+        let mut loaders = Vec::new();
+        collect_loaders(matched, &mut loaders);
+
+        // anything created while prefetching is owned by a short-lived owner,
+        // which is dropped once everything has loaded
+        let owner = owner.child();
+        let fut = owner.with(|| ScopedFuture::new(join_all(loaders)));
+        Some(Box::pin(async move {
+            fut.await;
+            drop(owner);
+        }))
+    })
+}
+
+fn collect_loaders<Match>(matched: Match, loaders: &mut Vec<PrefetchFuture>)
+where
+    Match: MatchInterface + MatchParams,
+{
+    if let Some(prefetch) = matched.prefetch() {
+        let params = matched.to_params().into_iter().collect();
+        loaders.push(prefetch.run(params));
+    }
+    let (view, child) = matched.into_view_and_child();
+    loaders.push(Box::pin(async move { view.preload().await }));
+    if let Some(child) = child {
+        collect_loaders(child, loaders);
+    }
+}
+
+/// Prefetches the route at `href`, unless it has already been prefetched.
+pub(crate) fn prefetch_href(router: &RouterContext, href: &str) {
+    let path = href.split(['?', '#']).next().unwrap_or_default();
+    if path.is_empty() || save_data() {
+        return;
+    }
+    if router.prefetched.read_value().contains(path) {
+        return;
+    }
+    // the prefetcher is only set once the routes have been rendered, so a link that prefetches
+    // before then is only marked as prefetched once it has actually started loading
+    let prefetcher = router.prefetcher.read_value().clone();
+    if let Some(fut) = prefetcher.and_then(|prefetcher| prefetcher(path)) {
+        router.prefetched.write_value().insert(path.to_string());
+        Executor::spawn_local(fut);
+    }
+}
+
+/// Whether the user has asked to reduce data usage, via the `Save-Data` preference.
+fn save_data() -> bool {
+    Reflect::get(&window().navigator(), &JsValue::from_str("connection"))
+        .ok()
+        .filter(|connection| connection.is_object())
+        .and_then(|connection| {
+            Reflect::get(&connection, &JsValue::from_str("saveData")).ok()
+        })
+        .and_then(|save_data| save_data.as_bool())
+        .unwrap_or(false)
+}
+
+/// Sets up prefetching of `href` for the link in `node_ref`, according to `strategy`.
+pub(crate) fn use_prefetch(
+    strategy: Prefetch,
+    node_ref: NodeRef<html::A>,
+    href: ArcMemo<Option<String>>,
+) {
+    let router = use_context::<RouterContext>()
+        .expect("tried to use <A/> outside a <Router/>.");
+    let prefetch = move || {
+        if let Some(href) = href.get_untracked() {
+            prefetch_href(&router, &href);
+        }
+    };
+
+    Effect::new(move |_| {
+        let Some(el) = node_ref.get() else {
+            return;
+        };
+        match strategy {
+            Prefetch::Render => prefetch(),
+            Prefetch::Hover => {
+                let prefetch = prefetch.clone();
+                let cb = Closure::<dyn Fn()>::new(prefetch);
+                for event in ["mouseenter", "focus", "touchstart"] {
+                    _ = el.add_event_listener_with_callback(
+                        event,
+                        cb.as_ref().unchecked_ref(),
+                    );
+                }
+                let el = SendWrapper::new(el);
+                let cb = SendWrapper::new(cb);
+                on_cleanup(move || {
+                    for event in ["mouseenter", "focus", "touchstart"] {
+                        _ = el.remove_event_listener_with_callback(
+                            event,
+                            cb.as_ref().unchecked_ref(),
+                        );
+                    }
+                });
+            }
+            Prefetch::Viewport => {
+                let prefetch = prefetch.clone();
+                let cb = Closure::<dyn Fn(js_sys::Array, web_sys::IntersectionObserver)>::new(
+                    move |entries: js_sys::Array,
+                          observer: web_sys::IntersectionObserver| {
+                        let is_visible = entries.iter().any(|entry| {
+                            entry
+                                .unchecked_into::<web_sys::IntersectionObserverEntry>()
+                                .is_intersecting()
+                        });
+                        if is_visible {
+                            observer.disconnect();
+                            prefetch();
+                        }
+                    },
+                );
+                let Ok(observer) = web_sys::IntersectionObserver::new(
+                    cb.as_ref().unchecked_ref(),
+                ) else {
+                    // browsers without IntersectionObserver just don't prefetch
+                    return;
+                };
+                observer.observe(&el);
+                let observer = SendWrapper::new(observer);
+                let cb = SendWrapper::new(cb);
+                on_cleanup(move || {
+                    observer.disconnect();
+                    drop(cb);
+                });
+            }
+        }
+    });
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use super::{prefetcher, PrefetchFn};
+    use crate::{
+        params::ParamsMap, NestedRoute, ParamSegment, RouteDefs, StaticSegment,
+    };
+    use reactive_graph::owner::Owner;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn prefetches_data_for_matched_routes() {
+        let owner = Owner::new();
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let prefetch = PrefetchFn::new({
+            let seen = Arc::clone(&seen);
+            move |params: ParamsMap| {
+                seen.lock().unwrap().push(params.get("id"));
+                async {}
+            }
+        });
+        let routes = RouteDefs::new(
+            NestedRoute::new(StaticSegment("posts"), || "Posts").child(
+                NestedRoute::new(ParamSegment("id"), || "Post")
+                    .prefetch(Some(prefetch)),
+            ),
+        );
+        let prefetcher = prefetcher(routes, owner);
+
+        assert!(prefetcher("/users").is_none());
+        assert!(seen.lock().unwrap().is_empty());
+
+        futures::executor::block_on(prefetcher("/posts/42").unwrap());
+        assert_eq!(*seen.lock().unwrap(), vec![Some("42".to_string())]);
+    }
+}
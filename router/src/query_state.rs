@@ -0,0 +1,505 @@
+use crate::{
+    hooks::{use_location, use_navigate, use_query_map},
+    location::Url,
+    navigate::NavigateOptions,
+    params::ParamsMap,
+};
+use leptos::leptos_dom::helpers::{set_timeout_with_handle, TimeoutHandle};
+#[doc(inline)]
+pub use leptos_router_macro::QueryState;
+use reactive_graph::{
+    computed::Memo,
+    owner::StoredValue,
+    signal::ArcRwSignal,
+    traits::{Get, GetUntracked, GetValue, Set, SetValue, With},
+    wrappers::write::SignalSetter,
+};
+use std::{str::FromStr, time::Duration};
+
+/// A struct whose fields are stored in the URL query string.
+///
+/// This is usually implemented with `#[derive(QueryState)]`, and used with
+/// [`use_query_state`]. Each field is stored under a query key with the same name. Fields that
+/// are equal to their value in the type's [`Default`] implementation are omitted from the URL,
+/// and missing or invalid values are read as the default. The fields of a `#[query(nested)]`
+/// field default to the value of that field in the parent's [`Default`] implementation.
+///
+/// The derive accepts these attributes:
+/// - `#[query(rename = "key")]` on a field stores it under a different key.
+/// - `#[query(nested)]` on a field whose type also implements `QueryState` stores its fields
+///   under `field.key`.
+/// - `#[query(from_str)]` on a field stores it with its [`FromStr`] and [`ToString`]
+///   implementations, instead of [`QueryValue`].
+/// - `#[query(replace)]` or `#[query(push)]` on a field or on the struct chooses whether
+///   changing the field adds an entry to the browser history (the default) or replaces the
+///   current one.
+///
+/// Lists (`Vec<T>`) are stored as repeated keys, like `?tag=a&tag=b`.
+///
+/// ```rust
+/// use leptos_router::query_state::QueryState;
+///
+/// #[derive(QueryState, Clone, Debug, Default, PartialEq)]
+/// struct Filters {
+///     #[query(rename = "q", replace)]
+///     search: String,
+///     tags: Vec<String>,
+///     page: u32,
+///     #[query(nested)]
+///     price: PriceRange,
+/// }
+///
+/// #[derive(QueryState, Clone, Debug, Default, PartialEq)]
+/// #[query(replace)]
+/// struct PriceRange {
+///     min: Option<u32>,
+///     max: Option<u32>,
+/// }
+/// ```
+pub trait QueryState: Default + PartialEq {
+    /// Reads the state from `query`, where each key starts with `prefix`.
+    fn read_query(query: &ParamsMap, prefix: &str) -> Self {
+        Self::read_query_with_default(query, prefix, Self::default())
+    }
+
+    /// Reads the state from `query`, where each key starts with `prefix`, using the fields of
+    /// `default` for missing or invalid values.
+    ///
+    /// This is used for `#[query(nested)]` fields, whose defaults come from the parent struct.
+    fn read_query_with_default(
+        query: &ParamsMap,
+        prefix: &str,
+        default: Self,
+    ) -> Self;
+
+    /// Writes the state into `query`, where each key starts with `prefix`, removing the keys of
+    /// fields that have their default value.
+    fn write_query(&self, query: &mut ParamsMap, prefix: &str) {
+        self.write_query_with_default(query, prefix, &Self::default())
+    }
+
+    /// Writes the state into `query`, where each key starts with `prefix`, removing the keys of
+    /// fields that are equal to the same field in `default`.
+    fn write_query_with_default(
+        &self,
+        query: &mut ParamsMap,
+        prefix: &str,
+        default: &Self,
+    );
+
+    /// Returns how the history should be updated when changing from `prev` to `self`, or `None`
+    /// if nothing changed.
+    fn history_mode(&self, prev: &Self) -> Option<HistoryMode>;
+}
+
+/// How the browser history is updated when query state changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HistoryMode {
+    /// Adds a new entry to the history stack.
+    Push,
+    /// Replaces the current entry in the history stack.
+    Replace,
+}
+
+impl HistoryMode {
+    /// Combines the modes of two changes: if either of them pushes, the combined change pushes.
+    pub fn merge(this: Option<Self>, other: Option<Self>) -> Option<Self> {
+        match (this, other) {
+            (Some(HistoryMode::Push), _) | (_, Some(HistoryMode::Push)) => {
+                Some(HistoryMode::Push)
+            }
+            (Some(HistoryMode::Replace), _)
+            | (_, Some(HistoryMode::Replace)) => Some(HistoryMode::Replace),
+            (None, None) => None,
+        }
+    }
+}
+
+/// A value that can be stored in one or more query values with the same key.
+pub trait QueryValue: Sized {
+    /// Parses the value from all the query values for its key. Returns `None` if they are invalid.
+    fn from_query(values: &[String]) -> Option<Self>;
+
+    /// Converts the value into query values for its key.
+    fn to_query(&self) -> Vec<String>;
+}
+
+macro_rules! query_value_from_str {
+    ($($ty:ty),*) => {
+        $(
+            impl QueryValue for $ty {
+                fn from_query(values: &[String]) -> Option<Self> {
+                    values.last()?.parse().ok()
+                }
+
+                fn to_query(&self) -> Vec<String> {
+                    vec![self.to_string()]
+                }
+            }
+        )*
+    };
+}
+
+query_value_from_str!(
+    String, bool, char, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64,
+    u128, usize, f32, f64
+);
+
+impl<T> QueryValue for Option<T>
+where
+    T: QueryValue,
+{
+    fn from_query(values: &[String]) -> Option<Self> {
+        if values.is_empty() {
+            Some(None)
+        } else {
+            T::from_query(values).map(Some)
+        }
+    }
+
+    fn to_query(&self) -> Vec<String> {
+        self.as_ref().map(T::to_query).unwrap_or_default()
+    }
+}
+
+impl<T> QueryValue for Vec<T>
+where
+    T: QueryValue,
+{
+    fn from_query(values: &[String]) -> Option<Self> {
+        values
+            .iter()
+            .map(|value| T::from_query(std::slice::from_ref(value)))
+            .collect()
+    }
+
+    fn to_query(&self) -> Vec<String> {
+        self.iter().flat_map(T::to_query).collect()
+    }
+}
+
+#[doc(hidden)]
+pub fn __read_value<T: QueryValue>(query: &ParamsMap, key: &str) -> Option<T> {
+    T::from_query(&query.get_all(key)?)
+}
+
+#[doc(hidden)]
+pub fn __read_from_str<T: FromStr>(query: &ParamsMap, key: &str) -> Option<T> {
+    query.get_str(key)?.parse().ok()
+}
+
+#[doc(hidden)]
+pub fn __write_value<T: QueryValue + PartialEq>(
+    query: &mut ParamsMap,
+    key: String,
+    value: &T,
+    default: &T,
+) {
+    query.remove(&key);
+    if value != default {
+        for value in value.to_query() {
+            // values are unescaped when they are inserted
+            query.insert(key.clone(), Url::escape(&value));
+        }
+    }
+}
+
+#[doc(hidden)]
+pub fn __write_from_str<T: ToString + PartialEq>(
+    query: &mut ParamsMap,
+    key: String,
+    value: &T,
+    default: &T,
+) {
+    query.remove(&key);
+    if value != default {
+        query.insert(key, Url::escape(&value.to_string()));
+    }
+}
+
+/// Options for [`use_query_state_with_options`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QueryStateOptions {
+    /// How long to wait after the last change before writing the state to the URL. While a
+    /// write is pending, the returned signal already holds the new state.
+    ///
+    /// Defaults to writing as soon as the current task has finished, so that several changes
+    /// made at once only navigate once.
+    pub debounce: Option<Duration>,
+    /// Whether to scroll to the top of the page after the URL has been updated. Defaults to
+    /// `false`.
+    pub scroll: bool,
+}
+
+/// Constructs a signal synchronized with the fields of a [`QueryState`] struct in the URL query.
+///
+/// See [`QueryState`] for how the fields are stored.
+#[track_caller]
+pub fn use_query_state<T>() -> (Memo<T>, SignalSetter<T>)
+where
+    T: QueryState + Clone + Send + Sync + 'static,
+{
+    use_query_state_with_options(QueryStateOptions::default())
+}
+
+/// Constructs a signal synchronized with the fields of a [`QueryState`] struct in the URL query.
+///
+/// This is the same as [`use_query_state`], but allows you to debounce writes to the URL.
+#[track_caller]
+pub fn use_query_state_with_options<T>(
+    options: QueryStateOptions,
+) -> (Memo<T>, SignalSetter<T>)
+where
+    T: QueryState + Clone + Send + Sync + 'static,
+{
+    let query_map = use_query_map();
+    let navigate = use_navigate();
+    let location = use_location();
+
+    let from_url =
+        Memo::new(move |_| query_map.with(|query| T::read_query(query, "")));
+    // a value that has been set, but not written to the URL yet
+    let pending = ArcRwSignal::new(None::<T>);
+    let get = Memo::new({
+        let pending = pending.clone();
+        move |_| pending.get().unwrap_or_else(|| from_url.get())
+    });
+
+    let write = {
+        let pending = pending.clone();
+        move || {
+            let Some(value) = pending.get_untracked() else {
+                return;
+            };
+            let Some(mode) = value.history_mode(&from_url.get_untracked())
+            else {
+                pending.set(None);
+                return;
+            };
+            let path = location.pathname.get_untracked();
+            let hash = location.hash.get_untracked();
+            let mut query = location.query.get_untracked();
+            value.write_query(&mut query, "");
+            let qs = query.to_query_string();
+            pending.set(None);
+            navigate(
+                &format!("{path}{qs}{hash}"),
+                NavigateOptions {
+                    replace: mode == HistoryMode::Replace,
+                    scroll: options.scroll,
+                    ..Default::default()
+                },
+            );
+        }
+    };
+
+    let timeout = StoredValue::new(None::<TimeoutHandle>);
+    let set = SignalSetter::map(move |value: T| {
+        pending.set(Some(value));
+        if let Some(handle) = timeout.get_value() {
+            handle.clear();
+        }
+        let write = write.clone();
+        let delay = options.debounce.unwrap_or_default();
+        timeout.set_value(set_timeout_with_handle(write, delay).ok());
+    });
+
+    (get, set)
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use super::{HistoryMode, QueryState};
+    use crate::params::ParamsMap;
+
+    #[derive(QueryState, Clone, Debug, PartialEq)]
+    #[query(crate = "crate")]
+    struct Filters {
+        #[query(rename = "q", replace)]
+        search: String,
+        tags: Vec<String>,
+        page: u32,
+        #[query(nested)]
+        price: PriceRange,
+        #[query(from_str)]
+        sort: Sort,
+    }
+
+    impl Default for Filters {
+        fn default() -> Self {
+            Self {
+                search: String::new(),
+                tags: Vec::new(),
+                page: 1,
+                price: PriceRange::default(),
+                sort: Sort::Newest,
+            }
+        }
+    }
+
+    #[derive(QueryState, Clone, Debug, Default, PartialEq)]
+    #[query(crate = "crate", replace)]
+    struct PriceRange {
+        min: Option<u32>,
+        #[query(push)]
+        max: Option<u32>,
+    }
+
+    #[derive(QueryState, Clone, Debug, PartialEq)]
+    #[query(crate = "crate")]
+    struct Budget {
+        #[query(nested)]
+        price: PriceRange,
+    }
+
+    impl Default for Budget {
+        fn default() -> Self {
+            Self {
+                price: PriceRange {
+                    min: None,
+                    max: Some(100),
+                },
+            }
+        }
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    enum Sort {
+        Newest,
+        Price,
+    }
+
+    impl std::str::FromStr for Sort {
+        type Err = ();
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match s {
+                "newest" => Ok(Sort::Newest),
+                "price" => Ok(Sort::Price),
+                _ => Err(()),
+            }
+        }
+    }
+
+    impl std::fmt::Display for Sort {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str(match self {
+                Sort::Newest => "newest",
+                Sort::Price => "price",
+            })
+        }
+    }
+
+    #[test]
+    fn reads_fields_and_falls_back_to_defaults() {
+        let mut query = ParamsMap::new();
+        query.insert("q", "red shoes".to_string());
+        query.insert("tags", "a".to_string());
+        query.insert("tags", "b".to_string());
+        query.insert("page", "not a number".to_string());
+        query.insert("price.min", "10".to_string());
+        query.insert("sort", "price".to_string());
+
+        let filters = Filters::read_query(&query, "");
+        assert_eq!(
+            filters,
+            Filters {
+                search: "red shoes".to_string(),
+                tags: vec!["a".to_string(), "b".to_string()],
+                page: 1,
+                price: PriceRange {
+                    min: Some(10),
+                    max: None
+                },
+                sort: Sort::Price,
+            }
+        );
+    }
+
+    #[test]
+    fn omits_defaults_when_writing() {
+        let mut query = ParamsMap::new();
+        query.insert("unrelated", "1".to_string());
+        query.insert("page", "3".to_string());
+
+        let filters = Filters {
+            search: "a&b".to_string(),
+            tags: vec!["x".to_string(), "y".to_string()],
+            price: PriceRange {
+                min: None,
+                max: Some(50),
+            },
+            ..Default::default()
+        };
+        filters.write_query(&mut query, "");
+
+        assert_eq!(query.get_str("unrelated"), Some("1"));
+        assert_eq!(query.get_str("page"), None);
+        assert_eq!(query.get_str("sort"), None);
+        assert_eq!(query.get_str("price.min"), None);
+        assert_eq!(query.get_str("q"), Some("a&b"));
+        assert_eq!(
+            query.get_all("tags"),
+            Some(vec!["x".to_string(), "y".to_string()])
+        );
+        assert_eq!(query.get_str("price.max"), Some("50"));
+        assert_eq!(Filters::read_query(&query, ""), filters);
+    }
+
+    #[test]
+    fn chooses_history_mode_per_field() {
+        let prev = Filters::default();
+        assert_eq!(prev.history_mode(&prev), None);
+
+        let search = Filters {
+            search: "a".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(search.history_mode(&prev), Some(HistoryMode::Replace));
+
+        let page = Filters {
+            page: 2,
+            ..search.clone()
+        };
+        assert_eq!(page.history_mode(&prev), Some(HistoryMode::Push));
+
+        let min = Filters {
+            price: PriceRange {
+                min: Some(1),
+                max: None,
+            },
+            ..Default::default()
+        };
+        assert_eq!(min.history_mode(&prev), Some(HistoryMode::Replace));
+
+        let max = Filters {
+            price: PriceRange {
+                min: None,
+                max: Some(1),
+            },
+            ..Default::default()
+        };
+        assert_eq!(max.history_mode(&prev), Some(HistoryMode::Push));
+    }
+
+    #[test]
+    fn nested_fields_use_the_parent_default() {
+        let mut query = ParamsMap::new();
+        query.insert("price.min", "10".to_string());
+
+        let budget = Budget::read_query(&query, "");
+        assert_eq!(
+            budget.price,
+            PriceRange {
+                min: Some(10),
+                max: Some(100)
+            }
+        );
+
+        let mut query = ParamsMap::new();
+        budget.write_query(&mut query, "");
+        assert_eq!(query.get_str("price.min"), Some("10"));
+        assert_eq!(query.get_str("price.max"), None);
+        assert_eq!(Budget::read_query(&query, ""), budget);
+    }
+}
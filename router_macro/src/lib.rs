@@ -12,6 +12,8 @@ use syn::{
     spanned::Spanned, Block, Ident, ImplItem, ItemImpl, Path, Type, TypePath,
 };
 
+mod query_state;
+
 const RFC3986_UNRESERVED: [char; 4] = ['-', '.', '_', '~'];
 const RFC3986_PCHAR_OTHER: [char; 1] = ['@'];
 
@@ -194,6 +196,20 @@ impl ToTokens for Segments {
     }
 }
 
+/// Derives [`QueryState`] for a struct with named fields, so that it can be stored in the URL
+/// query string.
+///
+/// See [`QueryState`] for the attributes this accepts.
+///
+/// [`QueryState`]: https://docs.rs/leptos_router/latest/leptos_router/query_state/trait.QueryState.html
+#[proc_macro_derive(QueryState, attributes(query))]
+pub fn derive_query_state(input: TokenStream) -> TokenStream {
+    let ast = syn::parse_macro_input!(input as syn::DeriveInput);
+    query_state::query_state_impl(&ast)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
 /// When added to an [`impl LazyRoute`] implementation block, this will automatically
 /// add a [`lazy`] annotation to the `view` method, which will cause the code for the view
 /// to lazy-load concurrently with the `data` being loaded for the route.
@@ -0,0 +1,226 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{
+    spanned::Spanned, Attribute, Data, DataStruct, DeriveInput, Fields, LitStr,
+    Path,
+};
+
+#[derive(Clone, Copy, PartialEq)]
+enum Mode {
+    Push,
+    Replace,
+}
+
+impl Mode {
+    fn to_tokens(self, krate: &Path) -> TokenStream {
+        match self {
+            Mode::Push => quote! { #krate::query_state::HistoryMode::Push },
+            Mode::Replace => {
+                quote! { #krate::query_state::HistoryMode::Replace }
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct StructAttrs {
+    krate: Option<Path>,
+    mode: Option<Mode>,
+}
+
+#[derive(Default)]
+struct FieldAttrs {
+    rename: Option<String>,
+    nested: bool,
+    from_str: bool,
+    mode: Option<Mode>,
+}
+
+fn parse_struct_attrs(attrs: &[Attribute]) -> syn::Result<StructAttrs> {
+    let mut parsed = StructAttrs::default();
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("query")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("crate") {
+                parsed.krate = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+            } else if meta.path.is_ident("push") {
+                parsed.mode = Some(Mode::Push);
+            } else if meta.path.is_ident("replace") {
+                parsed.mode = Some(Mode::Replace);
+            } else {
+                return Err(meta.error(
+                    "expected `crate = \"...\"`, `push`, or `replace`",
+                ));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(parsed)
+}
+
+fn parse_field_attrs(attrs: &[Attribute]) -> syn::Result<FieldAttrs> {
+    let mut parsed = FieldAttrs::default();
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("query")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                parsed.rename = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("nested") {
+                parsed.nested = true;
+            } else if meta.path.is_ident("from_str") {
+                parsed.from_str = true;
+            } else if meta.path.is_ident("push") {
+                parsed.mode = Some(Mode::Push);
+            } else if meta.path.is_ident("replace") {
+                parsed.mode = Some(Mode::Replace);
+            } else {
+                return Err(meta.error(
+                    "expected `rename = \"...\"`, `nested`, `from_str`, \
+                     `push`, or `replace`",
+                ));
+            }
+            Ok(())
+        })?;
+    }
+    if parsed.nested && parsed.from_str {
+        return Err(syn::Error::new(
+            attrs[0].span(),
+            "a field cannot be both `nested` and `from_str`",
+        ));
+    }
+    Ok(parsed)
+}
+
+pub fn query_state_impl(ast: &DeriveInput) -> syn::Result<TokenStream> {
+    let name = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) =
+        ast.generics.split_for_impl();
+    let struct_attrs = parse_struct_attrs(&ast.attrs)?;
+    let krate = struct_attrs
+        .krate
+        .unwrap_or_else(|| syn::parse_quote!(::leptos_router));
+    let struct_mode = struct_attrs.mode.unwrap_or(Mode::Push);
+
+    let Data::Struct(DataStruct {
+        fields: Fields::Named(fields),
+        ..
+    }) = &ast.data
+    else {
+        return Err(syn::Error::new(
+            ast.span(),
+            "QueryState can only be derived for structs with named fields",
+        ));
+    };
+
+    let mut reads = Vec::new();
+    let mut writes = Vec::new();
+    let mut modes = Vec::new();
+    for field in &fields.named {
+        let attrs = parse_field_attrs(&field.attrs)?;
+        let ident = field.ident.as_ref().expect("named field");
+        let ty = &field.ty;
+        let key = attrs.rename.unwrap_or_else(|| {
+            ident.to_string().trim_start_matches("r#").to_string()
+        });
+        let key_var = format_ident!("__key_{}", ident);
+        let key_expr = if attrs.nested {
+            quote! { let #key_var = ::std::format!("{}{}.", prefix, #key); }
+        } else {
+            quote! { let #key_var = ::std::format!("{}{}", prefix, #key); }
+        };
+
+        if attrs.nested {
+            reads.push(quote! {
+                #ident: {
+                    #key_expr
+                    <#ty as #krate::query_state::QueryState>::read_query_with_default(
+                        query,
+                        &#key_var,
+                        default.#ident,
+                    )
+                }
+            });
+            writes.push(quote! {
+                {
+                    #key_expr
+                    #krate::query_state::QueryState::write_query_with_default(
+                        &self.#ident,
+                        query,
+                        &#key_var,
+                        &default.#ident,
+                    );
+                }
+            });
+            modes.push(quote! {
+                mode = #krate::query_state::HistoryMode::merge(
+                    mode,
+                    #krate::query_state::QueryState::history_mode(&self.#ident, &prev.#ident),
+                );
+            });
+        } else {
+            let (read_fn, write_fn) = if attrs.from_str {
+                (
+                    quote! { #krate::query_state::__read_from_str::<#ty> },
+                    quote! { #krate::query_state::__write_from_str::<#ty> },
+                )
+            } else {
+                (
+                    quote! { #krate::query_state::__read_value::<#ty> },
+                    quote! { #krate::query_state::__write_value::<#ty> },
+                )
+            };
+            reads.push(quote! {
+                #ident: {
+                    #key_expr
+                    #read_fn(query, &#key_var).unwrap_or(default.#ident)
+                }
+            });
+            writes.push(quote! {
+                {
+                    #key_expr
+                    #write_fn(query, #key_var, &self.#ident, &default.#ident);
+                }
+            });
+            let field_mode =
+                attrs.mode.unwrap_or(struct_mode).to_tokens(&krate);
+            modes.push(quote! {
+                if self.#ident != prev.#ident {
+                    mode = #krate::query_state::HistoryMode::merge(
+                        mode,
+                        ::core::option::Option::Some(#field_mode),
+                    );
+                }
+            });
+        }
+    }
+
+    Ok(quote! {
+        impl #impl_generics #krate::query_state::QueryState for #name #ty_generics #where_clause {
+            #[allow(unused_variables, clippy::needless_update)]
+            fn read_query_with_default(
+                query: &#krate::params::ParamsMap,
+                prefix: &str,
+                default: Self,
+            ) -> Self {
+                Self {
+                    #(#reads,)*
+                }
+            }
+
+            #[allow(unused_variables)]
+            fn write_query_with_default(
+                &self,
+                query: &mut #krate::params::ParamsMap,
+                prefix: &str,
+                default: &Self,
+            ) {
+                #(#writes)*
+            }
+
+            #[allow(unused_mut)]
+            fn history_mode(&self, prev: &Self) -> ::core::option::Option<#krate::query_state::HistoryMode> {
+                let mut mode = ::core::option::Option::None;
+                #(#modes)*
+                mode
+            }
+        }
+    })
+}
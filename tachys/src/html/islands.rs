@@ -10,8 +10,17 @@ use crate::{
 pub struct Island<View> {
     component: &'static str,
     props_json: String,
+    shared_props: Option<SharedProps>,
+    contexts: Vec<(&'static str, String)>,
+    hydrate: Option<&'static str>,
     view: View,
 }
+
+/// Props that are sent in a `<script>` that can be shared by several islands.
+struct SharedProps {
+    key: String,
+    payload: Option<String>,
+}
 const ISLAND_TAG: &str = "leptos-island";
 const ISLAND_CHILDREN_TAG: &str = "leptos-children";
 
@@ -21,6 +30,9 @@ impl<View> Island<View> {
         Island {
             component,
             props_json: String::new(),
+            shared_props: None,
+            contexts: Vec::new(),
+            hydrate: None,
             view,
         }
     }
@@ -31,16 +43,79 @@ impl<View> Island<View> {
         self
     }
 
-    fn open_tag(component: &'static str, props: &str, buf: &mut String) {
+    /// Adds serialized component props that are shared with other islands, as a key and, if
+    /// the props have not been included in the page yet, their encoded payload.
+    ///
+    /// The payload is rendered in a `<script>` before the island, which refers to it by its key.
+    pub fn with_shared_props(
+        mut self,
+        (key, payload): (String, Option<String>),
+    ) -> Self {
+        self.shared_props = Some(SharedProps { key, payload });
+        self
+    }
+
+    /// Adds serialized contexts that are shared by every island in the page, as pairs of their
+    /// keys and values. They are rendered in `<script>`s before the island.
+    pub fn with_contexts(
+        mut self,
+        contexts: Vec<(&'static str, String)>,
+    ) -> Self {
+        self.contexts = contexts;
+        self
+    }
+
+    /// Sets the strategy the island loader uses to decide when to hydrate this island:
+    /// `"idle"` (the default), `"visible"`, `"interaction"`, or `"media(<query>)"`.
+    pub fn with_hydrate(mut self, hydrate: &'static str) -> Self {
+        self.hydrate = Some(hydrate);
+        self
+    }
+
+    fn open_tag(&self, buf: &mut String) {
+        for (key, payload) in &self.contexts {
+            push_script(
+                buf,
+                "application/leptos-island-context",
+                "data-island-context",
+                key,
+                payload,
+            );
+        }
+        if let Some(SharedProps {
+            key,
+            payload: Some(payload),
+        }) = &self.shared_props
+        {
+            push_script(
+                buf,
+                "application/leptos-island-props",
+                "data-island-props",
+                key,
+                payload,
+            );
+        }
         buf.push('<');
         buf.push_str(ISLAND_TAG);
         buf.push(' ');
         buf.push_str("data-component=\"");
-        buf.push_str(component);
+        buf.push_str(self.component);
         buf.push('"');
-        if !props.is_empty() {
+        if !self.props_json.is_empty() {
             buf.push_str(" data-props=\"");
-            buf.push_str(&html_escape::encode_double_quoted_attribute(&props));
+            buf.push_str(&html_escape::encode_double_quoted_attribute(
+                &self.props_json,
+            ));
+            buf.push('"');
+        }
+        if let Some(SharedProps { key, .. }) = &self.shared_props {
+            buf.push_str(" data-props-ref=\"");
+            buf.push_str(&html_escape::encode_double_quoted_attribute(key));
+            buf.push('"');
+        }
+        if let Some(hydrate) = self.hydrate {
+            buf.push_str(" data-hydrate=\"");
+            buf.push_str(&html_escape::encode_double_quoted_attribute(hydrate));
             buf.push('"');
         }
         buf.push('>');
@@ -85,11 +160,17 @@ where
         let Island {
             component,
             props_json,
+            shared_props,
+            contexts,
+            hydrate,
             view,
         } = self;
         Island {
             component,
             props_json,
+            shared_props,
+            contexts,
+            hydrate,
             view: view.add_any_attr(attr),
         }
     }
@@ -115,11 +196,17 @@ where
         let Island {
             component,
             props_json,
+            shared_props,
+            contexts,
+            hydrate,
             view,
         } = self;
         Island {
             component,
             props_json,
+            shared_props,
+            contexts,
+            hydrate,
             view: view.resolve().await,
         }
     }
@@ -131,7 +218,7 @@ where
         escape: bool,
         mark_branches: bool,
     ) {
-        Self::open_tag(self.component, &self.props_json, buf);
+        self.open_tag(buf);
         self.view
             .to_html_with_buf(buf, position, escape, mark_branches);
         Self::close_tag(buf);
@@ -148,7 +235,7 @@ where
     {
         // insert the opening tag synchronously
         let mut tag = String::new();
-        Self::open_tag(self.component, &self.props_json, &mut tag);
+        self.open_tag(&mut tag);
         buf.push_sync(&tag);
 
         // streaming render for the view
@@ -181,6 +268,28 @@ where
     }
 }
 
+/// Renders a payload in a `<script>` that is never run, but can be read by the island loader or
+/// by islands while they are hydrating.
+///
+/// The payload is escaped as HTML text, so that it can't close the `<script>` early.
+fn push_script(
+    buf: &mut String,
+    ty: &str,
+    key_attr: &str,
+    key: &str,
+    payload: &str,
+) {
+    buf.push_str("<script type=\"");
+    buf.push_str(ty);
+    buf.push_str("\" ");
+    buf.push_str(key_attr);
+    buf.push_str("=\"");
+    buf.push_str(&html_escape::encode_double_quoted_attribute(key));
+    buf.push_str("\">");
+    buf.push_str(&html_escape::encode_text(payload));
+    buf.push_str("</script>");
+}
+
 /// The children that will be projected into an [`Island`].
 pub struct IslandChildren<View> {
     view: View,